@@ -0,0 +1,5 @@
+pub mod rpc;
+pub mod server;
+
+pub use rpc::RpcContext;
+pub use server::{local_api_port_from_env, LocalApiState};
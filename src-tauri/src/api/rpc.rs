@@ -0,0 +1,227 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::contracts::{CheckImageVersionRequest, DeployStepRequest};
+use crate::deploy::execute_deploy_step_internal;
+use crate::docker::{execute_docker_action, DOCKER_ACTION_TIMEOUT_MS};
+use crate::runtime::{run_blocking, with_timing_async, AppRuntimeState};
+use crate::system::load_system_snapshot;
+use crate::tools::{detect_tools_cached, load_tool_catalog, ToolCacheState};
+use crate::version::{check_image_version, VersionRuntimeState};
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// 结果无法序列化等服务端自身的故障
+pub const INTERNAL_ERROR: i64 = -32603;
+/// 实现自定义错误码：方法存在但已在设置中禁用
+pub const METHOD_DISABLED: i64 = -32001;
+
+/// 可通过本地 API 调用的方法：(名称, 是否会改变本机状态)
+pub const API_METHODS: [(&str, bool); 5] = [
+    ("detect_dev_tools", false),
+    ("get_system_snapshot", false),
+    ("check_image_version", false),
+    ("run_docker_action", true),
+    ("execute_deploy_step", true),
+];
+
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: Option<String>,
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION, id, result: Some(result), error: None }
+    }
+
+    pub fn failure(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION,
+            id,
+            result: None,
+            error: Some(RpcError { code, message: message.into() }),
+        }
+    }
+}
+
+/// 方法执行所需的共享状态
+#[derive(Clone)]
+pub struct RpcContext {
+    pub runtime_state: AppRuntimeState,
+    pub version_state: VersionRuntimeState,
+    pub tool_cache: ToolCacheState,
+}
+
+/// 参数与 Tauri invoke 的参数对象保持一致，如 `{"action": "ps"}`、`{"request": {...}}`
+#[derive(Deserialize)]
+struct DockerActionParams {
+    action: String,
+    target: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DeployStepParams {
+    request: DeployStepRequest,
+}
+
+#[derive(Deserialize)]
+struct CheckImageVersionParams {
+    request: CheckImageVersionRequest,
+}
+
+pub fn parse_rpc_request(body: &[u8]) -> Result<RpcRequest, RpcResponse> {
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|error| RpcResponse::failure(Value::Null, PARSE_ERROR, format!("JSON 解析失败: {}", error)))?;
+
+    if value.is_array() {
+        return Err(RpcResponse::failure(Value::Null, INVALID_REQUEST, "不支持批量请求"));
+    }
+
+    let request: RpcRequest = serde_json::from_value(value)
+        .map_err(|error| RpcResponse::failure(Value::Null, INVALID_REQUEST, format!("请求格式无效: {}", error)))?;
+
+    if request.jsonrpc.as_deref() != Some(JSONRPC_VERSION) {
+        return Err(RpcResponse::failure(request.id, INVALID_REQUEST, "jsonrpc 字段必须为 \"2.0\""));
+    }
+
+    Ok(request)
+}
+
+pub fn is_known_method(method: &str) -> bool {
+    API_METHODS.iter().any(|(name, _)| *name == method)
+}
+
+/// 执行已通过鉴权与启用检查的方法，返回值为 UI 同款 CommandResponse 的 JSON
+pub async fn dispatch(request: RpcRequest, context: &RpcContext) -> RpcResponse {
+    let id = request.id;
+    let result = match request.method.as_str() {
+        "detect_dev_tools" => {
+            let cache = context.tool_cache.clone();
+            to_value(
+                with_timing_async(async move {
+                    run_blocking(move || detect_tools_cached(&load_tool_catalog(None).specs, &cache, false)).await
+                })
+                .await,
+            )
+        }
+        "get_system_snapshot" => {
+            let runtime_state = context.runtime_state.clone();
+            to_value(
+                with_timing_async(async move { run_blocking(move || Ok(load_system_snapshot(&runtime_state))).await })
+                    .await,
+            )
+        }
+        "run_docker_action" => match parse_params::<DockerActionParams>(request.params) {
+            Ok(params) => to_value(
+                with_timing_async(async move {
+                    run_blocking(move || {
+                        execute_docker_action(&params.action, params.target.as_deref(), DOCKER_ACTION_TIMEOUT_MS)
+                    })
+                    .await
+                })
+                .await,
+            ),
+            Err(message) => return RpcResponse::failure(id, INVALID_PARAMS, message),
+        },
+        "execute_deploy_step" => match parse_params::<DeployStepParams>(request.params) {
+            Ok(params) => to_value(
+                with_timing_async(async move {
+                    run_blocking(move || execute_deploy_step_internal(&params.request)).await
+                })
+                .await,
+            ),
+            Err(message) => return RpcResponse::failure(id, INVALID_PARAMS, message),
+        },
+        "check_image_version" => match parse_params::<CheckImageVersionParams>(request.params) {
+            Ok(params) => {
+                let version_state = context.version_state.clone();
+                to_value(
+                    with_timing_async(async move {
+                        check_image_version(params.request, &version_state)
+                            .await
+                            .map_err(|error| error.user_message())
+                    })
+                    .await,
+                )
+            }
+            Err(message) => return RpcResponse::failure(id, INVALID_PARAMS, message),
+        },
+        other => return RpcResponse::failure(id, METHOD_NOT_FOUND, format!("未知方法: {}", other)),
+    };
+
+    match result {
+        Ok(value) => RpcResponse::success(id, value),
+        Err(message) => RpcResponse::failure(id, INTERNAL_ERROR, message),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, String> {
+    serde_json::from_value(params).map_err(|error| format!("参数无效: {}", error))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|error| format!("序列化结果失败: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_batches_and_wrong_version() {
+        let error = parse_rpc_request(b"[]").unwrap_err();
+        assert_eq!(error.error.unwrap().code, INVALID_REQUEST);
+
+        let error = parse_rpc_request(br#"{"jsonrpc":"1.0","id":7,"method":"detect_dev_tools"}"#).unwrap_err();
+        assert_eq!(error.id, Value::from(7));
+        assert_eq!(error.error.unwrap().code, INVALID_REQUEST);
+
+        let error = parse_rpc_request(b"{").unwrap_err();
+        assert_eq!(error.error.unwrap().code, PARSE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn dispatch_reports_unknown_method_and_bad_params() {
+        let context = RpcContext {
+            runtime_state: AppRuntimeState::default(),
+            version_state: VersionRuntimeState::new(),
+            tool_cache: ToolCacheState::default(),
+        };
+
+        let request = parse_rpc_request(br#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#).unwrap();
+        let response = dispatch(request, &context).await;
+        assert_eq!(response.error.unwrap().code, METHOD_NOT_FOUND);
+
+        let request =
+            parse_rpc_request(br#"{"jsonrpc":"2.0","id":2,"method":"run_docker_action","params":{}}"#).unwrap();
+        let response = dispatch(request, &context).await;
+        assert_eq!(response.error.unwrap().code, INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::contracts::{LocalApiMethodState, LocalApiStatus};
+use crate::local_http::{loopback_addr, read_http_request, write_http_response, HttpRequest, HttpResponse};
+use crate::runtime::current_timestamp_ms;
+
+use super::rpc::{dispatch, is_known_method, parse_rpc_request, RpcContext, RpcResponse, API_METHODS, METHOD_DISABLED};
+
+pub const LOCAL_API_DEFAULT_PORT: u16 = 9465;
+/// 设置该环境变量即在启动时开启本地 API（值为端口号）
+pub const LOCAL_API_PORT_ENV: &str = "DEVENVPROBE_API_PORT";
+/// 固定访问令牌，便于脚本预先配置；未设置时每次启动随机生成
+pub const LOCAL_API_TOKEN_ENV: &str = "DEVENVPROBE_API_TOKEN";
+
+const TOKEN_BYTES: usize = 32;
+
+struct LocalApiSettings {
+    token: String,
+    enabled: BTreeMap<&'static str, bool>,
+}
+
+struct RunningApi {
+    port: u16,
+    started_at_ms: u64,
+    shutdown: oneshot::Sender<()>,
+}
+
+#[derive(Clone)]
+pub struct LocalApiState {
+    running: Arc<Mutex<Option<RunningApi>>>,
+    settings: Arc<RwLock<LocalApiSettings>>,
+}
+
+impl Default for LocalApiState {
+    fn default() -> Self {
+        let token = std::env::var(LOCAL_API_TOKEN_ENV)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            // 随机源不可用时留空，start 会再次尝试生成并在失败时拒绝启动
+            .or_else(|| generate_token().ok())
+            .unwrap_or_default();
+
+        // 只读方法默认开启，会执行 docker / 部署命令的方法需要手动开启
+        let enabled = API_METHODS
+            .iter()
+            .map(|(method, mutating)| (*method, !*mutating))
+            .collect();
+
+        Self {
+            running: Arc::new(Mutex::new(None)),
+            settings: Arc::new(RwLock::new(LocalApiSettings { token, enabled })),
+        }
+    }
+}
+
+impl LocalApiState {
+    pub fn status(&self) -> LocalApiStatus {
+        let settings = self.settings.read().unwrap();
+        let methods = API_METHODS
+            .iter()
+            .map(|(method, mutating)| LocalApiMethodState {
+                method: method.to_string(),
+                enabled: settings.enabled.get(method).copied().unwrap_or(false),
+                mutating: *mutating,
+            })
+            .collect();
+
+        let running = self.running.lock().unwrap();
+        LocalApiStatus {
+            running: running.is_some(),
+            port: running.as_ref().map(|api| api.port),
+            endpoint: running
+                .as_ref()
+                .map(|api| format!("http://{}/rpc", loopback_addr(api.port))),
+            token: settings.token.clone(),
+            methods,
+            started_at_ms: running.as_ref().map(|api| api.started_at_ms),
+        }
+    }
+
+    /// 在 127.0.0.1 上启动 JSON-RPC 监听；已在运行时直接返回当前状态
+    pub async fn start(&self, port: Option<u16>, context: RpcContext) -> Result<LocalApiStatus, String> {
+        if self.running.lock().unwrap().is_some() {
+            return Ok(self.status());
+        }
+
+        {
+            let mut settings = self.settings.write().unwrap();
+            if settings.token.is_empty() {
+                settings.token = generate_token()?;
+            }
+        }
+
+        let listener = TcpListener::bind(loopback_addr(port.unwrap_or(LOCAL_API_DEFAULT_PORT)))
+            .await
+            .map_err(|error| format!("本地 API 端口绑定失败: {}", error))?;
+        let bound_port = listener
+            .local_addr()
+            .map_err(|error| error.to_string())?
+            .port();
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+        let settings = self.settings.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => {
+                        let Ok((mut stream, _)) = accepted else {
+                            continue;
+                        };
+                        let settings = settings.clone();
+                        let context = context.clone();
+                        tokio::spawn(async move {
+                            let response = match read_http_request(&mut stream).await {
+                                Ok(request) => handle_request(&request, &settings, &context).await,
+                                Err(error) => HttpResponse::text(400, &error),
+                            };
+                            let _ = write_http_response(&mut stream, &response).await;
+                        });
+                    }
+                }
+            }
+        });
+
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            let _ = shutdown_tx.send(());
+            drop(running);
+            return Ok(self.status());
+        }
+
+        *running = Some(RunningApi {
+            port: bound_port,
+            started_at_ms: current_timestamp_ms(),
+            shutdown: shutdown_tx,
+        });
+        drop(running);
+
+        Ok(self.status())
+    }
+
+    pub fn stop(&self) -> LocalApiStatus {
+        if let Some(api) = self.running.lock().unwrap().take() {
+            let _ = api.shutdown.send(());
+        }
+
+        self.status()
+    }
+
+    /// 运行中修改立即生效，无需重启监听
+    pub fn set_method_enabled(&self, method: &str, enabled: bool) -> Result<LocalApiStatus, String> {
+        let Some((name, _)) = API_METHODS.iter().find(|(name, _)| *name == method) else {
+            return Err(format!("未知的本地 API 方法: {}", method));
+        };
+
+        self.settings.write().unwrap().enabled.insert(name, enabled);
+        Ok(self.status())
+    }
+
+    /// 重新生成令牌，已持有旧令牌的调用方随即失效
+    pub fn regenerate_token(&self) -> Result<LocalApiStatus, String> {
+        let token = generate_token()?;
+        self.settings.write().unwrap().token = token;
+        Ok(self.status())
+    }
+}
+
+async fn handle_request(request: &HttpRequest, settings: &RwLock<LocalApiSettings>, context: &RpcContext) -> HttpResponse {
+    // 浏览器页面发起的跨站请求一律拒绝，本地 API 只面向脚本与插件
+    if request.header("origin").is_some() {
+        return HttpResponse::text(403, "不接受浏览器跨站请求");
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => HttpResponse::text(200, "DevEnvProbe local API: POST /rpc (JSON-RPC 2.0, Bearer token)"),
+        ("POST", "/rpc") => {
+            let authorized = {
+                let settings = settings.read().unwrap();
+                !settings.token.is_empty()
+                    && request
+                        .bearer_token()
+                        .is_some_and(|token| constant_time_eq(token.as_bytes(), settings.token.as_bytes()))
+            };
+            if !authorized {
+                return HttpResponse::text(401, "缺少或无效的访问令牌");
+            }
+
+            let rpc_request = match parse_rpc_request(&request.body) {
+                Ok(rpc_request) => rpc_request,
+                Err(response) => return HttpResponse::json(200, &response),
+            };
+
+            let enabled = settings
+                .read()
+                .unwrap()
+                .enabled
+                .get(rpc_request.method.as_str())
+                .copied()
+                .unwrap_or(false);
+            if is_known_method(&rpc_request.method) && !enabled {
+                let response = RpcResponse::failure(
+                    rpc_request.id,
+                    METHOD_DISABLED,
+                    format!("方法 {} 已在设置中禁用", rpc_request.method),
+                );
+                return HttpResponse::json(200, &response);
+            }
+
+            HttpResponse::json(200, &dispatch(rpc_request, context).await)
+        }
+        (_, "/rpc") => HttpResponse::text(405, "仅支持 POST"),
+        _ => HttpResponse::text(404, "not found"),
+    }
+}
+
+/// 令牌只能来自系统随机源，不可用时报错而不是退化为可猜测的值
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes).map_err(|error| format!("系统随机源不可用，无法生成本地 API 访问令牌: {}", error))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    left.iter().zip(right).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// 读取启动时的本地 API 端口配置；未设置或非法时保持关闭
+pub fn local_api_port_from_env() -> Option<u16> {
+    std::env::var(LOCAL_API_PORT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u16>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_http::parse_http_request;
+    use crate::runtime::AppRuntimeState;
+    use crate::tools::ToolCacheState;
+    use crate::version::VersionRuntimeState;
+
+    fn context() -> RpcContext {
+        RpcContext {
+            runtime_state: AppRuntimeState::default(),
+            version_state: VersionRuntimeState::new(),
+            tool_cache: ToolCacheState::default(),
+        }
+    }
+
+    fn request(raw: &str) -> HttpRequest {
+        parse_http_request(raw.as_bytes()).unwrap().unwrap().0
+    }
+
+    #[tokio::test]
+    async fn rpc_requires_matching_bearer_token() {
+        let state = LocalApiState::default();
+        let token = state.status().token;
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"run_docker_action","params":{"action":"ps"}}"#;
+
+        let unauthorized = request(&format!(
+            "POST /rpc HTTP/1.1\r\nAuthorization: Bearer wrong\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        let response = handle_request(&unauthorized, &state.settings, &context()).await;
+        assert_eq!(response.status, 401);
+
+        let authorized = request(&format!(
+            "POST /rpc HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+            token,
+            body.len(),
+            body
+        ));
+        let response = handle_request(&authorized, &state.settings, &context()).await;
+        assert_eq!(response.status, 200);
+        let payload: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(payload["error"]["code"], METHOD_DISABLED);
+    }
+
+    #[tokio::test]
+    async fn missing_token_never_authorizes() {
+        let state = LocalApiState::default();
+        state.settings.write().unwrap().token.clear();
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"detect_dev_tools"}"#;
+
+        let empty = request(&format!(
+            "POST /rpc HTTP/1.1\r\nAuthorization: Bearer \r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        let response = handle_request(&empty, &state.settings, &context()).await;
+        assert_eq!(response.status, 401);
+    }
+
+    #[test]
+    fn mutating_methods_start_disabled_and_can_be_toggled() {
+        let state = LocalApiState::default();
+        let status = state.status();
+        assert_eq!(status.token.len(), TOKEN_BYTES * 2);
+        assert!(status.methods.iter().all(|method| method.enabled != method.mutating));
+
+        let status = state.set_method_enabled("execute_deploy_step", true).unwrap();
+        assert!(status.methods.iter().any(|method| method.method == "execute_deploy_step" && method.enabled));
+        assert!(state.set_method_enabled("rm_rf", true).is_err());
+
+        let previous = status.token;
+        assert_ne!(state.regenerate_token().unwrap().token, previous);
+    }
+}
@@ -0,0 +1,384 @@
+pub mod schedule;
+
+pub use schedule::{run_baseline_schedule, BaselineScheduleState, BASELINE_DRIFT_EVENT};
+
+use std::path::Path;
+
+use semver::Version;
+use serde_json::Value;
+
+use crate::contracts::{BaselineDriftEntry, BaselineDriftReport, RequirementCheck, ToolStatus};
+use crate::process_runner::run_command_with_timeout;
+use crate::project::requirements::{
+    evaluate_requirement, parse_version_range, Requirement, REQUIREMENT_MISSING, REQUIREMENT_OUTDATED, REQUIREMENT_PASS,
+};
+use crate::runtime::current_timestamp_ms;
+use crate::tools::{detect_tools_cached, find_tool_spec, load_tool_catalog, ToolCacheState, ToolSpec};
+
+pub const BASELINE_FETCH_TIMEOUT_MS: u64 = 15_000;
+const BASELINE_GIT_TIMEOUT_MS: u64 = 5_000;
+
+pub const DRIFT_COMPLIANT: &str = "compliant";
+pub const DRIFT_MISSING: &str = "missing";
+pub const DRIFT_TOO_OLD: &str = "too_old";
+pub const DRIFT_TOO_NEW: &str = "too_new";
+/// 已安装但被基线禁止，或 strict 基线中未列出的目录工具
+pub const DRIFT_UNEXPECTED: &str = "unexpected";
+/// [env] / [git] 的实际值与期望值不同
+pub const DRIFT_MISMATCH: &str = "mismatch";
+/// 工具不在目录中或版本无法解析，无法判断是否符合
+pub const DRIFT_UNKNOWN: &str = "unknown";
+
+pub const BASELINE_KIND_TOOL: &str = "tool";
+pub const BASELINE_KIND_ENV: &str = "env";
+pub const BASELINE_KIND_GIT: &str = "git";
+
+/// 团队基线：[tools] 写版本范围（与项目要求相同的语法，写 false 表示禁止安装），[env] / [git] 写期望值
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Baseline {
+    pub name: Option<String>,
+    /// 为 true 时，已安装但未在 [tools] 中列出的目录工具也算漂移
+    pub strict: bool,
+    pub tools: Vec<Requirement>,
+    pub forbidden: Vec<String>,
+    pub settings: Vec<BaselineSetting>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineSetting {
+    pub kind: &'static str,
+    pub key: String,
+    pub expected: String,
+}
+
+/// 基线来源可以是本地文件，也可以是 http(s) 地址
+pub async fn load_baseline(source: &str) -> Result<Baseline, String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err("基线来源不能为空".to_string());
+    }
+
+    let content = if source.starts_with("http://") || source.starts_with("https://") {
+        fetch_baseline(source).await?
+    } else {
+        tokio::fs::read_to_string(Path::new(source))
+            .await
+            .map_err(|error| format!("读取基线文件失败 {}: {}", source, error))?
+    };
+    parse_baseline(&content)
+}
+
+async fn fetch_baseline(url: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(BASELINE_FETCH_TIMEOUT_MS))
+        .user_agent("DevEnvProbe/1.0")
+        .build()
+        .map_err(|error| format!("创建 HTTP 客户端失败: {}", error))?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|error| format!("下载基线失败 {}: {}", url, error))?;
+    if !response.status().is_success() {
+        return Err(format!("下载基线失败 {}: HTTP {}", url, response.status()));
+    }
+    response.text().await.map_err(|error| format!("读取基线内容失败: {}", error))
+}
+
+/// 内容以 `{` 开头时按 JSON 解析，否则按 TOML 解析
+pub fn parse_baseline(content: &str) -> Result<Baseline, String> {
+    let document: Value = if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|error| format!("基线 JSON 解析失败: {}", error))?
+    } else {
+        toml::from_str(content).map_err(|error| format!("基线 TOML 解析失败: {}", error))?
+    };
+
+    let mut baseline = Baseline {
+        name: document.get("name").and_then(Value::as_str).map(ToString::to_string),
+        strict: document.get("strict").and_then(Value::as_bool).unwrap_or(false),
+        ..Baseline::default()
+    };
+
+    for (tool, value) in section(&document, "tools")? {
+        if value == &Value::Bool(false) {
+            baseline.forbidden.push(tool.clone());
+            continue;
+        }
+        baseline.tools.push(Requirement {
+            tool: tool.clone(),
+            range: value
+                .as_str()
+                .map(|range| range.trim().to_string())
+                .ok_or_else(|| "版本范围必须是字符串".to_string()),
+        });
+    }
+
+    for (kind, name) in [(BASELINE_KIND_ENV, "env"), (BASELINE_KIND_GIT, "git")] {
+        for (key, value) in section(&document, name)? {
+            let expected = match value {
+                Value::String(text) => text.clone(),
+                Value::Bool(_) | Value::Number(_) => value.to_string(),
+                _ => return Err(format!("[{}] 中 {} 的期望值必须是字符串", name, key)),
+            };
+            baseline.settings.push(BaselineSetting {
+                kind,
+                key: key.clone(),
+                expected,
+            });
+        }
+    }
+
+    if baseline.tools.is_empty() && baseline.forbidden.is_empty() && baseline.settings.is_empty() {
+        return Err("基线中没有任何 [tools]、[env] 或 [git] 条目".to_string());
+    }
+    Ok(baseline)
+}
+
+fn section<'a>(document: &'a Value, name: &str) -> Result<Vec<(&'a String, &'a Value)>, String> {
+    match document.get(name) {
+        None => Ok(Vec::new()),
+        Some(Value::Object(table)) => Ok(table.iter().collect()),
+        Some(_) => Err(format!("{} 必须是表（TOML 中写作 [{}]）", name, name)),
+    }
+}
+
+/// 加载基线后与 detect_dev_tools 的结果（含缓存）逐项比较
+pub async fn check_baseline_drift_internal(source: &str, cache: ToolCacheState) -> Result<BaselineDriftReport, String> {
+    let baseline = load_baseline(source).await?;
+    let source = source.trim().to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let catalog = load_tool_catalog(None);
+        let tools = detect_tools_cached(&catalog.specs, &cache, false)?;
+        let entries = evaluate_baseline(&baseline, &catalog.specs, &tools, read_setting);
+        Ok(build_drift_report(source, baseline.name, entries))
+    })
+    .await
+    .map_err(|error| format!("基线检查任务失败: {}", error))?
+}
+
+pub fn build_drift_report(
+    source: String,
+    baseline_name: Option<String>,
+    entries: Vec<BaselineDriftEntry>,
+) -> BaselineDriftReport {
+    let drift_count = entries.iter().filter(|entry| entry.status != DRIFT_COMPLIANT).count();
+    BaselineDriftReport {
+        source,
+        baseline_name,
+        compliant: drift_count == 0,
+        drift_count,
+        entries,
+        checked_at_ms: current_timestamp_ms(),
+    }
+}
+
+pub fn evaluate_baseline(
+    baseline: &Baseline,
+    specs: &[ToolSpec],
+    tools: &[ToolStatus],
+    read_setting: impl Fn(&BaselineSetting) -> Option<String>,
+) -> Vec<BaselineDriftEntry> {
+    let tool_entries = baseline.tools.iter().map(|requirement| {
+        let spec = find_tool_spec(specs, &requirement.tool);
+        let status = spec.and_then(|spec| tools.iter().find(|status| status.name == spec.name));
+        tool_entry(evaluate_requirement(requirement, spec, status))
+    });
+
+    let setting_entries = baseline.settings.iter().map(|setting| {
+        let actual = read_setting(setting);
+        let status = match actual.as_deref() {
+            None => DRIFT_MISSING,
+            Some(value) if setting_matches(setting, value) => DRIFT_COMPLIANT,
+            Some(_) => DRIFT_MISMATCH,
+        };
+        BaselineDriftEntry {
+            kind: setting.kind.to_string(),
+            key: setting.key.clone(),
+            expected: setting.expected.clone(),
+            message: (status == DRIFT_MISMATCH).then(|| format!("期望 {}", setting.expected)),
+            actual,
+            status: status.to_string(),
+            tool_name: None,
+            install_key: None,
+        }
+    });
+
+    let listed: Vec<&str> = baseline
+        .tools
+        .iter()
+        .filter_map(|requirement| find_tool_spec(specs, &requirement.tool))
+        .map(|spec| spec.name.as_str())
+        .collect();
+    let unexpected_entries = specs.iter().filter_map(|spec| {
+        let status = tools.iter().find(|status| status.name == spec.name && status.installed)?;
+        let forbidden = baseline
+            .forbidden
+            .iter()
+            .find(|key| find_tool_spec(specs, key).is_some_and(|found| found.name == spec.name));
+        let expected = match forbidden {
+            Some(_) => "禁止安装",
+            None if baseline.strict && !listed.contains(&spec.name.as_str()) => "未列入基线",
+            None => return None,
+        };
+        Some(BaselineDriftEntry {
+            kind: BASELINE_KIND_TOOL.to_string(),
+            key: forbidden.cloned().unwrap_or_else(|| spec.command.clone()),
+            expected: expected.to_string(),
+            actual: status.version.clone(),
+            status: DRIFT_UNEXPECTED.to_string(),
+            tool_name: Some(spec.name.clone()),
+            install_key: spec.install_key.clone(),
+            message: Some(format!("{} 已安装，但基线{}", spec.name, expected)),
+        })
+    });
+
+    tool_entries.chain(unexpected_entries).chain(setting_entries).collect()
+}
+
+/// 版本在范围之上为过新；目录中没有该工具或版本无法解析时为 unknown
+fn tool_entry(check: RequirementCheck) -> BaselineDriftEntry {
+    let status = match check.status.as_str() {
+        REQUIREMENT_PASS => DRIFT_COMPLIANT,
+        REQUIREMENT_MISSING => DRIFT_MISSING,
+        REQUIREMENT_OUTDATED => DRIFT_TOO_OLD,
+        // 不满足且不低于下限，说明版本高于范围
+        _ if is_comparable(&check) => DRIFT_TOO_NEW,
+        _ => DRIFT_UNKNOWN,
+    };
+
+    BaselineDriftEntry {
+        kind: BASELINE_KIND_TOOL.to_string(),
+        key: check.tool,
+        expected: check.range,
+        actual: check.installed_version,
+        status: status.to_string(),
+        tool_name: check.tool_name,
+        install_key: check.install_key,
+        message: check.message,
+    }
+}
+
+fn is_comparable(check: &RequirementCheck) -> bool {
+    check.tool_name.is_some()
+        && check.installed_version.as_deref().is_some_and(|version| Version::parse(version).is_ok())
+        && parse_version_range(&check.range).is_ok()
+}
+
+/// Git 布尔配置大小写不敏感，其余按原值比较
+fn setting_matches(setting: &BaselineSetting, actual: &str) -> bool {
+    if setting.kind == BASELINE_KIND_GIT {
+        actual.trim().eq_ignore_ascii_case(setting.expected.trim())
+    } else {
+        actual == setting.expected
+    }
+}
+
+fn read_setting(setting: &BaselineSetting) -> Option<String> {
+    match setting.kind {
+        BASELINE_KIND_ENV => std::env::var(&setting.key).ok(),
+        _ => run_command_with_timeout("git", &["config", "--get", &setting.key], BASELINE_GIT_TIMEOUT_MS)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(spec: &ToolSpec, semver: &str) -> ToolStatus {
+        ToolStatus {
+            category: spec.category.clone(),
+            install_key: spec.install_key.clone(),
+            ..ToolStatus::for_test(&spec.name, &spec.command, Some(semver)).with_semver(semver)
+        }
+    }
+
+    #[test]
+    fn baseline_parses_toml_and_json() {
+        let baseline = parse_baseline(
+            "name = \"backend\"\n[tools]\nnode = \">=20 <23\"\n[env]\nGOPROXY = \"direct\"\n[git]\n\"core.autocrlf\" = false\n",
+        )
+        .unwrap();
+        assert_eq!(baseline.name.as_deref(), Some("backend"));
+        assert_eq!(baseline.tools[0].range, Ok(">=20 <23".to_string()));
+        assert_eq!(baseline.settings[1].expected, "false");
+
+        let json = parse_baseline(r#"{"tools": {"git": ">=2.40"}}"#).unwrap();
+        assert_eq!(json.tools[0].tool, "git");
+        assert!(parse_baseline("name = \"empty\"").is_err());
+    }
+
+    #[test]
+    fn drift_is_classified_per_entry() {
+        let specs = vec![
+            ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", Some("nodejs-lts")),
+            ToolSpec::builtin("Go", "go", &["version"], "Runtime", Some("go")),
+            ToolSpec::builtin("Java", "java", &["-version"], "Runtime", Some("java-jdk")),
+            ToolSpec::builtin("Rust", "rustc", &["--version"], "Runtime", Some("rust")),
+            ToolSpec::builtin("PHP", "php", &["-v"], "Runtime", Some("php")),
+            ToolSpec::builtin("Bun", "bun", &["--version"], "Runtime", None),
+        ];
+        let tools = vec![
+            installed(&specs[0], "20.11.1"),
+            installed(&specs[1], "1.20.3"),
+            installed(&specs[2], "22.0.1"),
+            installed(&specs[4], "8.3.4"),
+        ];
+        let baseline = parse_baseline(
+            "[tools]\nnode = \"^20.10\"\ngo = \">=1.22\"\njava = \"~21\"\nrust = \">=1.75\"\nmystery = \"1\"\nphp = false\nbun = false\n\
+             [env]\nGOPROXY = \"https://goproxy.cn,direct\"\nGOFLAGS = \"-mod=mod\"\n[git]\n\"core.autocrlf\" = \"input\"\n",
+        )
+        .unwrap();
+
+        let entries = evaluate_baseline(&baseline, &specs, &tools, |setting| match setting.key.as_str() {
+            "GOPROXY" => Some("direct".to_string()),
+            "core.autocrlf" => Some("INPUT".to_string()),
+            _ => None,
+        });
+        let mut statuses: Vec<(&str, &str)> =
+            entries.iter().map(|entry| (entry.key.as_str(), entry.status.as_str())).collect();
+        statuses.sort_unstable();
+        assert_eq!(
+            statuses,
+            vec![
+                ("GOFLAGS", DRIFT_MISSING),
+                ("GOPROXY", DRIFT_MISMATCH),
+                ("core.autocrlf", DRIFT_COMPLIANT),
+                ("go", DRIFT_TOO_OLD),
+                ("java", DRIFT_TOO_NEW),
+                ("mystery", DRIFT_UNKNOWN),
+                ("node", DRIFT_COMPLIANT),
+                ("php", DRIFT_UNEXPECTED),
+                ("rust", DRIFT_MISSING),
+            ]
+        );
+        assert_eq!(entries.iter().find(|entry| entry.key == "php").unwrap().actual.as_deref(), Some("8.3.4"));
+
+        let report = build_drift_report("baseline.toml".to_string(), None, entries);
+        assert_eq!(report.drift_count, 7);
+        assert!(!report.compliant);
+    }
+
+    #[test]
+    fn strict_baseline_flags_unlisted_installed_tools() {
+        let specs = vec![
+            ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", Some("nodejs-lts")),
+            ToolSpec::builtin("Go", "go", &["version"], "Runtime", Some("go")),
+            ToolSpec::builtin("Rust", "rustc", &["--version"], "Runtime", Some("rust")),
+        ];
+        let tools = vec![installed(&specs[0], "20.11.1"), installed(&specs[1], "1.22.0")];
+
+        let relaxed = parse_baseline("[tools]\nnode = \"^20\"\n").unwrap();
+        assert_eq!(evaluate_baseline(&relaxed, &specs, &tools, |_| None).len(), 1);
+
+        let strict = parse_baseline("strict = true\n[tools]\nnode = \"^20\"\n").unwrap();
+        let entries = evaluate_baseline(&strict, &specs, &tools, |_| None);
+        let statuses: Vec<(&str, &str)> =
+            entries.iter().map(|entry| (entry.key.as_str(), entry.status.as_str())).collect();
+        assert_eq!(statuses, vec![("node", DRIFT_COMPLIANT), ("go", DRIFT_UNEXPECTED)]);
+    }
+}
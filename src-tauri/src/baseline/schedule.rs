@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::contracts::{BaselineDriftReport, BaselineSchedule, BaselineScheduleStatus};
+use crate::runtime::current_timestamp_ms;
+use crate::tools::catalog::app_config_dir;
+use crate::tools::ToolCacheState;
+
+use super::check_baseline_drift_internal;
+
+pub const BASELINE_SCHEDULE_FILE: &str = "baseline-schedule.json";
+pub const BASELINE_DRIFT_EVENT: &str = "baseline-drift";
+pub const DEFAULT_BASELINE_INTERVAL_MINUTES: u64 = 60;
+pub const MIN_BASELINE_INTERVAL_MINUTES: u64 = 5;
+/// 调度循环的唤醒间隔；启动后先等待一段时间，避免与首屏探测抢占资源
+const BASELINE_TICK_SECS: u64 = 60;
+const BASELINE_STARTUP_DELAY_SECS: u64 = 30;
+/// 检查失败后的重试间隔从 5 分钟起按次数翻倍，最长 24 小时
+const BASELINE_RETRY_BASE_MS: u64 = 5 * 60_000;
+const BASELINE_RETRY_MAX_MS: u64 = 24 * 60 * 60_000;
+
+/// 连续失败的次数与最近一次失败
+#[derive(Debug, Clone)]
+struct ScheduleFailure {
+    count: u32,
+    message: String,
+    at_ms: u64,
+}
+
+/// 定时基线检查的配置与最近一次结果，配置写入应用配置目录
+#[derive(Clone)]
+pub struct BaselineScheduleState {
+    path: Option<PathBuf>,
+    schedule: Arc<Mutex<BaselineSchedule>>,
+    last_report: Arc<Mutex<Option<BaselineDriftReport>>>,
+    /// 最近一次通知过的漂移内容，相同漂移不重复通知
+    notified_signature: Arc<Mutex<Option<String>>>,
+    failure: Arc<Mutex<Option<ScheduleFailure>>>,
+}
+
+impl Default for BaselineScheduleState {
+    fn default() -> Self {
+        Self::load(app_config_dir().map(|dir| dir.join(BASELINE_SCHEDULE_FILE)))
+    }
+}
+
+impl BaselineScheduleState {
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let schedule = path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<BaselineSchedule>(&content).ok())
+            .unwrap_or_else(default_schedule);
+
+        Self {
+            path,
+            schedule: Arc::new(Mutex::new(schedule)),
+            last_report: Arc::new(Mutex::new(None)),
+            notified_signature: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn schedule(&self) -> BaselineSchedule {
+        self.schedule.lock().unwrap().clone()
+    }
+
+    pub fn last_report(&self) -> Option<BaselineDriftReport> {
+        self.last_report.lock().unwrap().clone()
+    }
+
+    pub fn status(&self) -> BaselineScheduleStatus {
+        let failure = self.failure.lock().unwrap().clone();
+        BaselineScheduleStatus {
+            schedule: self.schedule(),
+            last_report: self.last_report(),
+            last_error: failure.as_ref().map(|failure| failure.message.clone()),
+            last_error_at_ms: failure.as_ref().map(|failure| failure.at_ms),
+            consecutive_failures: failure.map(|failure| failure.count).unwrap_or(0),
+            next_check_at_ms: self.next_check_at_ms(),
+        }
+    }
+
+    pub fn update(&self, mut schedule: BaselineSchedule) -> Result<BaselineSchedule, String> {
+        schedule.source = schedule.source.map(|source| source.trim().to_string()).filter(|source| !source.is_empty());
+        if schedule.enabled && schedule.source.is_none() {
+            return Err("启用定时检查前需要先设置基线来源".to_string());
+        }
+        if schedule.interval_minutes < MIN_BASELINE_INTERVAL_MINUTES {
+            return Err(format!("检查间隔不能小于 {} 分钟", MIN_BASELINE_INTERVAL_MINUTES));
+        }
+
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|error| format!("创建配置目录失败: {}", error))?;
+            }
+            let content = serde_json::to_string_pretty(&schedule).map_err(|error| format!("序列化配置失败: {}", error))?;
+            std::fs::write(path, content).map_err(|error| format!("保存基线检查配置失败: {}", error))?;
+        }
+
+        *self.schedule.lock().unwrap() = schedule.clone();
+        // 更换基线后之前的通知记录与失败退避不再适用
+        *self.notified_signature.lock().unwrap() = None;
+        *self.failure.lock().unwrap() = None;
+        Ok(schedule)
+    }
+
+    /// 记录检查结果；出现新的漂移时返回 true，表示需要通知
+    pub fn record(&self, report: &BaselineDriftReport) -> bool {
+        *self.last_report.lock().unwrap() = Some(report.clone());
+        *self.failure.lock().unwrap() = None;
+
+        let mut notified = self.notified_signature.lock().unwrap();
+        if report.compliant {
+            *notified = None;
+            return false;
+        }
+
+        let signature = drift_signature(report);
+        if notified.as_deref() == Some(signature.as_str()) {
+            return false;
+        }
+        *notified = Some(signature);
+        true
+    }
+
+    /// 定时检查失败（基线无法下载、解析失败等）时记录原因，下次检查按失败次数退避
+    pub fn record_failure(&self, message: String, now_ms: u64) {
+        let mut failure = self.failure.lock().unwrap();
+        let count = failure.as_ref().map(|failure| failure.count).unwrap_or(0) + 1;
+        *failure = Some(ScheduleFailure {
+            count,
+            message,
+            at_ms: now_ms,
+        });
+    }
+
+    fn next_check_at_ms(&self) -> Option<u64> {
+        let schedule = self.schedule();
+        if !schedule.enabled {
+            return None;
+        }
+        if let Some(failure) = self.failure.lock().unwrap().as_ref() {
+            let backoff = BASELINE_RETRY_BASE_MS
+                .saturating_mul(1 << (failure.count - 1).min(16))
+                .min(BASELINE_RETRY_MAX_MS);
+            return Some(failure.at_ms.saturating_add(backoff));
+        }
+        let last_checked = self.last_report().map(|report| report.checked_at_ms).unwrap_or(0);
+        Some(last_checked.saturating_add(schedule.interval_minutes.saturating_mul(60_000)))
+    }
+
+    fn is_due(&self, now_ms: u64) -> Option<String> {
+        let next = self.next_check_at_ms()?;
+        (now_ms >= next).then(|| self.schedule().source).flatten()
+    }
+}
+
+fn default_schedule() -> BaselineSchedule {
+    BaselineSchedule {
+        enabled: false,
+        source: None,
+        interval_minutes: DEFAULT_BASELINE_INTERVAL_MINUTES,
+    }
+}
+
+fn drift_signature(report: &BaselineDriftReport) -> String {
+    let mut parts: Vec<String> = report
+        .entries
+        .iter()
+        .filter(|entry| entry.status != super::DRIFT_COMPLIANT)
+        .map(|entry| format!("{}:{}:{}:{}", entry.kind, entry.key, entry.status, entry.actual.as_deref().unwrap_or("")))
+        .collect();
+    parts.sort();
+    parts.join("|")
+}
+
+/// 后台定时检查；出现新的漂移时调用 notify（由调用方转为前端事件）
+pub async fn run_baseline_schedule<F>(state: BaselineScheduleState, cache: ToolCacheState, notify: F)
+where
+    F: Fn(&BaselineDriftReport) + Send + 'static,
+{
+    tokio::time::sleep(Duration::from_secs(BASELINE_STARTUP_DELAY_SECS)).await;
+    loop {
+        if let Some(source) = state.is_due(current_timestamp_ms()) {
+            match check_baseline_drift_internal(&source, cache.clone()).await {
+                Ok(report) => {
+                    if state.record(&report) {
+                        notify(&report);
+                    }
+                }
+                Err(error) => state.record_failure(error, current_timestamp_ms()),
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(BASELINE_TICK_SECS)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::{build_drift_report, DRIFT_COMPLIANT, DRIFT_TOO_OLD};
+    use crate::contracts::BaselineDriftEntry;
+
+    fn entry(status: &str, actual: &str) -> BaselineDriftEntry {
+        BaselineDriftEntry {
+            kind: "tool".to_string(),
+            key: "node".to_string(),
+            expected: ">=20".to_string(),
+            actual: Some(actual.to_string()),
+            status: status.to_string(),
+            tool_name: None,
+            install_key: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn same_drift_is_notified_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = BaselineScheduleState::load(Some(dir.path().join(BASELINE_SCHEDULE_FILE)));
+        let drift = build_drift_report("team.toml".to_string(), None, vec![entry(DRIFT_TOO_OLD, "18.19.0")]);
+
+        assert!(state.record(&drift));
+        assert!(!state.record(&drift));
+        assert!(state.record(&build_drift_report("team.toml".to_string(), None, vec![entry(DRIFT_TOO_OLD, "19.0.0")])));
+        assert!(!state.record(&build_drift_report("team.toml".to_string(), None, vec![entry(DRIFT_COMPLIANT, "20.11.1")])));
+        assert!(state.record(&drift));
+    }
+
+    #[test]
+    fn schedule_is_validated_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BASELINE_SCHEDULE_FILE);
+        let state = BaselineScheduleState::load(Some(path.clone()));
+        assert!(state.is_due(u64::MAX).is_none());
+
+        let enabled = |source: Option<&str>, interval_minutes| BaselineSchedule {
+            enabled: true,
+            source: source.map(ToString::to_string),
+            interval_minutes,
+        };
+        assert!(state.update(enabled(Some("  "), 60)).is_err());
+        assert!(state.update(enabled(Some("team.toml"), 1)).is_err());
+        state.update(enabled(Some(" team.toml "), 30)).unwrap();
+
+        let reloaded = BaselineScheduleState::load(Some(path));
+        assert_eq!(reloaded.schedule().source.as_deref(), Some("team.toml"));
+        assert_eq!(reloaded.is_due(30 * 60_000).as_deref(), Some("team.toml"));
+        assert!(reloaded.is_due(30 * 60_000 - 1).is_none());
+    }
+
+    #[test]
+    fn failed_checks_are_reported_and_backed_off() {
+        let state = BaselineScheduleState::load(None);
+        state
+            .update(BaselineSchedule {
+                enabled: true,
+                source: Some("https://example.invalid/team.toml".to_string()),
+                interval_minutes: 60,
+            })
+            .unwrap();
+
+        let start = 10 * 60 * 60_000;
+        state.record_failure("下载基线失败".to_string(), start);
+        assert!(state.is_due(start + 60_000).is_none());
+        assert!(state.is_due(start + BASELINE_RETRY_BASE_MS).is_some());
+
+        state.record_failure("下载基线失败".to_string(), start + BASELINE_RETRY_BASE_MS);
+        let status = state.status();
+        assert_eq!(status.consecutive_failures, 2);
+        assert_eq!(status.last_error.as_deref(), Some("下载基线失败"));
+        assert_eq!(status.next_check_at_ms, Some(start + 3 * BASELINE_RETRY_BASE_MS));
+
+        let report = build_drift_report("team.toml".to_string(), None, vec![entry(DRIFT_COMPLIANT, "20.11.1")]);
+        state.record(&report);
+        let status = state.status();
+        assert_eq!((status.consecutive_failures, status.last_error), (0, None));
+        assert_eq!(status.next_check_at_ms, Some(report.checked_at_ms + 60 * 60_000));
+    }
+}
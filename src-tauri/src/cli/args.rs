@@ -0,0 +1,132 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Help,
+    Version,
+    Snapshot,
+    ToolsDetect,
+    ToolsCheck { names: Vec<String> },
+    DockerPs,
+    DeployRun { profile_path: String, branch: Option<String> },
+    VersionCheck { image: String, github: Option<String>, include_prerelease: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliInvocation {
+    pub command: CliCommand,
+    pub json: bool,
+}
+
+/// 手工解析参数：全局开关可出现在任意位置，带值选项只在对应子命令下生效
+pub fn parse_cli_args(args: &[String]) -> Result<CliInvocation, String> {
+    let mut json = false;
+    let mut help = false;
+    let mut version = false;
+    let mut include_prerelease = false;
+    let mut branch: Option<String> = None;
+    let mut github: Option<String> = None;
+    let mut positional: Vec<String> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => help = true,
+            "-V" | "--version" => version = true,
+            "--prerelease" => include_prerelease = true,
+            "--branch" => branch = Some(take_value(&mut iter, "--branch")?),
+            "--github" => github = Some(take_value(&mut iter, "--github")?),
+            other if other.starts_with('-') => return Err(format!("未知选项: {}", other)),
+            other => positional.push(other.to_string()),
+        }
+    }
+
+    if help {
+        return Ok(CliInvocation { command: CliCommand::Help, json });
+    }
+    if version {
+        return Ok(CliInvocation { command: CliCommand::Version, json });
+    }
+
+    let words: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match words.as_slice() {
+        [] | ["help"] => CliCommand::Help,
+        ["snapshot"] => CliCommand::Snapshot,
+        ["tools", "detect"] => CliCommand::ToolsDetect,
+        ["tools", "check"] => return Err("tools check 需要至少一个工具名称".to_string()),
+        ["tools", "check", names @ ..] => CliCommand::ToolsCheck {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        },
+        ["docker", "ps"] => CliCommand::DockerPs,
+        ["deploy", "run", profile_path] => CliCommand::DeployRun {
+            profile_path: profile_path.to_string(),
+            branch: branch.take(),
+        },
+        ["version", "check", image] => CliCommand::VersionCheck {
+            image: image.to_string(),
+            github: github.take(),
+            include_prerelease: std::mem::take(&mut include_prerelease),
+        },
+        _ => return Err(format!("未知命令: {}", positional.join(" "))),
+    };
+
+    if branch.is_some() {
+        return Err("--branch 仅适用于 deploy run".to_string());
+    }
+    if github.is_some() {
+        return Err("--github 仅适用于 version check".to_string());
+    }
+    if include_prerelease {
+        return Err("--prerelease 仅适用于 version check".to_string());
+    }
+
+    Ok(CliInvocation { command, json })
+}
+
+fn take_value<'a>(iter: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<String, String> {
+    iter.next()
+        .filter(|value| !value.starts_with('-'))
+        .cloned()
+        .ok_or_else(|| format!("{} 缺少参数值", flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<CliInvocation, String> {
+        let args: Vec<String> = line.split_whitespace().map(ToString::to_string).collect();
+        parse_cli_args(&args)
+    }
+
+    #[test]
+    fn parse_subcommands_with_global_json_flag() {
+        let invocation = parse("--json tools check node git").unwrap();
+        assert!(invocation.json);
+        assert_eq!(
+            invocation.command,
+            CliCommand::ToolsCheck { names: vec!["node".to_string(), "git".to_string()] }
+        );
+
+        let invocation = parse("deploy run profile.json --branch main").unwrap();
+        assert!(!invocation.json);
+        assert_eq!(
+            invocation.command,
+            CliCommand::DeployRun { profile_path: "profile.json".to_string(), branch: Some("main".to_string()) }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_or_misplaced_arguments() {
+        assert!(parse("tools check").is_err());
+        assert!(parse("docker rm").is_err());
+        assert!(parse("snapshot --branch main").is_err());
+        assert!(parse("deploy run profile.json --branch").is_err());
+        assert!(parse("snapshot --verbose").is_err());
+        assert!(parse("tools detect --prerelease").is_err());
+        assert_eq!(
+            parse("version check nginx --prerelease").unwrap().command,
+            CliCommand::VersionCheck { image: "nginx".to_string(), github: None, include_prerelease: true }
+        );
+        assert_eq!(parse("").unwrap().command, CliCommand::Help);
+    }
+}
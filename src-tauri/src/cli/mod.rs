@@ -0,0 +1,459 @@
+mod args;
+
+use serde::Serialize;
+use std::time::Instant;
+
+use crate::contracts::{
+    CheckImageVersionRequest, CheckImageVersionResponse, CommandResponse, DeployProfile, DeployStepRequest,
+    DeployStepResult, DockerCommandResult, DockerHubSourceConfig, GithubReleaseSourceConfig, ImageSelection,
+    SystemSnapshot, ToolStatus, VersionSourceConfig,
+};
+use crate::deploy::execute_deploy_step_internal;
+use crate::docker::{execute_docker_action, parse::parse_docker_containers, DOCKER_ACTION_TIMEOUT_MS};
+use crate::system::{query_system_snapshot_precise, system_snapshot_supported};
+use crate::tools::{detect_tools_cached, load_tool_catalog, ToolCacheState, ToolCatalog};
+use crate::version::{check_image_version, VersionRuntimeState};
+
+use args::{parse_cli_args, CliCommand, CliInvocation};
+
+/// 进程退出码：0 成功，1 执行失败，2 参数错误，3 检查未通过（缺少工具 / 存在可用更新），4 当前平台不支持
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_CHECK_FAILED: i32 = 3;
+pub const EXIT_UNSUPPORTED: i32 = 4;
+
+const DEPLOY_STEPS: [&str; 3] = ["pull_code", "stop_old", "deploy_new"];
+
+const USAGE: &str = "用法: devenvprobe <命令> [选项]
+
+命令:
+  snapshot                       输出系统快照（支持 Windows 与 Linux，其他平台退出码为 4）
+  tools detect                   探测全部开发工具
+  tools check <名称>...           检查指定工具是否已安装（未安装时退出码为 3）
+  docker ps                      列出容器
+  deploy run <profile.json>      按顺序执行部署配置中的全部步骤
+      --branch <分支>             拉取代码时使用的分支
+  version check <镜像[:标签]>     检查镜像是否有新版本（有更新时退出码为 3）
+      --github <owner/repo>       改用 GitHub Release 作为版本源
+      --prerelease                包含预发布版本
+
+通用选项:
+  --json                         以 JSON 输出（与 GUI 命令相同的 CommandResponse 结构）
+  -h, --help                     显示帮助
+  -V, --version                  显示版本
+
+退出码: 0 成功，1 执行失败，2 参数错误，3 检查未通过，4 当前平台不支持";
+
+/// CLI 入口，返回进程退出码
+pub fn run_cli(args: Vec<String>) -> i32 {
+    let invocation = match parse_cli_args(&args) {
+        Ok(invocation) => invocation,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let CliInvocation { command, json } = invocation;
+    match command {
+        CliCommand::Help => {
+            println!("{}", USAGE);
+            EXIT_OK
+        }
+        CliCommand::Version => {
+            println!("devenvprobe {}", env!("CARGO_PKG_VERSION"));
+            EXIT_OK
+        }
+        CliCommand::Snapshot => run_snapshot(json),
+        CliCommand::ToolsDetect => run_tools_detect(json),
+        CliCommand::ToolsCheck { names } => run_tools_check(&names, json),
+        CliCommand::DockerPs => run_docker_ps(json),
+        CliCommand::DeployRun { profile_path, branch } => run_deploy(&profile_path, branch, json),
+        CliCommand::VersionCheck { image, github, include_prerelease } => {
+            run_version_check(&image, github.as_deref(), include_prerelease, json)
+        }
+    }
+}
+
+fn run_snapshot(json: bool) -> i32 {
+    let started_at = Instant::now();
+    if !system_snapshot_supported() {
+        let result: Result<SystemSnapshot, String> =
+            Err(format!("当前平台 ({}) 暂不支持系统快照", std::env::consts::OS));
+        emit(&result, started_at, json, render_snapshot);
+        return EXIT_UNSUPPORTED;
+    }
+
+    // 采样失败时如实报错并返回 1，不用占位数据冒充结果
+    let result = query_system_snapshot_precise();
+    emit(&result, started_at, json, render_snapshot);
+    exit_code_for(&result, |_| EXIT_OK)
+}
+
+fn run_tools_detect(json: bool) -> i32 {
+    let started_at = Instant::now();
+    let catalog = load_cli_catalog();
+    // 与界面共用缓存文件，命中时不再启动探测进程，新的探测结果同样写入版本历史
+    let result = detect_tools_cached(&catalog.specs, &ToolCacheState::default(), false);
+    emit(&result, started_at, json, |tools| render_tools(tools));
+    exit_code_for(&result, |_| EXIT_OK)
+}
+
+fn run_tools_check(names: &[String], json: bool) -> i32 {
+    let started_at = Instant::now();
+    let catalog = load_cli_catalog();
+
+    let mut selected = Vec::with_capacity(names.len());
+    for name in names {
+        let normalized = name.trim().to_lowercase();
+        let spec = catalog.specs.iter().find(|spec| {
+            spec.name.to_lowercase() == normalized
+                || spec.command.to_lowercase() == normalized
+                || spec.install_key.as_deref().is_some_and(|key| key.to_lowercase() == normalized)
+        });
+
+        match spec {
+            Some(spec) => selected.push(spec.clone()),
+            None => {
+                eprintln!("未知工具: {}", name);
+                return EXIT_USAGE;
+            }
+        }
+    }
+
+    let result = detect_tools_cached(&selected, &ToolCacheState::default(), false);
+    emit(&result, started_at, json, |tools| render_tools(tools));
+    exit_code_for(&result, |tools| {
+        if tools.iter().all(|tool| tool.installed) {
+            EXIT_OK
+        } else {
+            EXIT_CHECK_FAILED
+        }
+    })
+}
+
+fn run_docker_ps(json: bool) -> i32 {
+    let started_at = Instant::now();
+    let result = execute_docker_action("ps", None, DOCKER_ACTION_TIMEOUT_MS);
+    emit(&result, started_at, json, render_docker_ps);
+    exit_code_for(&result, |result| if result.exit_code == 0 { EXIT_OK } else { EXIT_FAILURE })
+}
+
+fn run_deploy(profile_path: &str, branch: Option<String>, json: bool) -> i32 {
+    let started_at = Instant::now();
+    let profile = match load_deploy_profile(profile_path) {
+        Ok(profile) => profile,
+        Err(error) => {
+            emit::<Vec<DeployStepResult>, _>(&Err(error), started_at, json, |_| {});
+            return EXIT_FAILURE;
+        }
+    };
+
+    let mut results = Vec::with_capacity(DEPLOY_STEPS.len());
+    let mut failure = None;
+    for step in DEPLOY_STEPS {
+        let request = DeployStepRequest {
+            profile: profile.clone(),
+            step: step.to_string(),
+            selected_branch: branch.clone(),
+        };
+
+        match execute_deploy_step_internal(&request) {
+            Ok(result) => {
+                let ok = result.ok;
+                results.push(result);
+                if !ok {
+                    failure = Some(format!("部署步骤 {} 执行失败", step));
+                    break;
+                }
+            }
+            Err(error) => {
+                failure = Some(format!("部署步骤 {} 执行失败: {}", step, error));
+                break;
+            }
+        }
+    }
+
+    if json {
+        print_json(&CommandResponse {
+            ok: failure.is_none(),
+            data: Some(&results),
+            error: failure.clone(),
+            elapsed_ms: started_at.elapsed().as_millis(),
+        });
+    } else {
+        render_deploy(&results);
+        if let Some(error) = &failure {
+            eprintln!("错误: {}", error);
+        }
+    }
+
+    if failure.is_some() {
+        EXIT_FAILURE
+    } else {
+        EXIT_OK
+    }
+}
+
+fn run_version_check(image: &str, github: Option<&str>, include_prerelease: bool, json: bool) -> i32 {
+    let started_at = Instant::now();
+    let request = match build_version_request(image, github, include_prerelease) {
+        Ok(request) => request,
+        Err(error) => {
+            eprintln!("{}", error);
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|error| format!("初始化异步运行时失败: {}", error))
+        .and_then(|runtime| {
+            let state = VersionRuntimeState::new();
+            runtime
+                .block_on(check_image_version(request, &state))
+                .map_err(|error| error.user_message())
+        });
+
+    emit(&result, started_at, json, render_version_check);
+    exit_code_for(&result, |response| if response.has_update { EXIT_CHECK_FAILED } else { EXIT_OK })
+}
+
+/// 以当前目录作为项目目录加载工具目录，校验错误只提示不中断
+fn load_cli_catalog() -> ToolCatalog {
+    let project_dir = std::env::current_dir().ok();
+    let catalog = load_tool_catalog(project_dir.as_deref());
+    for error in &catalog.errors {
+        match error.index {
+            Some(index) => eprintln!("工具目录 {} 第 {} 项无效: {}", error.source, index + 1, error.message),
+            None => eprintln!("工具目录 {} 无效: {}", error.source, error.message),
+        }
+    }
+    catalog
+}
+
+fn load_deploy_profile(path: &str) -> Result<DeployProfile, String> {
+    let content = std::fs::read_to_string(path).map_err(|error| format!("读取部署配置失败({}): {}", path, error))?;
+    serde_json::from_str(&content).map_err(|error| format!("解析部署配置失败({}): {}", path, error))
+}
+
+/// `nginx:1.25`、`bitnami/redis`、`ghcr.io/org/app:v1` 均可；未带标签时视为 latest
+fn split_image_reference(image: &str) -> Result<(String, String), String> {
+    let image = image.trim();
+    if image.is_empty() {
+        return Err("镜像名称不能为空".to_string());
+    }
+
+    let last_segment_start = image.rfind('/').map(|index| index + 1).unwrap_or(0);
+    match image[last_segment_start..].rfind(':') {
+        Some(offset) => {
+            let split_at = last_segment_start + offset;
+            let tag = &image[split_at + 1..];
+            if tag.is_empty() {
+                return Err(format!("镜像标签为空: {}", image));
+            }
+            Ok((image[..split_at].to_string(), tag.to_string()))
+        }
+        None => Ok((image.to_string(), "latest".to_string())),
+    }
+}
+
+fn build_version_request(
+    image: &str,
+    github: Option<&str>,
+    include_prerelease: bool,
+) -> Result<CheckImageVersionRequest, String> {
+    let (repository, tag) = split_image_reference(image)?;
+
+    let source = match github {
+        Some(slug) => {
+            let (owner, repo) = slug
+                .split_once('/')
+                .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+                .ok_or_else(|| format!("GitHub 仓库格式应为 owner/repo: {}", slug))?;
+            VersionSourceConfig::GithubRelease(GithubReleaseSourceConfig {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                include_prerelease,
+                token: std::env::var("GITHUB_TOKEN").ok().filter(|token| !token.is_empty()),
+            })
+        }
+        None => {
+            let (namespace, name) = match repository.rsplit_once('/') {
+                Some((namespace, name)) => (namespace.rsplit('/').next().unwrap_or(namespace), name),
+                None => ("library", repository.as_str()),
+            };
+            VersionSourceConfig::DockerHub(DockerHubSourceConfig {
+                namespace: namespace.to_string(),
+                repository: name.to_string(),
+                include_prerelease,
+                tag_regex: None,
+            })
+        }
+    };
+
+    Ok(CheckImageVersionRequest {
+        image: ImageSelection {
+            image_id: None,
+            repository,
+            tag,
+            container_name: None,
+            project_path: None,
+        },
+        sources: vec![source],
+        timeout_ms: None,
+        overall_timeout_ms: None,
+    })
+}
+
+/// 统一输出：JSON 模式打印与 GUI 相同的 CommandResponse，文本模式调用渲染函数，错误写到 stderr
+fn emit<T, F>(result: &Result<T, String>, started_at: Instant, json: bool, render: F)
+where
+    T: Serialize,
+    F: FnOnce(&T),
+{
+    let elapsed_ms = started_at.elapsed().as_millis();
+    if json {
+        print_json(&CommandResponse {
+            ok: result.is_ok(),
+            data: result.as_ref().ok(),
+            error: result.as_ref().err().cloned(),
+            elapsed_ms,
+        });
+        return;
+    }
+
+    match result {
+        Ok(data) => render(data),
+        Err(error) => eprintln!("错误: {}", error),
+    }
+}
+
+fn exit_code_for<T>(result: &Result<T, String>, on_success: impl FnOnce(&T) -> i32) -> i32 {
+    match result {
+        Ok(data) => on_success(data),
+        Err(_) => EXIT_FAILURE,
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(error) => eprintln!("序列化输出失败: {}", error),
+    }
+}
+
+fn render_snapshot(snapshot: &SystemSnapshot) {
+    println!("主机      {}", snapshot.host_name);
+    println!("系统      {} {} ({})", snapshot.os_name, snapshot.os_version, snapshot.architecture);
+    println!("CPU       {} · {} 核 / {} 线程", snapshot.cpu_model, snapshot.cpu_cores, snapshot.cpu_logical_cores);
+    println!(
+        "内存      {:.1} / {:.1} GB ({:.1}%)",
+        snapshot.used_memory_gb, snapshot.total_memory_gb, snapshot.memory_usage_percent
+    );
+    if let Some(kind) = &snapshot.environment_kind {
+        println!("运行环境  {}", kind);
+    }
+    for disk in &snapshot.disks {
+        println!(
+            "磁盘      {} {:.1} / {:.1} GB ({:.1}%)",
+            disk.mount_point, disk.used_gb, disk.total_gb, disk.usage_percent
+        );
+    }
+}
+
+fn render_tools(tools: &[ToolStatus]) {
+    let name_width = tools.iter().map(|tool| tool.name.chars().count()).max().unwrap_or(0);
+    for tool in tools {
+        let marker = if tool.installed { "✓" } else { "✗" };
+        let version = tool.version.as_deref().unwrap_or("--");
+        println!("{} {:<width$}  {}", marker, tool.name, version, width = name_width);
+    }
+
+    let installed = tools.iter().filter(|tool| tool.installed).count();
+    println!("\n已安装 {}/{}", installed, tools.len());
+}
+
+fn render_docker_ps(result: &DockerCommandResult) {
+    if result.exit_code != 0 {
+        eprintln!("{} 执行失败({}): {}", result.command, result.exit_code, result.stderr.trim());
+        return;
+    }
+
+    let containers = parse_docker_containers(&result.stdout);
+    if containers.is_empty() {
+        println!("没有容器");
+        return;
+    }
+
+    for container in containers {
+        let marker = if container.is_running() { "●" } else { "○" };
+        println!("{} {}  {}  {}  {}", marker, container.id, container.name, container.status, container.ports);
+    }
+}
+
+fn render_deploy(results: &[DeployStepResult]) {
+    for result in results {
+        let marker = if result.skipped {
+            "-"
+        } else if result.ok {
+            "✓"
+        } else {
+            "✗"
+        };
+        println!("{} {} ({} ms)", marker, result.step, result.elapsed_ms);
+        for command in &result.commands {
+            println!("    $ {}", command);
+        }
+        if let Some(error) = &result.error {
+            println!("    {}", error);
+        }
+    }
+}
+
+fn render_version_check(response: &CheckImageVersionResponse) {
+    let current = response.current_version.as_deref().unwrap_or("--");
+    match (&response.recommended, response.has_update) {
+        (Some(candidate), true) => println!("{}: {} -> {} 可更新", response.image_key, current, candidate.version),
+        _ => println!("{}: {} 已是最新", response.image_key, current),
+    }
+    for source in response.results.iter().filter(|source| !source.ok) {
+        eprintln!(
+            "    {:?}: {}",
+            source.source,
+            source.error_message.as_deref().unwrap_or("检查失败")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_image_reference_handles_registry_ports_and_default_tag() {
+        assert_eq!(split_image_reference("nginx:1.25").unwrap(), ("nginx".to_string(), "1.25".to_string()));
+        assert_eq!(split_image_reference("bitnami/redis").unwrap(), ("bitnami/redis".to_string(), "latest".to_string()));
+        assert_eq!(
+            split_image_reference("localhost:5000/app:v2").unwrap(),
+            ("localhost:5000/app".to_string(), "v2".to_string())
+        );
+        assert!(split_image_reference("nginx:").is_err());
+    }
+
+    #[test]
+    fn version_request_defaults_to_docker_hub_library_namespace() {
+        let request = build_version_request("nginx", None, false).unwrap();
+        match &request.sources[0] {
+            VersionSourceConfig::DockerHub(config) => {
+                assert_eq!(config.namespace, "library");
+                assert_eq!(config.repository, "nginx");
+            }
+            other => panic!("unexpected source: {:?}", other),
+        }
+
+        assert!(build_version_request("nginx", Some("nginx"), false).is_err());
+    }
+}
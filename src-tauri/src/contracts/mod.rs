@@ -39,11 +39,61 @@ pub struct SystemSnapshot {
     pub used_memory_gb: f64,
     pub memory_usage_percent: f64,
     pub disks: Vec<DiskSnapshot>,
+    pub sensors: Option<HardwareSensors>,
+    pub environment_kind: Option<String>,
+    pub effective_limits: Option<EffectiveResourceLimits>,
     pub sample_mode: Option<String>,
     pub sampled_at_ms: Option<u64>,
     pub is_stale: Option<bool>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveResourceLimits {
+    pub cgroup_version: Option<u8>,
+    pub cpu_quota_cores: Option<f64>,
+    pub memory_limit_gb: Option<f64>,
+    pub effective_cpu_cores: Option<f64>,
+    pub effective_memory_gb: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemperatureReading {
+    pub source: String,
+    pub label: String,
+    pub celsius: f64,
+    pub high_celsius: Option<f64>,
+    pub critical_celsius: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FanReading {
+    pub label: String,
+    pub rpm: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryStatus {
+    pub name: String,
+    pub charge_percent: Option<f64>,
+    pub charging: Option<bool>,
+    pub status: Option<String>,
+    pub time_remaining_minutes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardwareSensors {
+    pub temperatures: Vec<TemperatureReading>,
+    pub fans: Vec<FanReading>,
+    pub batteries: Vec<BatteryStatus>,
+    pub ac_online: Option<bool>,
+    pub thermal_throttling: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SystemRealtimeSnapshot {
@@ -52,12 +102,13 @@ pub struct SystemRealtimeSnapshot {
     pub total_memory_gb: f64,
     pub used_memory_gb: f64,
     pub memory_usage_percent: f64,
+    pub thermal_throttling: Option<bool>,
     pub sample_mode: Option<String>,
     pub sampled_at_ms: Option<u64>,
     pub is_stale: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ToolStatus {
     pub name: String,
@@ -68,6 +119,575 @@ pub struct ToolStatus {
     pub details: Option<String>,
     pub install_key: Option<String>,
     pub install_path: Option<String>,
+    pub parsed_version: Option<ToolVersion>,
+    pub version_manager: Option<ManagedToolInfo>,
+    pub lifecycle: Option<ToolLifecycle>,
+    /// 探测命令超时；无输出时 installed 为 false，稍后会在后台用更长时间重新探测
+    #[serde(default)]
+    pub timed_out: bool,
+}
+
+/// 测试用：version 为 Some 时视为已安装，其余字段为空，需要时用结构体更新语法覆盖
+#[cfg(test)]
+impl ToolStatus {
+    pub fn for_test(name: &str, command: &str, version: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            category: "Runtime".to_string(),
+            installed: version.is_some(),
+            version: version.map(ToString::to_string),
+            details: None,
+            install_key: None,
+            install_path: None,
+            parsed_version: None,
+            version_manager: None,
+            lifecycle: None,
+            timed_out: false,
+        }
+    }
+
+    /// 附上只含 semver 的解析结果
+    pub fn with_semver(mut self, semver: &str) -> Self {
+        self.parsed_version = Some(ToolVersion {
+            raw: self.version.clone().unwrap_or_else(|| semver.to_string()),
+            semver: Some(semver.to_string()),
+            major: None,
+            minor: None,
+            patch: None,
+            build: None,
+        });
+        self
+    }
+}
+
+/// 工具版本所在发布周期的生命周期状态（数据源见 tools/eol-dataset.json）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolLifecycle {
+    pub product: String,
+    pub product_name: String,
+    pub cycle: String,
+    /// supported / security_only / eol
+    pub state: String,
+    pub release_date: Option<String>,
+    pub support_end: Option<String>,
+    pub eol_date: Option<String>,
+    pub lts: bool,
+}
+
+/// 工具版本变化记录；change 为 added / removed / changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolHistoryEntry {
+    pub tool: String,
+    pub change: String,
+    pub previous_version: Option<String>,
+    pub version: Option<String>,
+    pub previous_path: Option<String>,
+    pub install_path: Option<String>,
+    pub recorded_at_ms: u64,
+}
+
+/// 生命周期数据集的来源与更新时间
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolDatasetInfo {
+    pub updated_at: String,
+    pub source: String,
+    pub products: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// 工具由版本管理器提供时附带的信息，other_versions 为同一管理器中的其余版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedToolInfo {
+    pub manager: String,
+    pub manager_name: String,
+    pub active_version: Option<String>,
+    pub other_versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedVersion {
+    pub tool: String,
+    pub version: String,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionManagerStatus {
+    pub key: String,
+    pub name: String,
+    pub installed: bool,
+    pub version: Option<String>,
+    pub tools: Vec<String>,
+    pub versions: Vec<ManagedVersion>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionManagerActionResult {
+    pub manager: String,
+    pub action: String,
+    pub tool: String,
+    pub version: String,
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+/// 同名可执行文件的一处安装；active 为 PATH 中第一个，即实际被调用的那个
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInstallation {
+    pub path: String,
+    pub source: String,
+    pub active: bool,
+    pub on_path: bool,
+    pub version: Option<String>,
+    pub parsed_version: Option<ToolVersion>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInstallationsReport {
+    pub tool: String,
+    pub command: String,
+    pub active_path: Option<String>,
+    pub installations: Vec<ToolInstallation>,
+}
+
+/// 从工具输出中解析出的结构化版本；semver 缺省部分补 0，build 为非预发布后缀
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolVersion {
+    pub raw: String,
+    pub semver: Option<String>,
+    pub major: Option<u64>,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub build: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ToolInstallRecipe {
+    pub winget: Option<String>,
+    pub npm: Option<String>,
+    /// 自定义安装命令，按空白拆分为程序与参数
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCatalogEntry {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub category: String,
+    pub install_key: Option<String>,
+    pub timeout_ms: Option<u64>,
+    pub retries: Option<u32>,
+    pub version_regex: Option<String>,
+    pub install: Option<ToolInstallRecipe>,
+    /// 与目录文件写法一致，如 github:owner/repo
+    pub upstream: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCatalogError {
+    pub source: String,
+    pub index: Option<usize>,
+    pub name: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCatalogReport {
+    pub tools: Vec<ToolCatalogEntry>,
+    pub sources: Vec<String>,
+    pub errors: Vec<ToolCatalogError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementCheck {
+    /// 配置文件中书写的工具键，如 node、pnpm
+    pub tool: String,
+    pub range: String,
+    /// pass / fail / missing / outdated
+    pub status: String,
+    pub tool_name: Option<String>,
+    pub installed_version: Option<String>,
+    pub install_key: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRequirementsReport {
+    pub project_path: String,
+    pub config_path: String,
+    pub passed: bool,
+    pub checks: Vec<RequirementCheck>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolchainPinCheck {
+    pub tool: String,
+    pub tool_name: Option<String>,
+    pub source: String,
+    pub pinned: String,
+    pub range: Option<String>,
+    pub installed_version: Option<String>,
+    pub install_key: Option<String>,
+    pub status: String,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPinsReport {
+    pub project_path: String,
+    pub mismatches: usize,
+    pub checks: Vec<ToolchainPinCheck>,
+}
+
+/// 通过包管理器全局安装的包；actions 为可执行的 uninstall / upgrade
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPackage {
+    pub ecosystem: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub location: Option<String>,
+    pub source: Option<String>,
+    pub actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageEcosystemInventory {
+    pub ecosystem: String,
+    pub name: String,
+    pub available: bool,
+    pub packages: Vec<GlobalPackage>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPackageActionResult {
+    pub ecosystem: String,
+    pub action: String,
+    pub name: String,
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustToolchain {
+    pub name: String,
+    pub is_default: bool,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustTarget {
+    pub name: String,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustComponent {
+    pub name: String,
+    pub target: Option<String>,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustOverride {
+    pub path: String,
+    pub toolchain: String,
+}
+
+/// reason 为 rustup 给出的来源，如 `default`、`directory override for '...'`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustActiveToolchain {
+    pub toolchain: String,
+    pub reason: Option<String>,
+    pub directory: Option<String>,
+}
+
+/// targets 与 components 针对 toolchain 字段所指的工具链；
+/// 某一部分列出失败（如工具链尚未安装）时该部分为空，原因写在对应的 *_error 字段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupStatus {
+    pub toolchains: Vec<RustToolchain>,
+    pub toolchain: Option<String>,
+    pub targets: Vec<RustTarget>,
+    pub targets_error: Option<String>,
+    pub components: Vec<RustComponent>,
+    pub components_error: Option<String>,
+    pub overrides: Vec<RustOverride>,
+    pub overrides_error: Option<String>,
+    pub active: Option<RustActiveToolchain>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupActionResult {
+    pub kind: String,
+    pub action: String,
+    pub name: String,
+    pub toolchain: Option<String>,
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RustupOutputLine {
+    pub kind: String,
+    pub action: String,
+    pub name: String,
+    pub stream: String,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorPresetInfo {
+    pub key: String,
+    pub name: String,
+    pub url: String,
+}
+
+/// 某个生态当前使用的源；current_url 为空表示使用官方源
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorEcosystemStatus {
+    pub ecosystem: String,
+    pub name: String,
+    pub config_path: Option<String>,
+    pub current_url: Option<String>,
+    pub current_preset: Option<String>,
+    /// 设置了优先级更高的环境变量时，配置文件中的值不会生效
+    pub overridden_by: Option<String>,
+    pub presets: Vec<MirrorPresetInfo>,
+    pub backups: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorApplyResult {
+    pub ecosystem: String,
+    pub preset: String,
+    pub url: Option<String>,
+    pub config_path: String,
+    pub backup_path: String,
+    /// 如 Docker 需要重启守护进程才会生效
+    pub notice: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorLatency {
+    pub ecosystem: String,
+    pub preset: String,
+    pub url: String,
+    pub latency_ms: Option<u64>,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineDriftEntry {
+    /// tool / env / git
+    pub kind: String,
+    pub key: String,
+    pub expected: String,
+    pub actual: Option<String>,
+    /// compliant / missing / too_old / too_new / mismatch（配置值不同）/ unknown（工具不在目录中或版本无法解析）
+    pub status: String,
+    pub tool_name: Option<String>,
+    pub install_key: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineDriftReport {
+    pub source: String,
+    pub baseline_name: Option<String>,
+    pub compliant: bool,
+    pub drift_count: usize,
+    pub entries: Vec<BaselineDriftEntry>,
+    pub checked_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineSchedule {
+    pub enabled: bool,
+    pub source: Option<String>,
+    pub interval_minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineScheduleStatus {
+    pub schedule: BaselineSchedule,
+    pub last_report: Option<BaselineDriftReport>,
+    /// 最近一次定时检查失败的原因，检查成功后清空
+    pub last_error: Option<String>,
+    pub last_error_at_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub next_check_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerSummary {
+    pub cli_installed: bool,
+    pub cli_version: Option<String>,
+    pub server_version: Option<String>,
+    pub operating_system: Option<String>,
+    pub architecture: Option<String>,
+    pub storage_driver: Option<String>,
+    pub cpus: Option<u64>,
+    pub memory_bytes: Option<u64>,
+    pub containers: Option<u64>,
+    pub containers_running: Option<u64>,
+    pub images: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVarEntry {
+    pub name: String,
+    pub value: String,
+    pub redacted: bool,
+}
+
+/// 环境报告的 JSON 结构，schema / schemaVersion 用于识别与兼容旧报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentReport {
+    pub schema: String,
+    pub schema_version: u32,
+    pub generated_at_ms: u64,
+    pub app_version: String,
+    pub system: SystemSnapshot,
+    pub tools: Vec<ToolStatus>,
+    pub docker: DockerSummary,
+    pub env: Vec<EnvVarEntry>,
+    pub requirements: Option<ProjectRequirementsReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDiff {
+    pub name: String,
+    pub change: String,
+    pub before_version: Option<String>,
+    pub after_version: Option<String>,
+    pub version_direction: Option<String>,
+    pub path_changed: bool,
+    pub before_path: Option<String>,
+    pub after_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// 两份环境报告的差异，text 为可直接粘贴的纯文本
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentReportDiff {
+    pub before_label: String,
+    pub after_label: String,
+    pub identical: bool,
+    pub tools: Vec<ToolDiff>,
+    pub system: Vec<FieldDiff>,
+    pub env: Vec<FieldDiff>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedReport {
+    pub format: String,
+    pub file_name: String,
+    pub content: String,
+    pub saved_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorFix {
+    pub label: String,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheckResult {
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub severity: String,
+    pub message: String,
+    pub explanation: Option<String>,
+    pub fix: Option<DoctorFix>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheckResult>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub generated_at_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorFixResult {
+    pub check_id: String,
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -186,6 +806,35 @@ pub struct WingetStatus {
     pub error: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsExporterStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub endpoint: Option<String>,
+    pub started_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiMethodState {
+    pub method: String,
+    pub enabled: bool,
+    /// 会改变本机状态（执行 docker / 部署）的方法默认关闭
+    pub mutating: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub endpoint: Option<String>,
+    pub token: String,
+    pub methods: Vec<LocalApiMethodState>,
+    pub started_at_ms: Option<u64>,
+}
+
 // ============================================================================
 // Version Management Types
 // ============================================================================
@@ -207,6 +856,9 @@ pub enum VersionSourceKind {
     GithubRelease,
     LocalGit,
     CustomApi,
+    NodeDist,
+    GoDev,
+    PythonOrg,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -293,6 +945,20 @@ pub struct SourceCheckResult {
     pub elapsed_ms: u128,
 }
 
+/// 已安装工具的当前版本与上游最新稳定版；source 为空表示该工具没有已知的上游来源
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolUpdateStatus {
+    pub name: String,
+    pub command: String,
+    pub current_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub source: Option<VersionSourceKind>,
+    pub reference: Option<String>,
+    pub has_update: bool,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CheckImageVersionResponse {
@@ -432,6 +1098,9 @@ mod tests {
             used_memory_gb: 8.0,
             memory_usage_percent: 50.0,
             disks: vec![],
+            sensors: None,
+            environment_kind: Some("native".to_string()),
+            effective_limits: None,
             sample_mode: Some("quick".to_string()),
             sampled_at_ms: Some(1234567890),
             is_stale: Some(false),
@@ -450,6 +1119,7 @@ mod tests {
         assert_eq!(json["totalMemoryGb"], 16.0);
         assert_eq!(json["usedMemoryGb"], 8.0);
         assert_eq!(json["memoryUsagePercent"], 50.0);
+        assert_eq!(json["environmentKind"], "native");
         assert_eq!(json["sampleMode"], "quick");
         assert_eq!(json["sampledAtMs"], 1234567890);
         assert_eq!(json["isStale"], false);
@@ -466,6 +1136,10 @@ mod tests {
             details: None,
             install_key: Some("git".to_string()),
             install_path: None,
+            parsed_version: None,
+            version_manager: None,
+            lifecycle: None,
+            timed_out: false,
         };
 
         let json = serde_json::to_value(&tool).unwrap();
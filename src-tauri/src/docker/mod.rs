@@ -1,3 +1,5 @@
+pub mod parse;
+
 use std::time::Instant;
 use crate::contracts::DockerCommandResult;
 use crate::process_runner::execute_process_with_timeout;
@@ -0,0 +1,192 @@
+/// `docker stats --format table ...` 的单行解析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockerStatRow {
+    pub name: String,
+    pub cpu_percent: f64,
+    pub mem_used_bytes: Option<f64>,
+    pub mem_limit_bytes: Option<f64>,
+    pub net_rx_bytes: f64,
+    pub net_tx_bytes: f64,
+}
+
+/// `docker ps --format table ...` 的单行解析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockerContainerRow {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub ports: String,
+}
+
+impl DockerContainerRow {
+    /// docker ps 的运行中状态总以 "Up" 开头（如 "Up 2 hours (healthy)"），子串匹配会误判其他状态
+    pub fn is_running(&self) -> bool {
+        self.status.trim_start().starts_with("Up")
+    }
+}
+
+/// 跳过表头，按制表符或两个以上空格拆分列（与前端 docker-data.ts 保持一致）
+pub fn parse_docker_table_rows(raw: &str) -> Vec<Vec<String>> {
+    let lines: Vec<&str> = raw
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.len() <= 1 {
+        return Vec::new();
+    }
+
+    lines[1..]
+        .iter()
+        .map(|line| split_docker_columns(line))
+        .filter(|parts| !parts.is_empty())
+        .collect()
+}
+
+pub fn parse_docker_containers(raw: &str) -> Vec<DockerContainerRow> {
+    parse_docker_table_rows(raw)
+        .into_iter()
+        .map(|parts| {
+            let mut parts = parts.into_iter();
+            DockerContainerRow {
+                id: parts.next().unwrap_or_else(|| "--".to_string()),
+                name: parts.next().unwrap_or_else(|| "--".to_string()),
+                status: parts.next().unwrap_or_else(|| "--".to_string()),
+                ports: parts.collect::<Vec<_>>().join("  "),
+            }
+        })
+        .collect()
+}
+
+pub fn parse_docker_stats(raw: &str) -> Vec<DockerStatRow> {
+    parse_docker_table_rows(raw)
+        .into_iter()
+        .map(|parts| {
+            let mut parts = parts.into_iter();
+            let name = parts.next().unwrap_or_else(|| "--".to_string());
+            let cpu_text = parts.next().unwrap_or_default();
+            let mem_text = parts.next().unwrap_or_default();
+            let net_text = parts.collect::<Vec<_>>().join("  ");
+
+            let (mem_used_bytes, mem_limit_bytes) = split_pair(&mem_text);
+            let (net_rx_bytes, net_tx_bytes) = split_pair(&net_text);
+
+            DockerStatRow {
+                name,
+                cpu_percent: parse_percent_value(&cpu_text),
+                mem_used_bytes,
+                mem_limit_bytes,
+                net_rx_bytes: net_rx_bytes.unwrap_or(0.0),
+                net_tx_bytes: net_tx_bytes.unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+/// 解析 "12.5MiB"、"1.2GB"、"512B" 等 docker 输出中的容量文本
+pub fn parse_size_to_bytes(raw: &str) -> Option<f64> {
+    let normalized: String = raw.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let split_at = normalized
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(normalized.len());
+    let (number, unit) = normalized.split_at(split_at);
+    let value = number.parse::<f64>().ok()?;
+
+    let multiplier = match unit.to_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "kib" => 1024f64,
+        "mib" => 1024f64.powi(2),
+        "gib" => 1024f64.powi(3),
+        "tib" => 1024f64.powi(4),
+        "pib" => 1024f64.powi(5),
+        "kb" => 1000f64,
+        "mb" => 1000f64.powi(2),
+        "gb" => 1000f64.powi(3),
+        "tb" => 1000f64.powi(4),
+        "pb" => 1000f64.powi(5),
+        _ => return None,
+    };
+
+    Some(value * multiplier)
+}
+
+fn split_docker_columns(line: &str) -> Vec<String> {
+    let tab_parts: Vec<String> = line
+        .split('\t')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(ToString::to_string)
+        .collect();
+
+    if tab_parts.len() > 1 {
+        return tab_parts;
+    }
+
+    line.split("  ")
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn split_pair(text: &str) -> (Option<f64>, Option<f64>) {
+    let mut parts = text.split('/').map(str::trim);
+    let first = parts.next().and_then(parse_size_to_bytes);
+    let second = parts.next().and_then(parse_size_to_bytes);
+    (first, second)
+}
+
+fn parse_percent_value(text: &str) -> f64 {
+    let numeric: String = text
+        .trim()
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit() || *ch == '.' || *ch == '-')
+        .collect();
+    numeric.parse::<f64>().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_stats_table_with_padded_columns() {
+        let raw = "NAME        CPU %     MEM USAGE / LIMIT     NET I/O\n\
+                   web-1       1.25%     64.5MiB / 7.66GiB     1.2kB / 3MB\n";
+
+        let rows = parse_docker_stats(raw);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "web-1");
+        assert_eq!(rows[0].cpu_percent, 1.25);
+        assert_eq!(rows[0].mem_used_bytes, Some(64.5 * 1024.0 * 1024.0));
+        assert_eq!(rows[0].net_rx_bytes, 1200.0);
+        assert_eq!(rows[0].net_tx_bytes, 3_000_000.0);
+    }
+
+    #[test]
+    fn parse_containers_table_with_tabs() {
+        let raw = "CONTAINER ID\tNAMES\tSTATUS\tPORTS\nabc123\tdb\tUp 2 hours\t5432/tcp\ndef456\tjob\tExited (0) 3 days ago\t\n";
+
+        let rows = parse_docker_containers(raw);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].is_running());
+        assert_eq!(rows[0].ports, "5432/tcp");
+        assert!(!rows[1].is_running());
+
+        let raw = "CONTAINER ID\tNAMES\tSTATUS\tPORTS\nabc123\tsetup\tCreated\t\ndef456\tbackup\tRestarting (1) 5 seconds ago\t\n";
+        assert!(parse_docker_containers(raw).iter().all(|row| !row.is_running()));
+    }
+
+    #[test]
+    fn parse_size_units() {
+        assert_eq!(parse_size_to_bytes("512B"), Some(512.0));
+        assert_eq!(parse_size_to_bytes("1.5 GiB"), Some(1.5 * 1024f64.powi(3)));
+        assert_eq!(parse_size_to_bytes("2kB"), Some(2000.0));
+        assert_eq!(parse_size_to_bytes("--"), None);
+    }
+}
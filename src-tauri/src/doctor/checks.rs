@@ -0,0 +1,474 @@
+use std::path::{Path, PathBuf};
+
+use crate::docker::DOCKER_ACTION_TIMEOUT_MS;
+use crate::packages::resolve_go_bin;
+use crate::process_runner::{execute_process_with_timeout, ProcessCapture, TIMEOUT_EXIT_CODE};
+use crate::tools::resolver::{path_dirs, resolve_executable, resolve_symlinks};
+
+use super::{DoctorCheck, DoctorOutcome, SEVERITY_ERROR, SEVERITY_WARNING};
+
+pub const DOCTOR_PROBE_TIMEOUT_MS: u64 = 5_000;
+
+pub fn doctor_checks() -> Vec<DoctorCheck> {
+    vec![
+        DoctorCheck {
+            id: "docker_daemon",
+            title: "Docker 守护进程",
+            category: "Container",
+            run: check_docker_daemon,
+        },
+        DoctorCheck {
+            id: "git_identity",
+            title: "Git 提交身份",
+            category: "Version Control",
+            run: check_git_identity,
+        },
+        DoctorCheck {
+            id: "git_autocrlf",
+            title: "Git 换行符转换",
+            category: "Version Control",
+            run: check_git_autocrlf,
+        },
+        DoctorCheck {
+            id: "java_home",
+            title: "JAVA_HOME 与 PATH 中的 java",
+            category: "Java",
+            run: check_java_home,
+        },
+        DoctorCheck {
+            id: "gopath_bin",
+            title: "Go 安装目录在 PATH 中",
+            category: "Go",
+            run: check_gopath_bin,
+        },
+        DoctorCheck {
+            id: "npm_global_prefix",
+            title: "npm 全局目录在 PATH 中",
+            category: "Node.js",
+            run: check_npm_global_prefix,
+        },
+        DoctorCheck {
+            id: "pip_python",
+            title: "pip 与 python 版本一致",
+            category: "Python",
+            run: check_pip_python,
+        },
+    ]
+}
+
+/// 命令不在 PATH 中时返回 None，由调用方标记为不适用
+fn probe(command: &str, args: &[&str], timeout_ms: u64) -> Option<ProcessCapture> {
+    resolve_executable(command)?;
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    execute_process_with_timeout(command, &args, timeout_ms).ok()
+}
+
+fn stdout_value(capture: &ProcessCapture) -> Option<String> {
+    if capture.exit_code != 0 {
+        return None;
+    }
+    capture
+        .stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(ToString::to_string)
+}
+
+fn check_docker_daemon() -> DoctorOutcome {
+    let Some(capture) = probe("docker", &["version", "--format", "{{.Server.Version}}"], DOCKER_ACTION_TIMEOUT_MS) else {
+        return DoctorOutcome::skipped("未安装 Docker CLI");
+    };
+
+    let outcome = docker_daemon_outcome(&capture);
+    match docker_desktop_path().filter(|_| outcome.severity == SEVERITY_ERROR) {
+        Some(desktop) => outcome.with_fix("启动 Docker Desktop", "cmd", &["/C", "start", "", desktop.to_string_lossy().as_ref()]),
+        None => outcome,
+    }
+}
+
+pub fn docker_daemon_outcome(capture: &ProcessCapture) -> DoctorOutcome {
+    if capture.exit_code == 0 {
+        if let Some(version) = stdout_value(capture) {
+            return DoctorOutcome::ok(format!("Docker 守护进程 {} 可用", version));
+        }
+    }
+
+    let lowered = capture.stderr.to_lowercase();
+    let explanation = if lowered.contains("permission denied") {
+        "当前用户无权访问 Docker 套接字，可将用户加入 docker 组后重新登录（sudo usermod -aG docker $USER）。"
+    } else if capture.exit_code == TIMEOUT_EXIT_CODE {
+        "docker 命令长时间无响应，守护进程可能正在启动或已卡死，可稍后重试或重启 Docker。"
+    } else {
+        "docker 命令存在，但无法连接守护进程。请确认 Docker Desktop 或 dockerd 服务已启动，并检查 DOCKER_HOST / docker context 设置。"
+    };
+
+    DoctorOutcome::new(
+        SEVERITY_ERROR,
+        "Docker CLI 已安装，但守护进程不可达",
+        Some(explanation.to_string()),
+    )
+}
+
+fn docker_desktop_path() -> Option<PathBuf> {
+    if !cfg!(target_os = "windows") {
+        return None;
+    }
+
+    let root = std::env::var_os("ProgramFiles")?;
+    let path = Path::new(&root).join(r"Docker\Docker\Docker Desktop.exe");
+    path.is_file().then_some(path)
+}
+
+fn check_git_identity() -> DoctorOutcome {
+    if resolve_executable("git").is_none() {
+        return DoctorOutcome::skipped("未安装 Git");
+    }
+
+    let read = |key: &str| {
+        probe("git", &["config", "--get", key], DOCTOR_PROBE_TIMEOUT_MS)
+            .as_ref()
+            .and_then(stdout_value)
+    };
+    git_identity_outcome(read("user.name"), read("user.email"))
+}
+
+pub fn git_identity_outcome(name: Option<String>, email: Option<String>) -> DoctorOutcome {
+    let missing: Vec<&str> = [("user.name", &name), ("user.email", &email)]
+        .iter()
+        .filter(|(_, value)| value.as_deref().is_none_or(|value| value.trim().is_empty()))
+        .map(|(key, _)| *key)
+        .collect();
+
+    if missing.is_empty() {
+        return DoctorOutcome::ok(format!(
+            "{} <{}>",
+            name.unwrap_or_default(),
+            email.unwrap_or_default()
+        ));
+    }
+
+    DoctorOutcome::new(
+        SEVERITY_WARNING,
+        format!("未配置 {}", missing.join("、")),
+        Some("提交会被拒绝或使用错误的作者信息。请执行 git config --global user.name \"你的名字\" 与 git config --global user.email \"you@example.com\"。".to_string()),
+    )
+}
+
+fn check_git_autocrlf() -> DoctorOutcome {
+    let Some(capture) = probe("git", &["config", "--show-origin", "--get-all", "core.autocrlf"], DOCTOR_PROBE_TIMEOUT_MS) else {
+        return DoctorOutcome::skipped("未安装 Git");
+    };
+
+    // 未设置时 git config 返回 1 且无输出
+    let entries: Vec<(String, String)> = capture
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(origin, value)| (origin.trim().to_string(), value.trim().to_lowercase()))
+        .collect();
+    autocrlf_outcome(&entries, cfg!(target_os = "windows"))
+}
+
+/// entries 按 git 的读取顺序（system → global → local），最后一个生效
+pub fn autocrlf_outcome(entries: &[(String, String)], windows: bool) -> DoctorOutcome {
+    let Some((_, effective)) = entries.last() else {
+        return DoctorOutcome::ok("未设置 core.autocrlf");
+    };
+
+    let mut values: Vec<&str> = entries.iter().map(|(_, value)| value.as_str()).collect();
+    values.sort_unstable();
+    values.dedup();
+    if values.len() > 1 {
+        let detail: Vec<String> = entries
+            .iter()
+            .map(|(origin, value)| format!("{} = {}", origin, value))
+            .collect();
+        return DoctorOutcome::new(
+            SEVERITY_WARNING,
+            format!("core.autocrlf 在多个配置中取值不同，当前生效 {}", effective),
+            Some(format!(
+                "不同仓库或工具读取到的换行设置可能不一致，导致整文件出现换行差异。建议只在一处设置：{}",
+                detail.join("；")
+            )),
+        );
+    }
+
+    if !windows && effective == "true" {
+        return DoctorOutcome::new(
+            SEVERITY_WARNING,
+            "非 Windows 系统上 core.autocrlf = true",
+            Some("检出时会把 LF 转成 CRLF，脚本与 Makefile 可能无法执行。建议改为 input。".to_string()),
+        )
+        .with_fix("设置为 input", "git", &["config", "--global", "core.autocrlf", "input"]);
+    }
+
+    DoctorOutcome::ok(format!("core.autocrlf = {}", effective))
+}
+
+fn check_java_home() -> DoctorOutcome {
+    let java_home = std::env::var_os("JAVA_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let java = resolve_executable("java").map(|resolved| resolved.target);
+
+    let outcome = java_home_outcome(java_home.as_deref(), java.as_deref());
+    match java.as_deref().and_then(java_home_for) {
+        Some(home) if cfg!(target_os = "windows") && outcome.severity != super::SEVERITY_OK => {
+            let home = home.to_string_lossy().to_string();
+            outcome.with_fix(format!("将 JAVA_HOME 设为 {}", home), "setx", &["JAVA_HOME", home.as_str()])
+        }
+        _ => outcome,
+    }
+}
+
+pub fn java_home_outcome(java_home: Option<&Path>, java_target: Option<&Path>) -> DoctorOutcome {
+    let Some(java_home) = java_home else {
+        return match java_target {
+            Some(_) => DoctorOutcome::new(
+                SEVERITY_WARNING,
+                "未设置 JAVA_HOME",
+                Some("Maven、Gradle 等构建工具依赖 JAVA_HOME 定位 JDK。".to_string()),
+            ),
+            None => DoctorOutcome::skipped("未安装 Java"),
+        };
+    };
+
+    if !java_home.is_dir() {
+        return DoctorOutcome::new(
+            SEVERITY_ERROR,
+            format!("JAVA_HOME 指向不存在的目录 {}", java_home.display()),
+            Some("JDK 可能已被卸载或升级，请将 JAVA_HOME 更新为当前 JDK 的安装目录。".to_string()),
+        );
+    }
+
+    let Some(java_target) = java_target else {
+        return DoctorOutcome::new(
+            SEVERITY_WARNING,
+            "PATH 中没有 java",
+            Some(format!("请将 {} 加入 PATH。", java_home.join("bin").display())),
+        );
+    };
+
+    if is_within(java_target, java_home) {
+        return DoctorOutcome::ok(format!("java 来自 {}", java_home.display()));
+    }
+
+    DoctorOutcome::new(
+        SEVERITY_WARNING,
+        format!(
+            "JAVA_HOME 为 {}，但 PATH 中的 java 来自 {}",
+            java_home.display(),
+            java_target.display()
+        ),
+        Some("命令行与构建工具会使用不同的 JDK，编译与运行版本可能不一致。".to_string()),
+    )
+}
+
+/// <JDK>/bin/java → <JDK>
+fn java_home_for(java_target: &Path) -> Option<PathBuf> {
+    java_target.parent()?.parent().map(Path::to_path_buf)
+}
+
+fn check_gopath_bin() -> DoctorOutcome {
+    let Some(capture) = probe("go", &["env", "GOBIN", "GOPATH"], DOCTOR_PROBE_TIMEOUT_MS) else {
+        return DoctorOutcome::skipped("未安装 Go");
+    };
+    if capture.exit_code != 0 {
+        return DoctorOutcome::skipped("go env 执行失败");
+    }
+
+    let Some(bin) = resolve_go_bin(&capture.stdout) else {
+        return DoctorOutcome::skipped("无法确定 GOPATH");
+    };
+
+    dir_on_path_outcome(&bin, &path_dirs(), "go install 安装的命令")
+}
+
+fn check_npm_global_prefix() -> DoctorOutcome {
+    let Some(capture) = probe("npm", &["config", "get", "prefix"], DOCTOR_PROBE_TIMEOUT_MS) else {
+        return DoctorOutcome::skipped("未安装 npm");
+    };
+    let Some(prefix) = stdout_value(&capture).map(PathBuf::from) else {
+        return DoctorOutcome::skipped("npm config get prefix 执行失败");
+    };
+
+    // Windows 上全局命令直接放在 prefix 下，其他平台在 prefix/bin
+    let bin = if cfg!(target_os = "windows") { prefix } else { prefix.join("bin") };
+    dir_on_path_outcome(&bin, &path_dirs(), "npm install -g 安装的命令")
+}
+
+pub fn dir_on_path_outcome(dir: &Path, path_dirs: &[PathBuf], what: &str) -> DoctorOutcome {
+    let expected = normalize_dir(dir);
+    if path_dirs.iter().any(|entry| normalize_dir(entry) == expected) {
+        return DoctorOutcome::ok(format!("{} 已在 PATH 中", dir.display()));
+    }
+
+    DoctorOutcome::new(
+        SEVERITY_WARNING,
+        format!("{} 不在 PATH 中", dir.display()),
+        Some(format!("{}无法直接在终端中调用，请将该目录加入 PATH 后重新打开终端。", what)),
+    )
+}
+
+fn check_pip_python() -> DoctorOutcome {
+    let Some(python) = ["python", "python3"].into_iter().find(|command| resolve_executable(command).is_some()) else {
+        return DoctorOutcome::skipped("未安装 Python");
+    };
+    let Some(pip) = ["pip", "pip3"].into_iter().find(|command| resolve_executable(command).is_some()) else {
+        return DoctorOutcome::skipped(format!("PATH 中没有 pip，可使用 {} -m pip", python));
+    };
+
+    let output = |command: &str| {
+        probe(command, &["--version"], DOCTOR_PROBE_TIMEOUT_MS)
+            .filter(|capture| capture.exit_code == 0)
+            .map(|capture| format!("{}{}", capture.stdout, capture.stderr))
+    };
+    let (Some(python_output), Some(pip_output)) = (output(python), output(pip)) else {
+        // Microsoft Store 占位 python 等无法运行的情况
+        return DoctorOutcome::skipped("python 或 pip 无法运行");
+    };
+
+    pip_python_outcome(python, &python_output, pip, &pip_output)
+}
+
+pub fn pip_python_outcome(python: &str, python_output: &str, pip: &str, pip_output: &str) -> DoctorOutcome {
+    let python_version = python_output
+        .split_whitespace()
+        .nth(1)
+        .and_then(major_minor);
+    // pip 24.0 from C:\Python312\Lib\site-packages\pip (python 3.12)
+    let pip_python = pip_output
+        .rsplit_once("(python ")
+        .and_then(|(_, rest)| rest.split(')').next())
+        .and_then(major_minor);
+
+    match (python_version, pip_python) {
+        (Some(python_version), Some(pip_python)) if python_version != pip_python => DoctorOutcome::new(
+            SEVERITY_WARNING,
+            format!("{} 属于 Python {}，而 {} 为 {}", pip, pip_python, python, python_version),
+            Some(format!(
+                "pip install 的包不会出现在 {0} 中。请改用 {0} -m pip，或调整 PATH 中 Python 目录的顺序。",
+                python
+            )),
+        ),
+        (Some(python_version), Some(_)) => DoctorOutcome::ok(format!("{} 与 {} 均为 Python {}", pip, python, python_version)),
+        _ => DoctorOutcome::skipped("无法解析 python / pip 版本"),
+    }
+}
+
+fn major_minor(version: &str) -> Option<String> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next().filter(|part| part.chars().all(|ch| ch.is_ascii_digit()) && !part.is_empty())?;
+    let minor: String = parts.next()?.chars().take_while(char::is_ascii_digit).collect();
+    if minor.is_empty() {
+        return None;
+    }
+    Some(format!("{}.{}", major, minor))
+}
+
+fn normalize_dir(path: &Path) -> String {
+    let text = resolve_symlinks(path).to_string_lossy().to_string();
+    let text = text.trim_end_matches(['/', '\\']).to_string();
+    if cfg!(target_os = "windows") {
+        text.to_lowercase()
+    } else {
+        text
+    }
+}
+
+fn is_within(path: &Path, dir: &Path) -> bool {
+    let path = normalize_dir(path);
+    let dir = normalize_dir(dir);
+    path.strip_prefix(&dir)
+        .is_some_and(|rest| rest.starts_with(['/', '\\']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doctor::{SEVERITY_INFO, SEVERITY_OK};
+
+    fn capture(exit_code: i32, stdout: &str, stderr: &str) -> ProcessCapture {
+        ProcessCapture {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn docker_daemon_reachability() {
+        assert_eq!(docker_daemon_outcome(&capture(0, "24.0.7\n", "")).severity, SEVERITY_OK);
+
+        let unreachable = docker_daemon_outcome(&capture(
+            1,
+            "",
+            "error during connect: this error may indicate that the docker daemon is not running",
+        ));
+        assert_eq!(unreachable.severity, SEVERITY_ERROR);
+
+        let denied = docker_daemon_outcome(&capture(1, "", "permission denied while trying to connect to the Docker daemon socket"));
+        assert!(denied.explanation.unwrap().contains("docker 组"));
+    }
+
+    #[test]
+    fn git_identity_and_autocrlf() {
+        assert_eq!(
+            git_identity_outcome(Some("Dev".to_string()), Some("dev@example.com".to_string())).severity,
+            SEVERITY_OK
+        );
+        let missing = git_identity_outcome(Some("Dev".to_string()), None);
+        assert_eq!(missing.severity, SEVERITY_WARNING);
+        assert!(missing.message.contains("user.email"));
+
+        let conflicting = vec![
+            ("file:C:/Program Files/Git/etc/gitconfig".to_string(), "true".to_string()),
+            ("file:C:/Users/dev/.gitconfig".to_string(), "false".to_string()),
+        ];
+        assert_eq!(autocrlf_outcome(&conflicting, true).severity, SEVERITY_WARNING);
+
+        let unix_true = vec![("file:/home/dev/.gitconfig".to_string(), "true".to_string())];
+        let outcome = autocrlf_outcome(&unix_true, false);
+        assert_eq!(outcome.fix.unwrap().args, vec!["config", "--global", "core.autocrlf", "input"]);
+        assert_eq!(autocrlf_outcome(&unix_true, true).severity, SEVERITY_OK);
+        assert_eq!(autocrlf_outcome(&[], false).severity, SEVERITY_OK);
+    }
+
+    #[test]
+    fn java_home_must_match_java_on_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let jdk17 = dir.path().join("jdk-17");
+        let jdk21 = dir.path().join("jdk-21");
+        for jdk in [&jdk17, &jdk21] {
+            std::fs::create_dir_all(jdk.join("bin")).unwrap();
+            std::fs::write(jdk.join("bin").join("java"), "").unwrap();
+        }
+
+        let java21 = jdk21.join("bin").join("java");
+        assert_eq!(java_home_outcome(Some(&jdk21), Some(&java21)).severity, SEVERITY_OK);
+        assert_eq!(java_home_outcome(Some(&jdk17), Some(&java21)).severity, SEVERITY_WARNING);
+        assert_eq!(java_home_outcome(Some(&dir.path().join("jdk-11")), Some(&java21)).severity, SEVERITY_ERROR);
+        assert_eq!(java_home_outcome(None, None).severity, SEVERITY_INFO);
+        assert_eq!(java_home_for(&java21), Some(jdk21));
+        // jdk-2 与 jdk-21 只是前缀相同，不算在其目录内
+        assert!(!is_within(&java21, &dir.path().join("jdk-2")));
+    }
+
+    #[test]
+    fn directory_on_path_and_pip_python_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("go").join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+
+        let with_slash = PathBuf::from(format!("{}/", bin.display()));
+        assert_eq!(dir_on_path_outcome(&bin, &[with_slash], "go").severity, SEVERITY_OK);
+        assert_eq!(dir_on_path_outcome(&bin, &[dir.path().to_path_buf()], "go").severity, SEVERITY_WARNING);
+
+        let pip = "pip 24.0 from C:\\Python312\\Lib\\site-packages\\pip (python 3.12)";
+        assert_eq!(pip_python_outcome("python", "Python 3.12.2", "pip", pip).severity, SEVERITY_OK);
+        let mismatch = pip_python_outcome("python", "Python 3.11.7", "pip", pip);
+        assert_eq!(mismatch.severity, SEVERITY_WARNING);
+        assert!(mismatch.message.contains("3.12") && mismatch.message.contains("3.11"));
+    }
+}
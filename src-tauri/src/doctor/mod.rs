@@ -0,0 +1,127 @@
+pub mod checks;
+
+use rayon::prelude::*;
+
+use crate::contracts::{DoctorCheckResult, DoctorFix, DoctorFixResult, DoctorReport};
+use crate::process_runner::execute_process_with_timeout;
+use crate::runtime::current_timestamp_ms;
+
+pub const DOCTOR_FIX_TIMEOUT_MS: u64 = 60_000;
+
+pub const SEVERITY_OK: &str = "ok";
+pub const SEVERITY_INFO: &str = "info";
+pub const SEVERITY_WARNING: &str = "warning";
+pub const SEVERITY_ERROR: &str = "error";
+
+/// 一项体检：run 返回当前结论，可附带自动修复命令
+pub struct DoctorCheck {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub category: &'static str,
+    pub run: fn() -> DoctorOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorOutcome {
+    pub severity: &'static str,
+    pub message: String,
+    pub explanation: Option<String>,
+    pub fix: Option<DoctorFixAction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoctorFixAction {
+    pub label: String,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl DoctorOutcome {
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self::new(SEVERITY_OK, message, None)
+    }
+
+    /// 依赖的工具未安装等不适用的情况
+    pub fn skipped(message: impl Into<String>) -> Self {
+        Self::new(SEVERITY_INFO, message, None)
+    }
+
+    pub fn new(severity: &'static str, message: impl Into<String>, explanation: Option<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            explanation,
+            fix: None,
+        }
+    }
+
+    pub fn with_fix(mut self, label: impl Into<String>, command: &str, args: &[&str]) -> Self {
+        self.fix = Some(DoctorFixAction {
+            label: label.into(),
+            command: command.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+        });
+        self
+    }
+}
+
+pub fn run_doctor() -> DoctorReport {
+    let checks: Vec<DoctorCheckResult> = checks::doctor_checks()
+        .par_iter()
+        .map(|check| to_result(check, (check.run)()))
+        .collect();
+
+    DoctorReport {
+        errors: checks.iter().filter(|check| check.severity == SEVERITY_ERROR).count(),
+        warnings: checks.iter().filter(|check| check.severity == SEVERITY_WARNING).count(),
+        checks,
+        generated_at_ms: current_timestamp_ms(),
+    }
+}
+
+/// 重新执行该项检查并使用其给出的修复命令，不接受调用方传入的命令
+pub fn apply_doctor_fix_internal(check_id: &str) -> Result<DoctorFixResult, String> {
+    let checks = checks::doctor_checks();
+    let check = checks
+        .iter()
+        .find(|check| check.id == check_id)
+        .ok_or_else(|| format!("未知的检查项：{}", check_id))?;
+
+    let outcome = (check.run)();
+    let fix = outcome
+        .fix
+        .ok_or_else(|| format!("{} 当前没有可自动执行的修复", check.title))?;
+    let capture = execute_process_with_timeout(&fix.command, &fix.args, DOCTOR_FIX_TIMEOUT_MS)?;
+
+    Ok(DoctorFixResult {
+        check_id: check.id.to_string(),
+        command: format_command(&fix),
+        stdout: capture.stdout,
+        stderr: capture.stderr,
+        exit_code: capture.exit_code,
+    })
+}
+
+fn to_result(check: &DoctorCheck, outcome: DoctorOutcome) -> DoctorCheckResult {
+    DoctorCheckResult {
+        id: check.id.to_string(),
+        title: check.title.to_string(),
+        category: check.category.to_string(),
+        severity: outcome.severity.to_string(),
+        message: outcome.message,
+        explanation: outcome.explanation,
+        fix: outcome.fix.map(|fix| DoctorFix {
+            command: format_command(&fix),
+            label: fix.label,
+        }),
+    }
+}
+
+fn format_command(fix: &DoctorFixAction) -> String {
+    let args: Vec<String> = fix
+        .args
+        .iter()
+        .map(|arg| if arg.contains(' ') { format!("\"{}\"", arg) } else { arg.clone() })
+        .collect();
+    format!("{} {}", fix.command, args.join(" "))
+}
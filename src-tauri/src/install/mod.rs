@@ -1,6 +1,8 @@
-use crate::contracts::{InstallResult, PathValidationResult, UninstallResult, WingetStatus};
+use std::path::Path;
+
+use crate::contracts::{InstallResult, PathValidationResult, ToolInstallRecipe, UninstallResult, WingetStatus};
 use crate::process_runner::{execute_process_with_timeout, run_command_with_timeout};
-use crate::tools::TOOL_DETECT_TIMEOUT_MS;
+use crate::tools::{find_catalog_install_recipe, TOOL_DETECT_TIMEOUT_MS};
 
 pub const WINGET_INSTALL_TIMEOUT_MS: u64 = 20 * 60 * 1_000;
 pub const APP_INSTALLER_INSTALL_TIMEOUT_MS: u64 = 10 * 60 * 1_000;
@@ -178,13 +180,20 @@ pub fn install_specs() -> Vec<InstallSpec> {
     ]
 }
 
-pub fn execute_install_item(item_key: &str, install_path: Option<&str>) -> Result<InstallResult, String> {
-    let spec = install_specs()
-        .into_iter()
-        .find(|item| item.key == item_key)
-        .ok_or_else(|| format!("未找到可安装项：{}", item_key))?;
-
-    let plan = resolve_install_plan(spec.key, spec.package_id, install_path)?;
+/// project_path 用于查找项目 `.devenvprobe.toml` 中声明的安装项
+pub fn execute_install_item(
+    item_key: &str,
+    install_path: Option<&str>,
+    project_path: Option<&str>,
+) -> Result<InstallResult, String> {
+    let plan = match install_specs().into_iter().find(|item| item.key == item_key) {
+        Some(spec) => resolve_install_plan(spec.key, spec.package_id, install_path)?,
+        None => {
+            let recipe = find_catalog_install_recipe(item_key, project_path.map(Path::new))?
+                .ok_or_else(|| format!("未找到可安装项：{}", item_key))?;
+            resolve_recipe_install_plan(&recipe, install_path)?
+        }
+    };
     let capture = execute_process_with_timeout(&plan.command, &plan.args, WINGET_INSTALL_TIMEOUT_MS).map_err(|error| {
         if plan.command == "npm" {
             let lowered = error.to_lowercase();
@@ -226,6 +235,36 @@ pub fn resolve_install_plan(
         return Ok(build_python_direct_install_plan(install_path));
     }
 
+    build_winget_install_plan(package_id, install_path)
+}
+
+/// 用户工具目录中声明的安装方式
+pub fn resolve_recipe_install_plan(
+    recipe: &ToolInstallRecipe,
+    install_path: Option<&str>,
+) -> Result<InstallExecutionPlan, String> {
+    if let Some(package_id) = recipe.winget.as_deref() {
+        return build_winget_install_plan(package_id, install_path);
+    }
+
+    if let Some(npm_package) = recipe.npm.as_deref() {
+        return Ok(build_npm_global_install_plan(npm_package));
+    }
+
+    if let Some(command_line) = recipe.command.as_deref() {
+        let mut parts = command_line.split_whitespace().map(ToString::to_string);
+        let command = parts.next().ok_or_else(|| "安装命令为空".to_string())?;
+        return Ok(InstallExecutionPlan {
+            command,
+            args: parts.collect(),
+            package_id: command_line.to_string(),
+        });
+    }
+
+    Err("安装方式未配置".to_string())
+}
+
+fn build_winget_install_plan(package_id: &str, install_path: Option<&str>) -> Result<InstallExecutionPlan, String> {
     let winget_available = run_command_with_timeout("winget", &["--version"], TOOL_DETECT_TIMEOUT_MS).is_ok();
     if winget_available {
         let mut args = vec![
@@ -343,13 +382,15 @@ try {{
     }
 }
 
-pub fn execute_uninstall_item(item_key: &str) -> Result<UninstallResult, String> {
-    let spec = install_specs()
-        .into_iter()
-        .find(|item| item.key == item_key)
-        .ok_or_else(|| format!("未找到可卸载项：{}", item_key))?;
-
-    let plan = resolve_uninstall_plan(spec.key, spec.package_id)?;
+pub fn execute_uninstall_item(item_key: &str, project_path: Option<&str>) -> Result<UninstallResult, String> {
+    let plan = match install_specs().into_iter().find(|item| item.key == item_key) {
+        Some(spec) => resolve_uninstall_plan(spec.key, spec.package_id)?,
+        None => {
+            let recipe = find_catalog_install_recipe(item_key, project_path.map(Path::new))?
+                .ok_or_else(|| format!("未找到可卸载项：{}", item_key))?;
+            resolve_recipe_uninstall_plan(&recipe)?
+        }
+    };
     let capture = execute_process_with_timeout(&plan.command, &plan.args, WINGET_INSTALL_TIMEOUT_MS)?;
 
     Ok(UninstallResult {
@@ -374,6 +415,26 @@ pub fn resolve_uninstall_plan(
         });
     }
 
+    build_winget_uninstall_plan(package_id)
+}
+
+pub fn resolve_recipe_uninstall_plan(recipe: &ToolInstallRecipe) -> Result<InstallExecutionPlan, String> {
+    if let Some(package_id) = recipe.winget.as_deref() {
+        return build_winget_uninstall_plan(package_id);
+    }
+
+    if let Some(npm_package) = recipe.npm.as_deref() {
+        return Ok(InstallExecutionPlan {
+            command: "npm".to_string(),
+            args: vec!["uninstall".to_string(), "-g".to_string(), npm_package.to_string()],
+            package_id: format!("npm:{}", npm_package),
+        });
+    }
+
+    Err("自定义安装命令不支持自动卸载".to_string())
+}
+
+fn build_winget_uninstall_plan(package_id: &str) -> Result<InstallExecutionPlan, String> {
     let winget_available = run_command_with_timeout("winget", &["--version"], TOOL_DETECT_TIMEOUT_MS).is_ok();
     if winget_available {
         return Ok(InstallExecutionPlan {
@@ -1,8 +1,5 @@
-use rayon::prelude::*;
-use serde::Serialize;
-use std::future::Future;
-use std::time::Instant;
-use tauri::{AppHandle, LogicalSize, Manager, Size};
+use std::path::Path;
+use tauri::{AppHandle, Emitter, LogicalSize, Manager, Size};
 
 mod process_runner;
 mod contracts;
@@ -13,6 +10,17 @@ mod docker;
 mod deploy;
 mod install;
 mod version;
+mod local_http;
+mod metrics;
+mod api;
+mod project;
+mod doctor;
+mod report;
+mod baseline;
+mod packages;
+mod mirrors;
+mod rustup;
+mod cli;
 
 use contracts::*;
 use runtime::*;
@@ -22,37 +30,23 @@ use docker::*;
 use deploy::*;
 use install::*;
 use version::*;
+use metrics::*;
+use api::*;
+use project::*;
+use doctor::*;
+use report::*;
+use baseline::*;
+use packages::*;
+use mirrors::*;
+use rustup::*;
+
+pub use cli::run_cli;
 
 #[tauri::command]
 async fn get_system_snapshot(app: AppHandle) -> CommandResponse<SystemSnapshot> {
     let runtime_state = app.state::<AppRuntimeState>().inner().clone();
 
-    with_timing_async(async move {
-        if let Some(snapshot) = runtime_state.get_snapshot() {
-            return Ok(snapshot);
-        }
-
-        match run_blocking(query_system_snapshot_quick).await {
-            Ok(snapshot) => {
-                runtime_state.update_snapshot(snapshot.clone(), "quick", false);
-                Ok(snapshot)
-            }
-            Err(error) => {
-                if let Some(mut snapshot) = runtime_state.get_snapshot() {
-                    snapshot.is_stale = Some(true);
-                    return Ok(snapshot);
-                }
-
-                let mut snapshot = build_placeholder_snapshot();
-                snapshot.is_stale = Some(true);
-                snapshot.sample_mode = Some("quick".to_string());
-                snapshot.sampled_at_ms = Some(current_timestamp_ms());
-                let _ = error;
-                Ok(snapshot)
-            }
-        }
-    })
-    .await
+    with_timing_async(async move { run_blocking(move || Ok(load_system_snapshot(&runtime_state))).await }).await
 }
 
 #[tauri::command]
@@ -88,27 +82,347 @@ async fn get_system_realtime(app: AppHandle) -> CommandResponse<SystemRealtimeSn
 }
 
 #[tauri::command]
-async fn detect_dev_tools() -> CommandResponse<Vec<ToolStatus>> {
-    with_timing_async(async {
-        let tools = run_blocking(detect_dev_tools_parallel).await?;
+async fn detect_dev_tools(
+    app: AppHandle,
+    project_path: Option<String>,
+    force_refresh: Option<bool>,
+) -> CommandResponse<Vec<ToolStatus>> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+
+    with_timing_async(async move {
+        let reprobe_cache = cache.clone();
+        let (specs, tools) = run_blocking(move || {
+            let catalog = load_tool_catalog(project_path.as_deref().map(Path::new));
+            let tools = detect_tools_cached(&catalog.specs, &cache, force_refresh.unwrap_or(false))?;
+            Ok((catalog.specs, tools))
+        })
+        .await?;
+
+        spawn_reprobe(app, specs, tools.clone(), reprobe_cache);
         Ok(tools)
     })
     .await
 }
 
-fn detect_dev_tools_parallel() -> Result<Vec<ToolStatus>, String> {
-    let max_workers = std::thread::available_parallelism()
-        .map(|count| count.get())
-        .unwrap_or(4)
-        .min(8);
+/// 超时的工具在后台用更长时间重新探测，结果通过事件推送
+fn spawn_reprobe(app: AppHandle, specs: Vec<ToolSpec>, tools: Vec<ToolStatus>, cache: ToolCacheState) {
+    if !tools.iter().any(|tool| tool.timed_out) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let reprobed = run_blocking(move || Ok(reprobe_timed_out_tools(&specs, &tools, &cache))).await;
+        if let Some(reprobed) = reprobed.ok().filter(|reprobed| !reprobed.is_empty()) {
+            let _ = app.emit(TOOLS_REPROBED_EVENT, reprobed);
+        }
+    });
+}
+
+#[tauri::command]
+async fn detect_single_tool(
+    app: AppHandle,
+    name: String,
+    project_path: Option<String>,
+    force_refresh: Option<bool>,
+) -> CommandResponse<ToolStatus> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
 
-    let pool = rayon::ThreadPoolBuilder::new()
-        .num_threads(max_workers)
-        .build()
-        .map_err(|error| format!("初始化工具探测线程池失败: {}", error))?;
+    with_timing_async(async move {
+        let reprobe_cache = cache.clone();
+        let (spec, tool) = run_blocking(move || {
+            let catalog = load_tool_catalog(project_path.as_deref().map(Path::new));
+            let spec = find_tool_spec(&catalog.specs, &name).ok_or_else(|| format!("工具目录中没有 {}", name))?;
+            let tool = detect_tools_cached(std::slice::from_ref(spec), &cache, force_refresh.unwrap_or(false))?
+                .pop()
+                .ok_or_else(|| format!("探测 {} 失败", name))?;
+            Ok((spec.clone(), tool))
+        })
+        .await?;
 
-    let tools = pool.install(|| default_tool_specs().par_iter().map(detect_tool).collect::<Vec<_>>());
-    Ok(tools)
+        spawn_reprobe(app, vec![spec], vec![tool.clone()], reprobe_cache);
+        Ok(tool)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn get_tool_catalog(project_path: Option<String>) -> CommandResponse<ToolCatalogReport> {
+    with_timing_async(async move {
+        run_blocking(move || Ok(load_tool_catalog(project_path.as_deref().map(Path::new)).to_report())).await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn find_all_installations(tool: String) -> CommandResponse<ToolInstallationsReport> {
+    with_timing_async(async move { run_blocking(move || tools::find_all_installations(&tool)).await }).await
+}
+
+#[tauri::command]
+async fn get_version_managers() -> CommandResponse<Vec<VersionManagerStatus>> {
+    with_timing_async(async { run_blocking(|| Ok(detect_version_managers())).await }).await
+}
+
+#[tauri::command]
+async fn install_managed_version(
+    app: AppHandle,
+    manager: String,
+    version: String,
+    tool: Option<String>,
+) -> CommandResponse<VersionManagerActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || {
+            run_version_manager_action(&manager, tools::managers::MANAGER_ACTION_INSTALL, tool.as_deref(), &version)
+        })
+        .await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn uninstall_managed_version(
+    app: AppHandle,
+    manager: String,
+    version: String,
+    tool: Option<String>,
+) -> CommandResponse<VersionManagerActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || {
+            run_version_manager_action(&manager, tools::managers::MANAGER_ACTION_UNINSTALL, tool.as_deref(), &version)
+        })
+        .await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn set_default_managed_version(
+    app: AppHandle,
+    manager: String,
+    version: String,
+    tool: Option<String>,
+) -> CommandResponse<VersionManagerActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || {
+            run_version_manager_action(&manager, tools::managers::MANAGER_ACTION_SET_DEFAULT, tool.as_deref(), &version)
+        })
+        .await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn check_project_requirements(project_path: String) -> CommandResponse<ProjectRequirementsReport> {
+    with_timing_async(async move { run_blocking(move || check_project_requirements_internal(&project_path)).await })
+        .await
+}
+
+#[tauri::command]
+async fn check_project_pins(project_path: String) -> CommandResponse<ProjectPinsReport> {
+    with_timing_async(async move { run_blocking(move || check_project_pins_internal(&project_path)).await })
+        .await
+}
+
+#[tauri::command]
+async fn run_doctor_checks() -> CommandResponse<DoctorReport> {
+    with_timing_async(async { run_blocking(|| Ok(run_doctor())).await }).await
+}
+
+#[tauri::command]
+async fn apply_doctor_fix(check_id: String) -> CommandResponse<DoctorFixResult> {
+    with_timing_async(async move { run_blocking(move || apply_doctor_fix_internal(&check_id)).await }).await
+}
+
+#[tauri::command]
+async fn export_environment_report(
+    app: AppHandle,
+    format: String,
+    project_path: Option<String>,
+    output_path: Option<String>,
+) -> CommandResponse<ExportedReport> {
+    let runtime_state = app.state::<AppRuntimeState>().inner().clone();
+    let cache = app.state::<ToolCacheState>().inner().clone();
+
+    with_timing_async(async move {
+        run_blocking(move || {
+            let catalog = load_tool_catalog(project_path.as_deref().map(Path::new));
+            let tools = detect_tools_cached(&catalog.specs, &cache, false)?;
+            // 没有 .devenvprobe.toml 的项目不附带要求检查结果
+            let requirements = project_path
+                .as_deref()
+                .and_then(|path| check_project_requirements_internal(path).ok());
+            let report = build_environment_report(load_system_snapshot(&runtime_state), tools, requirements);
+            report::export_environment_report(&report, &format, output_path.as_deref())
+        })
+        .await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn diff_environment_reports(a: String, b: String) -> CommandResponse<EnvironmentReportDiff> {
+    with_timing_async(async move { run_blocking(move || diff_environment_reports_internal(&a, &b)).await }).await
+}
+
+#[tauri::command]
+async fn check_baseline_drift(app: AppHandle, source: Option<String>) -> CommandResponse<BaselineDriftReport> {
+    let state = app.state::<BaselineScheduleState>().inner().clone();
+    let cache = app.state::<ToolCacheState>().inner().clone();
+
+    with_timing_async(async move {
+        let source = source
+            .or_else(|| state.schedule().source)
+            .ok_or_else(|| "未指定基线文件或地址".to_string())?;
+        let report = check_baseline_drift_internal(&source, cache).await?;
+        state.record(&report);
+        Ok(report)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn get_baseline_schedule(app: AppHandle) -> CommandResponse<BaselineScheduleStatus> {
+    let state = app.state::<BaselineScheduleState>().inner().clone();
+    with_timing_async(async move { Ok(state.status()) })
+    .await
+}
+
+#[tauri::command]
+async fn set_baseline_schedule(app: AppHandle, schedule: BaselineSchedule) -> CommandResponse<BaselineSchedule> {
+    let state = app.state::<BaselineScheduleState>().inner().clone();
+    with_timing_async(async move { run_blocking(move || state.update(schedule)).await }).await
+}
+
+#[tauri::command]
+async fn list_global_packages(ecosystem: Option<String>) -> CommandResponse<Vec<PackageEcosystemInventory>> {
+    with_timing_async(async move {
+        run_blocking(move || list_global_packages_internal(ecosystem.as_deref())).await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn uninstall_global_package(
+    app: AppHandle,
+    ecosystem: String,
+    name: String,
+) -> CommandResponse<GlobalPackageActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || run_global_package_action(&ecosystem, PACKAGE_ACTION_UNINSTALL, &name)).await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn upgrade_global_package(
+    app: AppHandle,
+    ecosystem: String,
+    name: String,
+) -> CommandResponse<GlobalPackageActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || run_global_package_action(&ecosystem, PACKAGE_ACTION_UPGRADE, &name)).await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn get_mirror_status() -> CommandResponse<Vec<MirrorEcosystemStatus>> {
+    with_timing_async(async move { run_blocking(|| Ok(get_mirror_status_internal())).await }).await
+}
+
+#[tauri::command]
+async fn apply_mirror_preset(
+    ecosystem: String,
+    preset: String,
+    custom_url: Option<String>,
+) -> CommandResponse<MirrorApplyResult> {
+    with_timing_async(async move {
+        run_blocking(move || apply_mirror_preset_internal(&ecosystem, &preset, custom_url.as_deref())).await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn restore_mirror_backup(ecosystem: String) -> CommandResponse<MirrorEcosystemStatus> {
+    with_timing_async(async move { run_blocking(move || restore_mirror_backup_internal(&ecosystem)).await }).await
+}
+
+#[tauri::command]
+async fn measure_mirror_latency(ecosystem: Option<String>) -> CommandResponse<Vec<MirrorLatency>> {
+    with_timing_async(async move { measure_mirror_latency_internal(ecosystem.as_deref()).await }).await
+}
+
+#[tauri::command]
+async fn get_rustup_status(toolchain: Option<String>, project_path: Option<String>) -> CommandResponse<RustupStatus> {
+    with_timing_async(async move {
+        run_blocking(move || get_rustup_status_internal(toolchain.as_deref(), project_path.as_deref())).await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn run_rustup_action(
+    app: AppHandle,
+    kind: String,
+    action: String,
+    name: String,
+    toolchain: Option<String>,
+) -> CommandResponse<RustupActionResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        let result = run_blocking(move || {
+            run_rustup_action_internal(&kind, &action, &name, toolchain.as_deref(), |line| {
+                let _ = app.emit(RUSTUP_OUTPUT_EVENT, line);
+            })
+        })
+        .await;
+        cache.invalidate();
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+async fn get_tool_history(
+    app: AppHandle,
+    tool: Option<String>,
+    since_ms: Option<u64>,
+) -> CommandResponse<Vec<ToolHistoryEntry>> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move {
+        run_blocking(move || Ok(cache.history().entries(tool.as_deref(), since_ms))).await
+    })
+    .await
+}
+
+#[tauri::command]
+async fn check_tool_updates(app: AppHandle) -> CommandResponse<Vec<ToolUpdateStatus>> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
+    with_timing_async(async move { check_tool_updates_internal(cache).await }).await
+}
+
+#[tauri::command]
+async fn get_eol_dataset_info() -> CommandResponse<EolDatasetInfo> {
+    with_timing_async(async move { run_blocking(|| Ok(get_eol_dataset_info_internal())).await }).await
+}
+
+#[tauri::command]
+async fn update_eol_dataset() -> CommandResponse<EolDatasetInfo> {
+    with_timing_async(async move { update_eol_dataset_internal().await }).await
 }
 
 #[tauri::command]
@@ -135,17 +449,35 @@ async fn execute_deploy_step(request: DeployStepRequest) -> CommandResponse<Depl
 }
 
 #[tauri::command]
-async fn install_market_item(item_key: String, install_path: Option<String>) -> CommandResponse<InstallResult> {
+async fn install_market_item(
+    app: AppHandle,
+    item_key: String,
+    install_path: Option<String>,
+    project_path: Option<String>,
+) -> CommandResponse<InstallResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
     with_timing_async(async move {
-        run_blocking(move || execute_install_item(&item_key, install_path.as_deref())).await
+        let result = run_blocking(move || {
+            execute_install_item(&item_key, install_path.as_deref(), project_path.as_deref())
+        })
+        .await;
+        cache.invalidate();
+        result
     })
     .await
 }
 
 #[tauri::command]
-async fn uninstall_market_item(item_key: String) -> CommandResponse<UninstallResult> {
+async fn uninstall_market_item(
+    app: AppHandle,
+    item_key: String,
+    project_path: Option<String>,
+) -> CommandResponse<UninstallResult> {
+    let cache = app.state::<ToolCacheState>().inner().clone();
     with_timing_async(async move {
-        run_blocking(move || execute_uninstall_item(&item_key)).await
+        let result = run_blocking(move || execute_uninstall_item(&item_key, project_path.as_deref())).await;
+        cache.invalidate();
+        result
     })
     .await
 }
@@ -228,36 +560,73 @@ async fn update_image_and_restart(
     .await
 }
 
-async fn with_timing_async<T, Fut>(operation: Fut) -> CommandResponse<T>
-where
-    T: Serialize,
-    Fut: Future<Output = Result<T, String>>,
-{
-    let start = Instant::now();
-    match operation.await {
-        Ok(data) => CommandResponse {
-            ok: true,
-            data: Some(data),
-            error: None,
-            elapsed_ms: start.elapsed().as_millis(),
-        },
-        Err(error) => CommandResponse {
-            ok: false,
-            data: None,
-            error: Some(error),
-            elapsed_ms: start.elapsed().as_millis(),
-        },
-    }
+// ============================================================================
+// Metrics Exporter Commands
+// ============================================================================
+
+#[tauri::command]
+async fn get_metrics_exporter_status(app: AppHandle) -> CommandResponse<MetricsExporterStatus> {
+    let exporter = app.state::<MetricsExporterState>().inner().clone();
+    with_timing_async(async move { Ok(exporter.status()) }).await
 }
 
-async fn run_blocking<T, F>(operation: F) -> Result<T, String>
-where
-    T: Send + 'static,
-    F: FnOnce() -> Result<T, String> + Send + 'static,
-{
-    tauri::async_runtime::spawn_blocking(operation)
-        .await
-        .map_err(|error| format!("后台任务执行失败: {}", error))?
+#[tauri::command]
+async fn start_metrics_exporter(app: AppHandle, port: Option<u16>) -> CommandResponse<MetricsExporterStatus> {
+    let exporter = app.state::<MetricsExporterState>().inner().clone();
+    let runtime_state = app.state::<AppRuntimeState>().inner().clone();
+    let tool_cache = app.state::<ToolCacheState>().inner().clone();
+
+    with_timing_async(async move { exporter.start(port, runtime_state, tool_cache).await }).await
+}
+
+#[tauri::command]
+async fn stop_metrics_exporter(app: AppHandle) -> CommandResponse<MetricsExporterStatus> {
+    let exporter = app.state::<MetricsExporterState>().inner().clone();
+    with_timing_async(async move { Ok(exporter.stop()) }).await
+}
+
+// ============================================================================
+// Local API Commands
+// ============================================================================
+
+#[tauri::command]
+async fn get_local_api_status(app: AppHandle) -> CommandResponse<LocalApiStatus> {
+    let local_api = app.state::<LocalApiState>().inner().clone();
+    with_timing_async(async move { Ok(local_api.status()) }).await
+}
+
+#[tauri::command]
+async fn start_local_api(app: AppHandle, port: Option<u16>) -> CommandResponse<LocalApiStatus> {
+    let local_api = app.state::<LocalApiState>().inner().clone();
+    let context = build_rpc_context(&app);
+
+    with_timing_async(async move { local_api.start(port, context).await }).await
+}
+
+#[tauri::command]
+async fn stop_local_api(app: AppHandle) -> CommandResponse<LocalApiStatus> {
+    let local_api = app.state::<LocalApiState>().inner().clone();
+    with_timing_async(async move { Ok(local_api.stop()) }).await
+}
+
+#[tauri::command]
+async fn set_local_api_method_enabled(app: AppHandle, method: String, enabled: bool) -> CommandResponse<LocalApiStatus> {
+    let local_api = app.state::<LocalApiState>().inner().clone();
+    with_timing_async(async move { local_api.set_method_enabled(&method, enabled) }).await
+}
+
+#[tauri::command]
+async fn regenerate_local_api_token(app: AppHandle) -> CommandResponse<LocalApiStatus> {
+    let local_api = app.state::<LocalApiState>().inner().clone();
+    with_timing_async(async move { local_api.regenerate_token() }).await
+}
+
+fn build_rpc_context(app: &AppHandle) -> RpcContext {
+    RpcContext {
+        runtime_state: app.state::<AppRuntimeState>().inner().clone(),
+        version_state: app.state::<VersionRuntimeState>().inner().clone(),
+        tool_cache: app.state::<ToolCacheState>().inner().clone(),
+    }
 }
 
 fn adapt_main_window_for_monitor(app: &tauri::AppHandle) {
@@ -294,6 +663,10 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .manage(AppRuntimeState::default())
         .manage(VersionRuntimeState::new())
+        .manage(MetricsExporterState::default())
+        .manage(LocalApiState::default())
+        .manage(ToolCacheState::default())
+        .manage(BaselineScheduleState::default())
         .setup(|app| {
             adapt_main_window_for_monitor(app.handle());
 
@@ -303,12 +676,65 @@ pub fn run() {
                 query_system_realtime_quick,
                 query_system_snapshot_precise,
             );
+
+            let baseline_state = app.state::<BaselineScheduleState>().inner().clone();
+            let tool_cache = app.state::<ToolCacheState>().inner().clone();
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(run_baseline_schedule(baseline_state, tool_cache, move |report| {
+                let _ = handle.emit(BASELINE_DRIFT_EVENT, report.clone());
+            }));
+
+            if let Some(port) = metrics_port_from_env() {
+                let exporter = app.state::<MetricsExporterState>().inner().clone();
+                let runtime_state = app.state::<AppRuntimeState>().inner().clone();
+                let tool_cache = app.state::<ToolCacheState>().inner().clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = exporter.start(Some(port), runtime_state, tool_cache).await;
+                });
+            }
+
+            if let Some(port) = local_api_port_from_env() {
+                let local_api = app.state::<LocalApiState>().inner().clone();
+                let context = build_rpc_context(app.handle());
+                tauri::async_runtime::spawn(async move {
+                    let _ = local_api.start(Some(port), context).await;
+                });
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_system_snapshot,
             get_system_realtime,
             detect_dev_tools,
+            detect_single_tool,
+            get_tool_catalog,
+            find_all_installations,
+            get_version_managers,
+            install_managed_version,
+            uninstall_managed_version,
+            set_default_managed_version,
+            check_project_requirements,
+            check_project_pins,
+            run_doctor_checks,
+            apply_doctor_fix,
+            export_environment_report,
+            diff_environment_reports,
+            check_baseline_drift,
+            get_baseline_schedule,
+            set_baseline_schedule,
+            list_global_packages,
+            uninstall_global_package,
+            upgrade_global_package,
+            get_mirror_status,
+            apply_mirror_preset,
+            restore_mirror_backup,
+            measure_mirror_latency,
+            get_rustup_status,
+            run_rustup_action,
+            get_tool_history,
+            check_tool_updates,
+            get_eol_dataset_info,
+            update_eol_dataset,
             run_docker_action,
             get_docker_overview_batch,
             list_git_branches,
@@ -322,6 +748,14 @@ pub fn run() {
             install_app_installer_auto,
             check_image_version,
             update_image_and_restart,
+            get_metrics_exporter_status,
+            start_metrics_exporter,
+            stop_metrics_exporter,
+            get_local_api_status,
+            start_local_api,
+            stop_local_api,
+            set_local_api_method_enabled,
+            regenerate_local_api_token,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -349,6 +783,9 @@ mod tests {
             used_memory_gb: 6.0,
             memory_usage_percent: 37.5,
             disks: Vec::new(),
+            sensors: None,
+            environment_kind: None,
+            effective_limits: None,
             sample_mode: None,
             sampled_at_ms: None,
             is_stale: None,
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// 请求头与请求体上限，本地服务只处理小体量请求
+pub const MAX_HEADER_BYTES: usize = 16 * 1024;
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+pub const REQUEST_READ_TIMEOUT_MS: u64 = 5_000;
+
+/// 本地服务只允许绑定回环地址
+pub fn loopback_addr(port: u16) -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    /// 头名称统一转为小写
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// 读取 `Authorization: Bearer <token>` 中的令牌
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        if scheme.eq_ignore_ascii_case("bearer") {
+            Some(token.trim()).filter(|token| !token.is_empty())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type: content_type.to_string(),
+            body: body.into(),
+        }
+    }
+
+    pub fn text(status: u16, body: &str) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", body.as_bytes().to_vec())
+    }
+
+    pub fn json<T: serde::Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self::new(status, "application/json; charset=utf-8", body),
+            Err(error) => Self::text(500, &format!("序列化响应失败: {}", error)),
+        }
+    }
+}
+
+pub fn parse_http_request(raw: &[u8]) -> Result<Option<(HttpRequest, usize)>, String> {
+    let Some(header_end) = find_header_end(raw) else {
+        if raw.len() > MAX_HEADER_BYTES {
+            return Err("请求头过大".to_string());
+        }
+        return Ok(None);
+    };
+
+    let head = std::str::from_utf8(&raw[..header_end]).map_err(|_| "请求头不是有效的 UTF-8".to_string())?;
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().ok_or_else(|| "缺少请求方法".to_string())?.to_uppercase();
+    let target = parts.next().ok_or_else(|| "缺少请求路径".to_string())?;
+
+    let mut headers = HashMap::new();
+    for line in lines.filter(|line| !line.is_empty()) {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .map(|value| value.parse::<usize>().map_err(|_| "Content-Length 无效".to_string()))
+        .transpose()?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err("请求体过大".to_string());
+    }
+
+    let body_start = header_end + 4;
+    if raw.len() < body_start + content_length {
+        return Ok(None);
+    }
+
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let body = raw[body_start..body_start + content_length].to_vec();
+
+    Ok(Some((
+        HttpRequest { method, path, headers, body },
+        body_start + content_length,
+    )))
+}
+
+pub async fn read_http_request(stream: &mut TcpStream) -> Result<HttpRequest, String> {
+    let mut buffer: Vec<u8> = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+
+    let read_all = async {
+        loop {
+            if let Some((request, _)) = parse_http_request(&buffer)? {
+                return Ok(request);
+            }
+
+            let read = stream.read(&mut chunk).await.map_err(|error| error.to_string())?;
+            if read == 0 {
+                return Err("连接在请求完成前关闭".to_string());
+            }
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+    };
+
+    tokio::time::timeout(Duration::from_millis(REQUEST_READ_TIMEOUT_MS), read_all)
+        .await
+        .map_err(|_| "读取请求超时".to_string())?
+}
+
+pub async fn write_http_response(stream: &mut TcpStream, response: &HttpResponse) -> Result<(), String> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        status_reason(response.status),
+        response.content_type,
+        response.body.len()
+    );
+
+    stream.write_all(head.as_bytes()).await.map_err(|error| error.to_string())?;
+    stream.write_all(&response.body).await.map_err(|error| error.to_string())?;
+    stream.shutdown().await.map_err(|error| error.to_string())
+}
+
+fn find_header_end(raw: &[u8]) -> Option<usize> {
+    raw.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request_strips_query_and_consumes_body() {
+        let raw = b"POST /metrics?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\n\r\n{}";
+
+        let (request, consumed) = parse_http_request(raw).unwrap().unwrap();
+        assert_eq!(consumed, raw.len());
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/metrics");
+        assert_eq!(request.body, b"{}");
+        assert_eq!(request.header("HOST"), Some("localhost"));
+    }
+
+    #[test]
+    fn bearer_token_is_case_insensitive_on_scheme() {
+        let raw = b"POST /rpc HTTP/1.1\r\nAuthorization: bearer abc123\r\n\r\n";
+        let (request, _) = parse_http_request(raw).unwrap().unwrap();
+        assert_eq!(request.bearer_token(), Some("abc123"));
+
+        let raw = b"POST /rpc HTTP/1.1\r\nAuthorization: Basic abc123\r\n\r\n";
+        let (request, _) = parse_http_request(raw).unwrap().unwrap();
+        assert_eq!(request.bearer_token(), None);
+    }
+
+    #[test]
+    fn parse_incomplete_request_waits_for_more_bytes() {
+        assert!(parse_http_request(b"GET /metrics HTTP/1.1\r\nHost: x").unwrap().is_none());
+        assert!(parse_http_request(b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n{}").unwrap().is_none());
+    }
+}
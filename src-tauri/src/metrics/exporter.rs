@@ -0,0 +1,204 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::contracts::{DockerCommandResult, MetricsExporterStatus, ToolStatus};
+use crate::docker::execute_docker_overview_batch;
+use crate::local_http::{loopback_addr, read_http_request, write_http_response, HttpResponse};
+use crate::runtime::{current_timestamp_ms, AppRuntimeState};
+use crate::tools::{detect_tools_cached, load_tool_catalog, ToolCacheState};
+
+use super::render::{render_openmetrics, MetricsInput};
+
+pub const METRICS_DEFAULT_PORT: u16 = 9464;
+/// 设置该环境变量即在启动时开启导出器（值为端口号）
+pub const METRICS_PORT_ENV: &str = "DEVENVPROBE_METRICS_PORT";
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// 工具结果按较长周期从工具缓存重新读取；docker 数据按短周期复用
+const TOOLS_REFRESH_INTERVAL_MS: u64 = 5 * 60 * 1_000;
+const DOCKER_REFRESH_INTERVAL_MS: u64 = 15 * 1_000;
+
+#[derive(Default)]
+struct ScrapeCache {
+    tools: Option<(Vec<ToolStatus>, u64)>,
+    docker: Option<(Vec<DockerCommandResult>, u64)>,
+}
+
+struct RunningExporter {
+    port: u16,
+    started_at_ms: u64,
+    shutdown: oneshot::Sender<()>,
+}
+
+#[derive(Clone, Default)]
+pub struct MetricsExporterState {
+    running: Arc<Mutex<Option<RunningExporter>>>,
+    cache: Arc<tokio::sync::Mutex<ScrapeCache>>,
+}
+
+impl MetricsExporterState {
+    pub fn status(&self) -> MetricsExporterStatus {
+        let running = self.running.lock().unwrap();
+        match running.as_ref() {
+            Some(exporter) => MetricsExporterStatus {
+                running: true,
+                port: Some(exporter.port),
+                endpoint: Some(format!("http://{}/metrics", loopback_addr(exporter.port))),
+                started_at_ms: Some(exporter.started_at_ms),
+            },
+            None => MetricsExporterStatus {
+                running: false,
+                port: None,
+                endpoint: None,
+                started_at_ms: None,
+            },
+        }
+    }
+
+    /// 在 127.0.0.1 上启动 /metrics 监听；已在运行时直接返回当前状态
+    pub async fn start(
+        &self,
+        port: Option<u16>,
+        runtime_state: AppRuntimeState,
+        tool_cache: ToolCacheState,
+    ) -> Result<MetricsExporterStatus, String> {
+        if self.running.lock().unwrap().is_some() {
+            return Ok(self.status());
+        }
+
+        let listener = TcpListener::bind(loopback_addr(port.unwrap_or(METRICS_DEFAULT_PORT)))
+            .await
+            .map_err(|error| format!("指标导出端口绑定失败: {}", error))?;
+        let bound_port = listener
+            .local_addr()
+            .map_err(|error| error.to_string())?
+            .port();
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+        let cache = self.cache.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => {
+                        let Ok((mut stream, _)) = accepted else {
+                            continue;
+                        };
+                        let cache = cache.clone();
+                        let runtime_state = runtime_state.clone();
+                        let tool_cache = tool_cache.clone();
+                        tokio::spawn(async move {
+                            let response = match read_http_request(&mut stream).await {
+                                Ok(request) => {
+                                    handle_request(&request.method, &request.path, &runtime_state, &tool_cache, &cache).await
+                                }
+                                Err(error) => HttpResponse::text(400, &error),
+                            };
+                            let _ = write_http_response(&mut stream, &response).await;
+                        });
+                    }
+                }
+            }
+        });
+
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            let _ = shutdown_tx.send(());
+            drop(running);
+            return Ok(self.status());
+        }
+
+        *running = Some(RunningExporter {
+            port: bound_port,
+            started_at_ms: current_timestamp_ms(),
+            shutdown: shutdown_tx,
+        });
+        drop(running);
+
+        Ok(self.status())
+    }
+
+    pub fn stop(&self) -> MetricsExporterStatus {
+        if let Some(exporter) = self.running.lock().unwrap().take() {
+            let _ = exporter.shutdown.send(());
+        }
+
+        self.status()
+    }
+}
+
+async fn handle_request(
+    method: &str,
+    path: &str,
+    runtime_state: &AppRuntimeState,
+    tool_cache: &ToolCacheState,
+    cache: &tokio::sync::Mutex<ScrapeCache>,
+) -> HttpResponse {
+    if method != "GET" {
+        return HttpResponse::text(405, "仅支持 GET");
+    }
+
+    match path {
+        "/metrics" => {
+            let input = collect_metrics_input(runtime_state, tool_cache, cache).await;
+            HttpResponse::new(200, OPENMETRICS_CONTENT_TYPE, render_openmetrics(&input).into_bytes())
+        }
+        "/" => HttpResponse::text(200, "DevEnvProbe metrics exporter: GET /metrics"),
+        _ => HttpResponse::text(404, "not found"),
+    }
+}
+
+async fn collect_metrics_input(
+    runtime_state: &AppRuntimeState,
+    tool_cache: &ToolCacheState,
+    cache: &tokio::sync::Mutex<ScrapeCache>,
+) -> MetricsInput {
+    let now = current_timestamp_ms();
+    let expired = |refreshed: Option<u64>, interval: u64| {
+        refreshed.map(|refreshed_at| now.saturating_sub(refreshed_at) >= interval).unwrap_or(true)
+    };
+
+    // 只在判断与写回时持锁，探测期间不阻塞其他抓取；工具结果与界面共用工具缓存
+    let (tools_expired, docker_expired) = {
+        let cache = cache.lock().await;
+        (
+            expired(cache.tools.as_ref().map(|(_, at)| *at), TOOLS_REFRESH_INTERVAL_MS),
+            expired(cache.docker.as_ref().map(|(_, at)| *at), DOCKER_REFRESH_INTERVAL_MS),
+        )
+    };
+
+    if tools_expired {
+        let tool_cache = tool_cache.clone();
+        let detected = tokio::task::spawn_blocking(move || {
+            detect_tools_cached(&load_tool_catalog(None).specs, &tool_cache, false)
+        })
+        .await;
+        if let Ok(Ok(tools)) = detected {
+            cache.lock().await.tools = Some((tools, current_timestamp_ms()));
+        }
+    }
+
+    if docker_expired {
+        if let Ok(Ok(results)) = tokio::task::spawn_blocking(|| execute_docker_overview_batch("quick")).await {
+            cache.lock().await.docker = Some((results, current_timestamp_ms()));
+        }
+    }
+
+    let cache = cache.lock().await;
+    MetricsInput {
+        snapshot: runtime_state.get_snapshot(),
+        tools: cache.tools.as_ref().map(|(tools, _)| tools.clone()),
+        tools_refreshed_at_ms: cache.tools.as_ref().map(|(_, refreshed_at)| *refreshed_at),
+        docker: cache.docker.as_ref().map(|(results, _)| results.clone()),
+    }
+}
+
+/// 读取启动时的导出端口配置；未设置或非法时保持关闭
+pub fn metrics_port_from_env() -> Option<u16> {
+    std::env::var(METRICS_PORT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u16>().ok())
+}
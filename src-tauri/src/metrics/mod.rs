@@ -0,0 +1,4 @@
+pub mod exporter;
+pub mod render;
+
+pub use exporter::{metrics_port_from_env, MetricsExporterState};
@@ -0,0 +1,413 @@
+use std::fmt::Write;
+
+use crate::contracts::{DockerCommandResult, SystemSnapshot, ToolStatus};
+use crate::docker::parse::{parse_docker_containers, parse_docker_stats};
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 一次抓取所需的全部数据，均为可选：缺失的部分直接不输出对应指标
+#[derive(Debug, Clone, Default)]
+pub struct MetricsInput {
+    pub snapshot: Option<SystemSnapshot>,
+    pub tools: Option<Vec<ToolStatus>>,
+    pub tools_refreshed_at_ms: Option<u64>,
+    pub docker: Option<Vec<DockerCommandResult>>,
+}
+
+/// 指标类型；info 与 counter 的样本名按 OpenMetrics 规范带 `_info` / `_total` 后缀
+#[derive(Clone, Copy, PartialEq)]
+enum MetricKind {
+    Gauge,
+    Counter,
+    Info,
+}
+
+impl MetricKind {
+    fn type_name(self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+            MetricKind::Info => "info",
+        }
+    }
+
+    fn sample_suffix(self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "",
+            MetricKind::Counter => "_total",
+            MetricKind::Info => "_info",
+        }
+    }
+}
+
+struct MetricFamily {
+    name: &'static str,
+    help: &'static str,
+    kind: MetricKind,
+    samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl MetricFamily {
+    fn new(kind: MetricKind, name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            kind,
+            samples: Vec::new(),
+        }
+    }
+
+    fn gauge(name: &'static str, help: &'static str) -> Self {
+        Self::new(MetricKind::Gauge, name, help)
+    }
+
+    fn counter(name: &'static str, help: &'static str) -> Self {
+        Self::new(MetricKind::Counter, name, help)
+    }
+
+    fn info(name: &'static str, help: &'static str) -> Self {
+        Self::new(MetricKind::Info, name, help)
+    }
+
+    fn push(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push((labels, value));
+    }
+
+    fn single(name: &'static str, help: &'static str, value: f64) -> Self {
+        let mut family = Self::gauge(name, help);
+        family.push(Vec::new(), value);
+        family
+    }
+}
+
+/// 按 OpenMetrics 文本格式渲染指标，以 `# EOF` 结尾
+pub fn render_openmetrics(input: &MetricsInput) -> String {
+    let mut families: Vec<MetricFamily> = Vec::new();
+
+    if let Some(snapshot) = &input.snapshot {
+        families.extend(snapshot_families(snapshot));
+    }
+
+    if let Some(tools) = &input.tools {
+        families.extend(tool_families(tools, input.tools_refreshed_at_ms));
+    }
+
+    if let Some(docker) = &input.docker {
+        families.extend(docker_families(docker));
+    }
+
+    let mut output = String::new();
+    for family in families.iter().filter(|family| !family.samples.is_empty()) {
+        let _ = writeln!(output, "# TYPE {} {}", family.name, family.kind.type_name());
+        let _ = writeln!(output, "# HELP {} {}", family.name, family.help);
+        for (labels, value) in &family.samples {
+            output.push_str(family.name);
+            output.push_str(family.kind.sample_suffix());
+            if !labels.is_empty() {
+                let rendered: Vec<String> = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                    .collect();
+                let _ = write!(output, "{{{}}}", rendered.join(","));
+            }
+            let _ = writeln!(output, " {}", format_value(*value));
+        }
+    }
+
+    output.push_str("# EOF\n");
+    output
+}
+
+fn snapshot_families(snapshot: &SystemSnapshot) -> Vec<MetricFamily> {
+    let mut info = MetricFamily::info("devenvprobe_system", "Static host information.");
+    info.push(
+        vec![
+            ("host", snapshot.host_name.clone()),
+            ("os", snapshot.os_name.clone()),
+            ("os_version", snapshot.os_version.clone()),
+            ("build", snapshot.build_number.clone()),
+            ("arch", snapshot.architecture.clone()),
+            ("cpu_model", snapshot.cpu_model.clone()),
+            ("environment", snapshot.environment_kind.clone().unwrap_or_default()),
+        ],
+        1.0,
+    );
+
+    let mut families = vec![
+        info,
+        MetricFamily::single("devenvprobe_uptime_seconds", "Host uptime in seconds.", snapshot.uptime_seconds as f64),
+        MetricFamily::single("devenvprobe_cpu_cores", "Physical CPU cores.", snapshot.cpu_cores as f64),
+        MetricFamily::single("devenvprobe_cpu_logical_cores", "Logical CPU cores.", snapshot.cpu_logical_cores as f64),
+        MetricFamily::single("devenvprobe_cpu_usage_percent", "CPU usage percent.", snapshot.cpu_usage_percent),
+        MetricFamily::single("devenvprobe_memory_total_bytes", "Total physical memory in bytes.", snapshot.total_memory_gb * BYTES_PER_GB),
+        MetricFamily::single("devenvprobe_memory_used_bytes", "Used physical memory in bytes.", snapshot.used_memory_gb * BYTES_PER_GB),
+        MetricFamily::single("devenvprobe_memory_usage_percent", "Memory usage percent.", snapshot.memory_usage_percent),
+        MetricFamily::single("devenvprobe_snapshot_stale", "Whether the last system sample is stale.", bool_value(snapshot.is_stale.unwrap_or(false))),
+    ];
+
+    if let Some(sampled_at_ms) = snapshot.sampled_at_ms {
+        families.push(MetricFamily::single(
+            "devenvprobe_snapshot_sampled_timestamp_seconds",
+            "Unix time of the last system sample.",
+            sampled_at_ms as f64 / 1000.0,
+        ));
+    }
+
+    let mut disk_total = MetricFamily::gauge("devenvprobe_disk_total_bytes", "Disk capacity in bytes.");
+    let mut disk_used = MetricFamily::gauge("devenvprobe_disk_used_bytes", "Disk used space in bytes.");
+    let mut disk_usage = MetricFamily::gauge("devenvprobe_disk_usage_percent", "Disk usage percent.");
+    for disk in &snapshot.disks {
+        let labels = vec![("disk", disk.name.clone()), ("mount_point", disk.mount_point.clone())];
+        disk_total.push(labels.clone(), disk.total_gb * BYTES_PER_GB);
+        disk_used.push(labels.clone(), disk.used_gb * BYTES_PER_GB);
+        disk_usage.push(labels, disk.usage_percent);
+    }
+    families.extend([disk_total, disk_used, disk_usage]);
+
+    if let Some(limits) = &snapshot.effective_limits {
+        if let Some(cores) = limits.effective_cpu_cores {
+            families.push(MetricFamily::single("devenvprobe_effective_cpu_cores", "CPU cores available after cgroup quota.", cores));
+        }
+        if let Some(memory_gb) = limits.effective_memory_gb {
+            families.push(MetricFamily::single(
+                "devenvprobe_effective_memory_bytes",
+                "Memory available after cgroup limit in bytes.",
+                memory_gb * BYTES_PER_GB,
+            ));
+        }
+    }
+
+    if let Some(sensors) = &snapshot.sensors {
+        let mut temperatures = MetricFamily::gauge("devenvprobe_temperature_celsius", "Sensor temperature in degrees Celsius.");
+        for reading in &sensors.temperatures {
+            temperatures.push(vec![("source", reading.source.clone()), ("sensor", reading.label.clone())], reading.celsius);
+        }
+
+        let mut fans = MetricFamily::gauge("devenvprobe_fan_rpm", "Fan speed in RPM.");
+        for fan in &sensors.fans {
+            fans.push(vec![("fan", fan.label.clone())], fan.rpm as f64);
+        }
+
+        let mut battery = MetricFamily::gauge("devenvprobe_battery_charge_percent", "Battery charge percent.");
+        for item in &sensors.batteries {
+            if let Some(percent) = item.charge_percent {
+                battery.push(vec![("battery", item.name.clone())], percent);
+            }
+        }
+
+        families.extend([
+            temperatures,
+            fans,
+            battery,
+            MetricFamily::single("devenvprobe_thermal_throttling", "Whether any sensor is at its throttling limit.", bool_value(sensors.thermal_throttling)),
+        ]);
+
+        if let Some(ac_online) = sensors.ac_online {
+            families.push(MetricFamily::single("devenvprobe_ac_online", "Whether AC power is connected.", bool_value(ac_online)));
+        }
+    }
+
+    families
+}
+
+fn tool_families(tools: &[ToolStatus], refreshed_at_ms: Option<u64>) -> Vec<MetricFamily> {
+    let mut installed = MetricFamily::gauge("devenvprobe_tool_installed", "Whether a developer tool is installed.");
+    let mut info = MetricFamily::info("devenvprobe_tool", "Detected developer tool version and path.");
+
+    for tool in tools {
+        installed.push(
+            vec![
+                ("tool", tool.name.clone()),
+                ("command", tool.command.clone()),
+                ("category", tool.category.clone()),
+            ],
+            bool_value(tool.installed),
+        );
+
+        if tool.installed {
+            info.push(
+                vec![
+                    ("tool", tool.name.clone()),
+                    ("version", tool.version.clone().unwrap_or_default()),
+                    ("path", tool.install_path.clone().unwrap_or_default()),
+                ],
+                1.0,
+            );
+        }
+    }
+
+    let mut families = vec![installed, info];
+    if let Some(refreshed_at_ms) = refreshed_at_ms {
+        families.push(MetricFamily::single(
+            "devenvprobe_tools_refreshed_timestamp_seconds",
+            "Unix time of the last tool detection run.",
+            refreshed_at_ms as f64 / 1000.0,
+        ));
+    }
+    families
+}
+
+fn docker_families(results: &[DockerCommandResult]) -> Vec<MetricFamily> {
+    let find = |action: &str| results.iter().find(|result| result.action == action && result.exit_code == 0);
+    let mut families = vec![MetricFamily::single(
+        "devenvprobe_docker_up",
+        "Whether the docker CLI answered.",
+        bool_value(find("version").is_some()),
+    )];
+
+    if let Some(ps) = find("ps") {
+        let running = parse_docker_containers(&ps.stdout)
+            .iter()
+            .filter(|row| row.is_running())
+            .count();
+        families.push(MetricFamily::single(
+            "devenvprobe_docker_containers_running",
+            "Running docker containers.",
+            running as f64,
+        ));
+    }
+
+    if let Some(stats) = find("stats") {
+        let mut cpu = MetricFamily::gauge("devenvprobe_docker_container_cpu_percent", "Container CPU usage percent.");
+        let mut mem_used = MetricFamily::gauge("devenvprobe_docker_container_memory_used_bytes", "Container memory usage in bytes.");
+        let mut mem_limit = MetricFamily::gauge("devenvprobe_docker_container_memory_limit_bytes", "Container memory limit in bytes.");
+        let mut net_rx = MetricFamily::counter("devenvprobe_docker_container_network_receive_bytes", "Container network bytes received.");
+        let mut net_tx = MetricFamily::counter("devenvprobe_docker_container_network_transmit_bytes", "Container network bytes sent.");
+
+        for row in parse_docker_stats(&stats.stdout) {
+            let labels = vec![("container", row.name.clone())];
+            cpu.push(labels.clone(), row.cpu_percent);
+            if let Some(used) = row.mem_used_bytes {
+                mem_used.push(labels.clone(), used);
+            }
+            if let Some(limit) = row.mem_limit_bytes {
+                mem_limit.push(labels.clone(), limit);
+            }
+            net_rx.push(labels.clone(), row.net_rx_bytes);
+            net_tx.push(labels, row.net_tx_bytes);
+        }
+
+        families.extend([cpu, mem_used, mem_limit, net_rx, net_tx]);
+    }
+
+    families
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf".to_string() } else { "-Inf".to_string() }
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::DiskSnapshot;
+
+    fn sample_snapshot() -> SystemSnapshot {
+        SystemSnapshot {
+            host_name: "dev-box".to_string(),
+            os_name: "Windows 11".to_string(),
+            os_version: "10.0.22631".to_string(),
+            build_number: "22631".to_string(),
+            architecture: "64 位".to_string(),
+            uptime_seconds: 3600,
+            cpu_model: "AMD \"Ryzen\" 7".to_string(),
+            cpu_cores: 8,
+            cpu_logical_cores: 16,
+            cpu_usage_percent: 12.5,
+            total_memory_gb: 32.0,
+            used_memory_gb: 8.0,
+            memory_usage_percent: 25.0,
+            disks: vec![DiskSnapshot {
+                name: "C:".to_string(),
+                mount_point: "C:".to_string(),
+                total_gb: 512.0,
+                used_gb: 256.0,
+                usage_percent: 50.0,
+            }],
+            sensors: None,
+            environment_kind: None,
+            effective_limits: None,
+            sample_mode: Some("precise".to_string()),
+            sampled_at_ms: Some(1_700_000_000_000),
+            is_stale: Some(false),
+        }
+    }
+
+    #[test]
+    fn render_snapshot_tools_and_docker() {
+        let input = MetricsInput {
+            snapshot: Some(sample_snapshot()),
+            tools: Some(vec![ToolStatus {
+                category: "SCM".to_string(),
+                install_key: Some("git".to_string()),
+                install_path: Some(r"C:\Program Files\Git\cmd\git.exe".to_string()),
+                ..ToolStatus::for_test("Git", "git", Some("git version 2.43.0"))
+            }]),
+            tools_refreshed_at_ms: None,
+            docker: Some(vec![
+                DockerCommandResult {
+                    action: "version".to_string(),
+                    command: "docker --version".to_string(),
+                    stdout: "Docker version 27.0.3".to_string(),
+                    stderr: String::new(),
+                    exit_code: 0,
+                },
+                DockerCommandResult {
+                    action: "stats".to_string(),
+                    command: "docker stats".to_string(),
+                    stdout: "NAME\tCPU %\tMEM USAGE / LIMIT\tNET I/O\nweb\t2.50%\t1GiB / 2GiB\t1kB / 2kB".to_string(),
+                    stderr: String::new(),
+                    exit_code: 0,
+                },
+            ]),
+        };
+
+        let text = render_openmetrics(&input);
+        assert!(text.ends_with("# EOF\n"));
+        assert!(text.contains("# TYPE devenvprobe_cpu_usage_percent gauge\n"));
+        assert!(text.contains("devenvprobe_cpu_usage_percent 12.5\n"));
+        assert!(text.contains("devenvprobe_memory_total_bytes 34359738368\n"));
+        assert!(text.contains("devenvprobe_disk_usage_percent{disk=\"C:\",mount_point=\"C:\"} 50\n"));
+        assert!(text.contains("cpu_model=\"AMD \\\"Ryzen\\\" 7\""));
+        assert!(text.contains("# TYPE devenvprobe_system info\n"));
+        assert!(text.contains("devenvprobe_system_info{host=\"dev-box\","));
+        assert!(text.contains("# TYPE devenvprobe_tool info\n"));
+        assert!(text.contains("devenvprobe_tool_installed{tool=\"Git\",command=\"git\",category=\"SCM\"} 1\n"));
+        assert!(text.contains("path=\"C:\\\\Program Files\\\\Git\\\\cmd\\\\git.exe\""));
+        assert!(text.contains("devenvprobe_docker_up 1\n"));
+        assert!(text.contains("devenvprobe_docker_container_cpu_percent{container=\"web\"} 2.5\n"));
+        assert!(text.contains("devenvprobe_docker_container_memory_limit_bytes{container=\"web\"} 2147483648\n"));
+        assert!(text.contains("# TYPE devenvprobe_docker_container_network_receive_bytes counter\n"));
+        assert!(text.contains("devenvprobe_docker_container_network_receive_bytes_total{container=\"web\"} 1000\n"));
+        assert!(text.contains("devenvprobe_docker_container_network_transmit_bytes_total{container=\"web\"} 2000\n"));
+        assert!(!text.contains("devenvprobe_docker_containers_running"));
+    }
+
+    #[test]
+    fn render_empty_input_only_emits_eof() {
+        assert_eq!(render_openmetrics(&MetricsInput::default()), "# EOF\n");
+    }
+}
@@ -0,0 +1,397 @@
+use serde_json::Value;
+
+/// 写入 cargo 配置时使用的替换源名称
+pub const CARGO_MIRROR_SOURCE: &str = "devenvprobe-mirror";
+/// 写入 Maven settings.xml 的 mirror id
+pub const MAVEN_MIRROR_ID: &str = "devenvprobe-mirror";
+
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+fn finish_lines(lines: Vec<String>, newline: &str) -> String {
+    let mut content = lines.join(newline);
+    if !content.is_empty() {
+        content.push_str(newline);
+    }
+    content
+}
+
+/// `.npmrc`、go env 文件这类 `key=value` 行格式
+pub fn read_key_value(content: &str, key: &str) -> Option<String> {
+    content.lines().rev().find_map(|line| {
+        let (name, value) = line.trim().split_once('=')?;
+        (name.trim() == key).then(|| value.trim().to_string()).filter(|value| !value.is_empty())
+    })
+}
+
+pub fn write_key_value(content: &str, key: &str, value: Option<&str>) -> String {
+    let newline = line_ending(content);
+    let mut lines: Vec<String> = content
+        .lines()
+        .filter(|line| line.trim().split_once('=').is_none_or(|(name, _)| name.trim() != key))
+        .map(ToString::to_string)
+        .collect();
+    if let Some(value) = value {
+        lines.push(format!("{}={}", key, value));
+    }
+    finish_lines(lines, newline)
+}
+
+fn ini_section(line: &str) -> Option<&str> {
+    line.trim().strip_prefix('[')?.strip_suffix(']').map(str::trim)
+}
+
+fn ini_key(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('#') || line.starts_with(';') {
+        return None;
+    }
+    line.split_once(['=', ':']).map(|(key, _)| key.trim())
+}
+
+/// pip.conf / pip.ini
+pub fn read_ini(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut current = None;
+    for line in content.lines() {
+        if let Some(name) = ini_section(line) {
+            current = Some(name);
+            continue;
+        }
+        if current == Some(section) && ini_key(line) == Some(key) {
+            let (_, value) = line.split_once(['=', ':'])?;
+            return Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        }
+    }
+    None
+}
+
+pub fn write_ini(content: &str, section: &str, key: &str, value: Option<&str>) -> String {
+    let newline = line_ending(content);
+    let mut lines: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+    let mut written = value.is_none();
+
+    for line in content.lines() {
+        if let Some(name) = ini_section(line) {
+            // 离开目标节时若还没写入，补在节末尾
+            if current.as_deref() == Some(section) && !written {
+                lines.push(format!("{} = {}", key, value.unwrap_or_default()));
+                written = true;
+            }
+            current = Some(name.to_string());
+            lines.push(line.to_string());
+            continue;
+        }
+        if current.as_deref() == Some(section) && ini_key(line) == Some(key) {
+            if let (Some(value), false) = (value, written) {
+                lines.push(format!("{} = {}", key, value));
+                written = true;
+            }
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+
+    if !written {
+        if current.as_deref() != Some(section) {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", section));
+        }
+        lines.push(format!("{} = {}", key, value.unwrap_or_default()));
+    }
+    finish_lines(lines, newline)
+}
+
+/// `[source.crates-io] replace-with` 指向的源地址
+pub fn read_cargo_mirror(content: &str) -> Result<Option<String>, String> {
+    let document: toml::Table = toml::from_str(content).map_err(|error| format!("cargo 配置解析失败: {}", error))?;
+    let sources = document.get("source").and_then(toml::Value::as_table);
+    let replacement = sources
+        .and_then(|sources| sources.get("crates-io"))
+        .and_then(|source| source.get("replace-with"))
+        .and_then(toml::Value::as_str);
+    Ok(replacement
+        .and_then(|name| sources?.get(name))
+        .and_then(|source| source.get("registry"))
+        .and_then(toml::Value::as_str)
+        .map(ToString::to_string))
+}
+
+/// 移除 `[source.crates-io]` 与本工具写入的替换源，再按需追加；其余内容保持原样
+pub fn write_cargo_mirror(content: &str, registry: Option<&str>) -> Result<String, String> {
+    let newline = line_ending(content);
+    let managed = ["source.crates-io".to_string(), format!("source.{}", CARGO_MIRROR_SOURCE)];
+    let mut lines: Vec<String> = Vec::new();
+    let mut skipping = false;
+
+    for line in content.lines() {
+        if let Some(header) = line.trim().strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            let header = header.trim().replace(['"', ' '], "");
+            skipping = managed.contains(&header);
+        }
+        if !skipping {
+            lines.push(line.to_string());
+        }
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    if let Some(registry) = registry {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("[source.crates-io]".to_string());
+        lines.push(format!("replace-with = \"{}\"", CARGO_MIRROR_SOURCE));
+        lines.push(String::new());
+        lines.push(format!("[source.{}]", CARGO_MIRROR_SOURCE));
+        lines.push(format!("registry = \"{}\"", registry));
+    }
+
+    let updated = finish_lines(lines, newline);
+    toml::from_str::<toml::Table>(&updated).map_err(|error| format!("改写后的 cargo 配置无效: {}", error))?;
+    Ok(updated)
+}
+
+fn xml_blocks<'a>(content: &'a str, tag: &str) -> Vec<(usize, usize, &'a str)> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = content[offset..].find(&open).map(|index| offset + index) {
+        let Some(end) = content[start..].find(&close).map(|index| start + index + close.len()) else {
+            break;
+        };
+        blocks.push((start, end, &content[start..end]));
+        offset = end;
+    }
+    blocks
+}
+
+fn xml_text(block: &str, tag: &str) -> Option<String> {
+    let (_, _, inner) = xml_blocks(block, tag).into_iter().next()?;
+    let text = inner.strip_prefix(&format!("<{}>", tag))?.strip_suffix(&format!("</{}>", tag))?;
+    Some(text.trim().to_string())
+}
+
+/// settings.xml 中对 central 生效的第一个 mirror
+pub fn read_maven_mirror(content: &str) -> Option<String> {
+    xml_blocks(content, "mirror").into_iter().find_map(|(_, _, block)| {
+        let mirror_of = xml_text(block, "mirrorOf")?;
+        let applies = mirror_of.split(',').any(|item| matches!(item.trim(), "central" | "*"));
+        applies.then(|| xml_text(block, "url")).flatten()
+    })
+}
+
+pub fn write_maven_mirror(content: &str, url: Option<&str>) -> Result<String, String> {
+    let newline = line_ending(content);
+    let mut updated = content.to_string();
+    let marker = format!("<id>{}</id>", MAVEN_MIRROR_ID);
+    if let Some((start, end, _)) = xml_blocks(content, "mirror").into_iter().find(|(_, _, block)| block.contains(&marker)) {
+        // 连同所在行的缩进与换行一起删除
+        let line_start = content[..start].rfind('\n').map(|index| index + 1).unwrap_or(start);
+        let line_start = if content[line_start..start].trim().is_empty() { line_start } else { start };
+        let line_end = content[end..].find('\n').map(|index| end + index + 1).unwrap_or(end);
+        let line_end = if content[end..line_end].trim().is_empty() { line_end } else { end };
+        updated.replace_range(line_start..line_end, "");
+    }
+
+    let Some(url) = url else {
+        return Ok(updated);
+    };
+
+    let mirror = [
+        "    <mirror>".to_string(),
+        format!("      <id>{}</id>", MAVEN_MIRROR_ID),
+        "      <mirrorOf>central</mirrorOf>".to_string(),
+        "      <name>DevEnvProbe mirror</name>".to_string(),
+        format!("      <url>{}</url>", url),
+        "    </mirror>".to_string(),
+    ]
+    .join(newline);
+
+    // 放在 <mirrors> 的最前面，Maven 按顺序取第一个匹配的 mirror
+    if let Some(index) = updated.find("<mirrors>") {
+        let insert_at = index + "<mirrors>".len();
+        updated.insert_str(insert_at, &format!("{}{}", newline, mirror));
+        return Ok(updated);
+    }
+    if let Some(index) = updated.find("</settings>") {
+        let block = format!("  <mirrors>{nl}{}{nl}  </mirrors>{nl}", mirror, nl = newline);
+        updated.insert_str(index, &block);
+        return Ok(updated);
+    }
+    if !updated.trim().is_empty() {
+        return Err("settings.xml 中没有 <settings> 根元素，无法写入镜像".to_string());
+    }
+    Ok([
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        "<settings>".to_string(),
+        "  <mirrors>".to_string(),
+        mirror,
+        "  </mirrors>".to_string(),
+        "</settings>".to_string(),
+        String::new(),
+    ]
+    .join(newline))
+}
+
+/// docker daemon.json 的 registry-mirrors
+pub fn read_docker_mirror(content: &str) -> Result<Option<String>, String> {
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+    let document: Value = serde_json::from_str(content).map_err(|error| format!("daemon.json 解析失败: {}", error))?;
+    Ok(document
+        .get("registry-mirrors")
+        .and_then(Value::as_array)
+        .and_then(|mirrors| mirrors.first())
+        .and_then(Value::as_str)
+        .map(ToString::to_string))
+}
+
+pub fn write_docker_mirror(content: &str, url: Option<&str>) -> Result<String, String> {
+    let mut document: Value = if content.trim().is_empty() {
+        Value::Object(Default::default())
+    } else {
+        serde_json::from_str(content).map_err(|error| format!("daemon.json 解析失败: {}", error))?
+    };
+    let object = document.as_object_mut().ok_or_else(|| "daemon.json 顶层必须是对象".to_string())?;
+    match url {
+        Some(url) => {
+            object.insert("registry-mirrors".to_string(), Value::Array(vec![Value::String(url.to_string())]));
+        }
+        None => {
+            object.remove("registry-mirrors");
+        }
+    }
+    let mut updated = serde_json::to_string_pretty(&document).map_err(|error| format!("序列化 daemon.json 失败: {}", error))?;
+    updated.push('\n');
+    Ok(updated)
+}
+
+fn yaml_top_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '-', '#']) {
+        return None;
+    }
+    line.split_once(':').map(|(key, _)| key.trim())
+}
+
+/// 删除顶层键及其下属的缩进行 / 列表项
+fn remove_yaml_keys(content: &str, keys: &[&str]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut skipping = false;
+    for line in content.lines() {
+        if let Some(key) = yaml_top_key(line) {
+            skipping = keys.contains(&key);
+        } else if skipping && !line.trim().is_empty() && !line.starts_with([' ', '\t', '-']) {
+            skipping = false;
+        }
+        if !skipping {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// `.condarc` 的 default_channels；预设地址为镜像根目录（如 .../anaconda）
+pub fn read_conda_mirror(content: &str) -> Option<String> {
+    let mut in_defaults = false;
+    for line in content.lines() {
+        if let Some(key) = yaml_top_key(line) {
+            in_defaults = key == "default_channels";
+            continue;
+        }
+        if in_defaults {
+            if let Some(item) = line.trim().strip_prefix('-') {
+                let channel = item.trim().trim_matches(['"', '\'']);
+                return Some(channel.strip_suffix("/pkgs/main").unwrap_or(channel).to_string());
+            }
+        }
+    }
+    None
+}
+
+pub fn write_conda_mirror(content: &str, base: Option<&str>) -> String {
+    let newline = line_ending(content);
+    let mut lines = remove_yaml_keys(content, &["default_channels", "custom_channels"]);
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    if let Some(base) = base {
+        let base = base.trim_end_matches('/');
+        lines.push("default_channels:".to_string());
+        for channel in ["main", "r", "msys2"] {
+            lines.push(format!("  - {}/pkgs/{}", base, channel));
+        }
+        lines.push("custom_channels:".to_string());
+        for channel in ["conda-forge", "pytorch"] {
+            lines.push(format!("  {}: {}/cloud", channel, base));
+        }
+    }
+    finish_lines(lines, newline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_value_and_ini_files_keep_other_lines() {
+        let npmrc = "# team\nregistry=https://registry.npmjs.org/\nsave-exact=true\n";
+        let updated = write_key_value(npmrc, "registry", Some("https://registry.npmmirror.com/"));
+        assert_eq!(updated, "# team\nsave-exact=true\nregistry=https://registry.npmmirror.com/\n");
+        assert_eq!(read_key_value(&updated, "registry").as_deref(), Some("https://registry.npmmirror.com/"));
+        assert_eq!(write_key_value(&updated, "registry", None), "# team\nsave-exact=true\n");
+
+        let pip = "[install]\ntrusted-host = example.com\n\n[global]\ntimeout = 60\nindex-url = https://pypi.org/simple\n";
+        let updated = write_ini(pip, "global", "index-url", Some("https://pypi.tuna.tsinghua.edu.cn/simple"));
+        assert_eq!(read_ini(&updated, "global", "index-url").as_deref(), Some("https://pypi.tuna.tsinghua.edu.cn/simple"));
+        assert!(updated.contains("trusted-host = example.com"));
+        assert_eq!(read_ini(&write_ini(&updated, "global", "index-url", None), "global", "index-url"), None);
+
+        let created = write_ini("", "global", "index-url", Some("https://mirrors.aliyun.com/pypi/simple/"));
+        assert_eq!(created, "[global]\nindex-url = https://mirrors.aliyun.com/pypi/simple/\n");
+    }
+
+    #[test]
+    fn cargo_source_replacement_round_trips() {
+        let original = "[build]\njobs = 8\n\n[source.crates-io]\nreplace-with = 'tuna'\n\n[source.tuna]\nregistry = \"sparse+https://mirrors.tuna.tsinghua.edu.cn/crates.io-index/\"\n";
+        assert_eq!(
+            read_cargo_mirror(original).unwrap().as_deref(),
+            Some("sparse+https://mirrors.tuna.tsinghua.edu.cn/crates.io-index/")
+        );
+
+        let updated = write_cargo_mirror(original, Some("sparse+https://rsproxy.cn/index/")).unwrap();
+        assert_eq!(read_cargo_mirror(&updated).unwrap().as_deref(), Some("sparse+https://rsproxy.cn/index/"));
+        assert!(updated.starts_with("[build]\njobs = 8\n"));
+
+        let official = write_cargo_mirror(&updated, None).unwrap();
+        assert_eq!(read_cargo_mirror(&official).unwrap(), None);
+    }
+
+    #[test]
+    fn maven_docker_and_conda_mirrors_round_trip() {
+        let settings = "<settings>\n  <localRepository>/data/m2</localRepository>\n</settings>\n";
+        let updated = write_maven_mirror(settings, Some("https://maven.aliyun.com/repository/public")).unwrap();
+        assert_eq!(read_maven_mirror(&updated).as_deref(), Some("https://maven.aliyun.com/repository/public"));
+        let replaced = write_maven_mirror(&updated, Some("https://repo.huaweicloud.com/repository/maven/")).unwrap();
+        assert_eq!(xml_blocks(&replaced, "mirror").len(), 1);
+        assert_eq!(write_maven_mirror(&replaced, None).unwrap().replace("  <mirrors>\n  </mirrors>\n", ""), settings);
+        assert!(read_maven_mirror(&write_maven_mirror("", Some("https://m.example/")).unwrap()).is_some());
+
+        let daemon = write_docker_mirror("{\"debug\": true}", Some("https://docker.m.daocloud.io")).unwrap();
+        assert_eq!(read_docker_mirror(&daemon).unwrap().as_deref(), Some("https://docker.m.daocloud.io"));
+        assert!(!write_docker_mirror(&daemon, None).unwrap().contains("registry-mirrors"));
+
+        let condarc = "channels:\n  - defaults\nshow_channel_urls: true\ndefault_channels:\n  - https://old/pkgs/main\n";
+        let updated = write_conda_mirror(condarc, Some("https://mirrors.tuna.tsinghua.edu.cn/anaconda"));
+        assert_eq!(read_conda_mirror(&updated).as_deref(), Some("https://mirrors.tuna.tsinghua.edu.cn/anaconda"));
+        assert!(updated.starts_with("channels:\n  - defaults\nshow_channel_urls: true\n"));
+        assert_eq!(write_conda_mirror(&updated, None), "channels:\n  - defaults\nshow_channel_urls: true\n");
+    }
+}
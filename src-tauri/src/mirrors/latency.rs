@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+
+use crate::contracts::MirrorLatency;
+
+use super::{find_ecosystem, mirror_ecosystems};
+
+pub const MIRROR_LATENCY_TIMEOUT_MS: u64 = 5_000;
+
+/// 预设地址不一定能直接访问，换成各生态客户端实际会请求的入口
+pub fn probe_url(ecosystem: &str, url: &str) -> String {
+    // GOPROXY 可以是逗号分隔的列表，只测第一个代理
+    let url = url.split(',').next().unwrap_or(url).trim();
+    let url = url.strip_prefix("sparse+").unwrap_or(url);
+    let base = url.trim_end_matches('/');
+    match ecosystem {
+        "cargo" => format!("{}/config.json", base),
+        "docker" => format!("{}/v2/", base),
+        "conda" => format!("{}/pkgs/main/noarch/current_repodata.json", base),
+        _ => format!("{}/", base),
+    }
+}
+
+/// 并发测量各候选镜像的响应时间；收到任意 HTTP 响应（含 401/404）即视为可达
+pub async fn measure_mirror_latency_internal(ecosystem: Option<&str>) -> Result<Vec<MirrorLatency>, String> {
+    let ecosystems = mirror_ecosystems();
+    let selected = match ecosystem.map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => vec![find_ecosystem(&ecosystems, key)?],
+        None => ecosystems.iter().collect(),
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(MIRROR_LATENCY_TIMEOUT_MS))
+        .user_agent("DevEnvProbe/1.0")
+        .build()
+        .map_err(|error| format!("创建 HTTP 客户端失败: {}", error))?;
+
+    let probes = selected.iter().flat_map(|ecosystem| {
+        ecosystem.presets.iter().map(|preset| MirrorLatency {
+            ecosystem: ecosystem.key.to_string(),
+            preset: preset.key.to_string(),
+            url: probe_url(ecosystem.key, preset.url),
+            latency_ms: None,
+            status_code: None,
+            error: None,
+        })
+    });
+
+    Ok(join_all(probes.map(|probe| measure(&client, probe))).await)
+}
+
+pub async fn measure(client: &reqwest::Client, mut probe: MirrorLatency) -> MirrorLatency {
+    let started = Instant::now();
+    match client.get(&probe.url).send().await {
+        Ok(response) => {
+            probe.latency_ms = Some(started.elapsed().as_millis() as u64);
+            probe.status_code = Some(response.status().as_u16());
+        }
+        Err(error) if error.is_timeout() => probe.error = Some(format!("超过 {} ms 未响应", MIRROR_LATENCY_TIMEOUT_MS)),
+        Err(error) => probe.error = Some(error.to_string()),
+    }
+    probe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn probe_urls_follow_client_entry_points() {
+        assert_eq!(probe_url("go", "https://goproxy.cn,direct"), "https://goproxy.cn/");
+        assert_eq!(
+            probe_url("cargo", "sparse+https://rsproxy.cn/index/"),
+            "https://rsproxy.cn/index/config.json"
+        );
+        assert_eq!(probe_url("docker", "https://docker.m.daocloud.io"), "https://docker.m.daocloud.io/v2/");
+    }
+
+    #[test]
+    fn latency_is_measured_against_local_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buffer = [0u8; 1024];
+                let _ = stream.read(&mut buffer);
+                let _ = stream.write_all(b"HTTP/1.1 401 Unauthorized\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+            }
+        });
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let client = reqwest::Client::new();
+        let probe = MirrorLatency {
+            ecosystem: "docker".to_string(),
+            preset: "custom".to_string(),
+            url: format!("http://{}/v2/", address),
+            latency_ms: None,
+            status_code: None,
+            error: None,
+        };
+        let measured = runtime.block_on(measure(&client, probe));
+        assert_eq!(measured.status_code, Some(401));
+        assert!(measured.latency_ms.is_some());
+        assert!(measured.error.is_none());
+    }
+}
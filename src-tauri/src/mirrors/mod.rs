@@ -0,0 +1,522 @@
+pub mod formats;
+pub mod latency;
+
+pub use latency::measure_mirror_latency_internal;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::contracts::{MirrorApplyResult, MirrorEcosystemStatus, MirrorPresetInfo};
+use crate::runtime::current_timestamp_ms;
+use crate::tools::catalog::app_config_dir;
+
+use formats::*;
+
+pub const MIRROR_PRESET_OFFICIAL: &str = "official";
+pub const MIRROR_PRESET_CUSTOM: &str = "custom";
+const MIRROR_BACKUP_DIR: &str = "mirror-backups";
+/// 备份时目标文件不存在，恢复时删除该文件
+const ABSENT_BACKUP_SUFFIX: &str = ".absent";
+
+pub struct MirrorPreset {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub url: &'static str,
+}
+
+/// 一个生态的镜像配置：所在文件、读取与改写方式、可选预设
+pub struct MirrorEcosystem {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub presets: &'static [MirrorPreset],
+    /// 优先级高于配置文件的环境变量
+    pub env_overrides: &'static [&'static str],
+    pub config_path: fn() -> Option<PathBuf>,
+    pub read: fn(&str) -> Result<Option<String>, String>,
+    /// url 为 None 时移除镜像设置，恢复官方源
+    pub write: fn(&str, Option<&str>) -> Result<String, String>,
+    /// 写入后还需要用户处理才会生效时的提示
+    pub apply_notice: Option<&'static str>,
+}
+
+pub fn mirror_ecosystems() -> Vec<MirrorEcosystem> {
+    vec![
+        MirrorEcosystem {
+            key: "npm",
+            name: "npm",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "npm 官方", url: "https://registry.npmjs.org/" },
+                MirrorPreset { key: "npmmirror", name: "npmmirror（淘宝）", url: "https://registry.npmmirror.com/" },
+                MirrorPreset { key: "tencent", name: "腾讯云", url: "https://mirrors.cloud.tencent.com/npm/" },
+                MirrorPreset { key: "huawei", name: "华为云", url: "https://repo.huaweicloud.com/repository/npm/" },
+            ],
+            env_overrides: &["NPM_CONFIG_REGISTRY", "npm_config_registry"],
+            config_path: npmrc_path,
+            read: |content| Ok(read_key_value(content, "registry")),
+            write: |content, url| Ok(write_key_value(content, "registry", url)),
+            apply_notice: None,
+        },
+        MirrorEcosystem {
+            key: "pip",
+            name: "pip",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "PyPI 官方", url: "https://pypi.org/simple" },
+                MirrorPreset { key: "tuna", name: "清华 TUNA", url: "https://pypi.tuna.tsinghua.edu.cn/simple" },
+                MirrorPreset { key: "aliyun", name: "阿里云", url: "https://mirrors.aliyun.com/pypi/simple/" },
+                MirrorPreset { key: "ustc", name: "中科大", url: "https://mirrors.ustc.edu.cn/pypi/simple" },
+                MirrorPreset { key: "tencent", name: "腾讯云", url: "https://mirrors.cloud.tencent.com/pypi/simple" },
+            ],
+            env_overrides: &["PIP_INDEX_URL"],
+            config_path: pip_config_path,
+            read: |content| Ok(read_ini(content, "global", "index-url")),
+            write: |content, url| Ok(write_ini(content, "global", "index-url", url)),
+            apply_notice: None,
+        },
+        MirrorEcosystem {
+            key: "go",
+            name: "Go",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "proxy.golang.org", url: "https://proxy.golang.org,direct" },
+                MirrorPreset { key: "goproxy-cn", name: "goproxy.cn", url: "https://goproxy.cn,direct" },
+                MirrorPreset { key: "aliyun", name: "阿里云", url: "https://mirrors.aliyun.com/goproxy/,direct" },
+                MirrorPreset { key: "goproxy-io", name: "goproxy.io", url: "https://goproxy.io,direct" },
+            ],
+            env_overrides: &["GOPROXY"],
+            config_path: go_env_path,
+            // 与 `go env -w GOPROXY=...` 写入的是同一个文件
+            read: |content| Ok(read_key_value(content, "GOPROXY")),
+            write: |content, url| Ok(write_key_value(content, "GOPROXY", url)),
+            apply_notice: None,
+        },
+        MirrorEcosystem {
+            key: "cargo",
+            name: "Cargo",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "crates.io", url: "sparse+https://index.crates.io/" },
+                MirrorPreset {
+                    key: "tuna",
+                    name: "清华 TUNA",
+                    url: "sparse+https://mirrors.tuna.tsinghua.edu.cn/crates.io-index/",
+                },
+                MirrorPreset { key: "ustc", name: "中科大", url: "sparse+https://mirrors.ustc.edu.cn/crates.io-index/" },
+                MirrorPreset { key: "rsproxy", name: "字节 rsproxy", url: "sparse+https://rsproxy.cn/index/" },
+                MirrorPreset { key: "aliyun", name: "阿里云", url: "sparse+https://mirrors.aliyun.com/crates.io-index/" },
+            ],
+            env_overrides: &[],
+            config_path: cargo_config_path,
+            read: read_cargo_mirror,
+            write: write_cargo_mirror,
+            apply_notice: None,
+        },
+        MirrorEcosystem {
+            key: "maven",
+            name: "Maven",
+            presets: &[
+                MirrorPreset {
+                    key: MIRROR_PRESET_OFFICIAL,
+                    name: "Maven Central",
+                    url: "https://repo.maven.apache.org/maven2/",
+                },
+                MirrorPreset { key: "aliyun", name: "阿里云", url: "https://maven.aliyun.com/repository/public" },
+                MirrorPreset { key: "huawei", name: "华为云", url: "https://repo.huaweicloud.com/repository/maven/" },
+                MirrorPreset {
+                    key: "tencent",
+                    name: "腾讯云",
+                    url: "https://mirrors.cloud.tencent.com/nexus/repository/maven-public/",
+                },
+            ],
+            env_overrides: &[],
+            config_path: || dirs::home_dir().map(|home| home.join(".m2").join("settings.xml")),
+            read: |content| Ok(read_maven_mirror(content)),
+            write: write_maven_mirror,
+            apply_notice: None,
+        },
+        MirrorEcosystem {
+            key: "docker",
+            name: "Docker",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "Docker Hub", url: "https://registry-1.docker.io" },
+                MirrorPreset { key: "daocloud", name: "DaoCloud", url: "https://docker.m.daocloud.io" },
+                MirrorPreset { key: "1panel", name: "1Panel", url: "https://docker.1panel.live" },
+            ],
+            env_overrides: &[],
+            config_path: docker_daemon_path,
+            read: read_docker_mirror,
+            write: write_docker_mirror,
+            // 守护进程只在启动时读取 daemon.json
+            apply_notice: Some("Docker 守护进程重启后才会使用新的镜像：Linux 执行 sudo systemctl restart docker，Docker Desktop 需重启应用"),
+        },
+        MirrorEcosystem {
+            key: "conda",
+            name: "conda",
+            presets: &[
+                MirrorPreset { key: MIRROR_PRESET_OFFICIAL, name: "Anaconda 官方", url: "https://repo.anaconda.com" },
+                MirrorPreset { key: "tuna", name: "清华 TUNA", url: "https://mirrors.tuna.tsinghua.edu.cn/anaconda" },
+                MirrorPreset { key: "ustc", name: "中科大", url: "https://mirrors.ustc.edu.cn/anaconda" },
+                MirrorPreset { key: "aliyun", name: "阿里云", url: "https://mirrors.aliyun.com/anaconda" },
+            ],
+            env_overrides: &[],
+            config_path: || {
+                std::env::var_os("CONDARC")
+                    .map(PathBuf::from)
+                    .or_else(|| dirs::home_dir().map(|home| home.join(".condarc")))
+            },
+            read: |content| Ok(read_conda_mirror(content)),
+            write: |content, url| Ok(write_conda_mirror(content, url)),
+            apply_notice: None,
+        },
+    ]
+}
+
+fn npmrc_path() -> Option<PathBuf> {
+    std::env::var_os("NPM_CONFIG_USERCONFIG")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".npmrc")))
+}
+
+/// Windows 为 %APPDATA%\pip\pip.ini；其他平台优先沿用已存在的旧路径 ~/.pip/pip.conf
+fn pip_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("PIP_CONFIG_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    if cfg!(target_os = "windows") {
+        return dirs::config_dir().map(|dir| dir.join("pip").join("pip.ini"));
+    }
+
+    let current = dirs::config_dir().map(|dir| dir.join("pip").join("pip.conf"));
+    let legacy = dirs::home_dir().map(|home| home.join(".pip").join("pip.conf"));
+    match (&current, &legacy) {
+        (Some(current), Some(legacy)) if !current.exists() && legacy.exists() => Some(legacy.clone()),
+        _ => current,
+    }
+}
+
+fn go_env_path() -> Option<PathBuf> {
+    std::env::var_os("GOENV")
+        .filter(|value| !value.is_empty() && value != "off")
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("go").join("env")))
+}
+
+fn cargo_config_path() -> Option<PathBuf> {
+    let home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))?;
+    let legacy = home.join("config");
+    let current = home.join("config.toml");
+    Some(if !current.exists() && legacy.is_file() { legacy } else { current })
+}
+
+/// Linux 上 dockerd 读取 /etc/docker/daemon.json（rootless 模式为 ~/.config/docker），Docker Desktop 读取 ~/.docker
+fn docker_daemon_path() -> Option<PathBuf> {
+    if cfg!(target_os = "linux") {
+        let rootless = dirs::config_dir().map(|dir| dir.join("docker").join("daemon.json"));
+        if let Some(rootless) = rootless.filter(|path| path.exists()) {
+            return Some(rootless);
+        }
+        return Some(PathBuf::from("/etc/docker/daemon.json"));
+    }
+    dirs::home_dir().map(|home| home.join(".docker").join("daemon.json"))
+}
+
+fn find_ecosystem<'a>(ecosystems: &'a [MirrorEcosystem], key: &str) -> Result<&'a MirrorEcosystem, String> {
+    ecosystems
+        .iter()
+        .find(|ecosystem| ecosystem.key.eq_ignore_ascii_case(key.trim()))
+        .ok_or_else(|| format!("未知的镜像类型：{}", key))
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_lowercase()
+}
+
+/// 未设置镜像时视为官方源
+pub fn match_preset(ecosystem: &MirrorEcosystem, current: Option<&str>) -> Option<&'static str> {
+    let Some(current) = current else {
+        return Some(MIRROR_PRESET_OFFICIAL);
+    };
+    ecosystem
+        .presets
+        .iter()
+        .find(|preset| normalize_url(preset.url) == normalize_url(current))
+        .map(|preset| preset.key)
+}
+
+fn read_config(path: &Path) -> Result<String, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(format!("读取 {} 失败: {}", path.display(), error)),
+    }
+}
+
+fn backup_root() -> Result<PathBuf, String> {
+    app_config_dir()
+        .map(|dir| dir.join(MIRROR_BACKUP_DIR))
+        .ok_or_else(|| "无法确定应用配置目录".to_string())
+}
+
+pub fn get_mirror_status_internal() -> Vec<MirrorEcosystemStatus> {
+    let backups = backup_root().ok();
+    mirror_ecosystems()
+        .iter()
+        .map(|ecosystem| ecosystem_status(ecosystem, backups.as_deref()))
+        .collect()
+}
+
+fn ecosystem_status(ecosystem: &MirrorEcosystem, backup_root: Option<&Path>) -> MirrorEcosystemStatus {
+    let path = (ecosystem.config_path)();
+    let mut status = MirrorEcosystemStatus {
+        ecosystem: ecosystem.key.to_string(),
+        name: ecosystem.name.to_string(),
+        config_path: path.as_ref().map(|path| path.to_string_lossy().to_string()),
+        current_url: None,
+        current_preset: None,
+        overridden_by: ecosystem
+            .env_overrides
+            .iter()
+            .find(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+            .map(|name| format!("{}={}", name, std::env::var(name).unwrap_or_default())),
+        presets: ecosystem
+            .presets
+            .iter()
+            .map(|preset| MirrorPresetInfo {
+                key: preset.key.to_string(),
+                name: preset.name.to_string(),
+                url: preset.url.to_string(),
+            })
+            .collect(),
+        backups: backup_root
+            .map(|root| list_backups(&root.join(ecosystem.key)).len())
+            .unwrap_or(0),
+        error: None,
+    };
+
+    let Some(path) = path else {
+        status.error = Some("无法确定配置文件位置".to_string());
+        return status;
+    };
+    match read_config(&path).and_then(|content| (ecosystem.read)(&content)) {
+        Ok(current) => {
+            status.current_preset = match_preset(ecosystem, current.as_deref()).map(ToString::to_string);
+            status.current_url = current;
+        }
+        Err(error) => status.error = Some(error),
+    }
+    status
+}
+
+/// 切换到预设（或 custom 自定义地址）；改写前备份原文件，写入临时文件后原子替换
+pub fn apply_mirror_preset_internal(
+    ecosystem: &str,
+    preset: &str,
+    custom_url: Option<&str>,
+) -> Result<MirrorApplyResult, String> {
+    let ecosystems = mirror_ecosystems();
+    let ecosystem = find_ecosystem(&ecosystems, ecosystem)?;
+    let url = resolve_preset_url(ecosystem, preset, custom_url)?;
+    let path = (ecosystem.config_path)().ok_or_else(|| "无法确定配置文件位置".to_string())?;
+    let backup = apply_mirror_to(ecosystem, &path, &backup_root()?.join(ecosystem.key), url.as_deref())?;
+
+    Ok(MirrorApplyResult {
+        ecosystem: ecosystem.key.to_string(),
+        preset: preset.to_string(),
+        url,
+        config_path: path.to_string_lossy().to_string(),
+        backup_path: backup.to_string_lossy().to_string(),
+        notice: ecosystem.apply_notice.map(ToString::to_string),
+    })
+}
+
+/// 官方源返回 None，表示移除镜像设置
+fn resolve_preset_url(ecosystem: &MirrorEcosystem, preset: &str, custom_url: Option<&str>) -> Result<Option<String>, String> {
+    if preset == MIRROR_PRESET_CUSTOM {
+        let url = custom_url
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| "自定义镜像需要提供地址".to_string())?;
+        if !url.contains("://") || url.chars().any(|ch| ch.is_whitespace() || matches!(ch, '"' | '\'' | '<' | '>')) {
+            return Err(format!("镜像地址无效：{}", url));
+        }
+        return Ok(Some(url.to_string()));
+    }
+    if preset == MIRROR_PRESET_OFFICIAL {
+        return Ok(None);
+    }
+    ecosystem
+        .presets
+        .iter()
+        .find(|item| item.key == preset)
+        .map(|item| Some(item.url.to_string()))
+        .ok_or_else(|| format!("{} 没有预设 {}", ecosystem.name, preset))
+}
+
+pub fn apply_mirror_to(ecosystem: &MirrorEcosystem, path: &Path, backup_dir: &Path, url: Option<&str>) -> Result<PathBuf, String> {
+    let original = read_config(path)?;
+    let updated = (ecosystem.write)(&original, url)?;
+    let backup = backup_file(path, backup_dir)?;
+    if let Err(error) = write_atomic(path, &updated) {
+        let _ = std::fs::remove_file(&backup);
+        return Err(error);
+    }
+    Ok(backup)
+}
+
+fn backup_file(path: &Path, backup_dir: &Path) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(backup_dir).map_err(|error| format!("创建备份目录失败: {}", error))?;
+    let mut stamp = current_timestamp_ms();
+    // 同一毫秒内连续切换时顺延，保证备份名唯一且有序
+    while std::fs::read_dir(backup_dir)
+        .map(|entries| entries.flatten().any(|entry| entry.file_name().to_string_lossy().starts_with(&stamp.to_string())))
+        .unwrap_or(false)
+    {
+        stamp += 1;
+    }
+
+    if path.exists() {
+        let backup = backup_dir.join(format!("{}.bak", stamp));
+        std::fs::copy(path, &backup).map_err(|error| format!("备份 {} 失败: {}", path.display(), error))?;
+        Ok(backup)
+    } else {
+        let backup = backup_dir.join(format!("{}{}", stamp, ABSENT_BACKUP_SUFFIX));
+        std::fs::write(&backup, "").map_err(|error| format!("写入备份记录失败: {}", error))?;
+        Ok(backup)
+    }
+}
+
+/// 同目录写临时文件再 rename，避免写到一半的配置被包管理器读到；
+/// 符号链接写到其指向的文件，临时文件在写入内容前沿用原文件权限（如含令牌的 0600 .npmrc）
+fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| write_error("创建目录", parent, error))?;
+    }
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.devenvprobe-tmp", file_name));
+
+    let written = std::fs::File::create(&temp)
+        .and_then(|mut file| {
+            if let Ok(metadata) = std::fs::metadata(&path) {
+                file.set_permissions(metadata.permissions())?;
+            }
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .map_err(|error| write_error("写入", &temp, error))
+        .and_then(|_| std::fs::rename(&temp, &path).map_err(|error| write_error("替换", &path, error)));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    written
+}
+
+fn write_error(action: &str, path: &Path, error: std::io::Error) -> String {
+    if error.kind() == std::io::ErrorKind::PermissionDenied {
+        return format!("{} {} 失败：没有权限，需要以管理员身份修改", action, path.display());
+    }
+    format!("{} {} 失败: {}", action, path.display(), error)
+}
+
+fn list_backups(dir: &Path) -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|path| path.is_file()).collect())
+        .unwrap_or_default();
+    // 文件名以毫秒时间戳开头，数字位数相同，按名称排序即按时间排序
+    backups.sort();
+    backups
+}
+
+/// 恢复最近一次备份，恢复后删除该备份
+pub fn restore_mirror_backup_internal(ecosystem: &str) -> Result<MirrorEcosystemStatus, String> {
+    let ecosystems = mirror_ecosystems();
+    let ecosystem = find_ecosystem(&ecosystems, ecosystem)?;
+    let path = (ecosystem.config_path)().ok_or_else(|| "无法确定配置文件位置".to_string())?;
+    let root = backup_root()?;
+    restore_latest_backup(&path, &root.join(ecosystem.key))?;
+    Ok(ecosystem_status(ecosystem, Some(&root)))
+}
+
+pub fn restore_latest_backup(path: &Path, backup_dir: &Path) -> Result<PathBuf, String> {
+    let backup = list_backups(backup_dir)
+        .pop()
+        .ok_or_else(|| "没有可恢复的备份".to_string())?;
+
+    if backup.to_string_lossy().ends_with(ABSENT_BACKUP_SUFFIX) {
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|error| format!("删除 {} 失败: {}", path.display(), error))?;
+        }
+    } else {
+        let content = std::fs::read_to_string(&backup).map_err(|error| format!("读取备份失败: {}", error))?;
+        write_atomic(path, &content)?;
+    }
+    std::fs::remove_file(&backup).map_err(|error| format!("删除备份失败: {}", error))?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_backs_up_and_restore_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("home").join(".npmrc");
+        let backups = dir.path().join("backups");
+        let ecosystems = mirror_ecosystems();
+        let npm = find_ecosystem(&ecosystems, "npm").unwrap();
+
+        // 原本没有 .npmrc：恢复后应当删除
+        apply_mirror_to(npm, &config, &backups, Some("https://registry.npmmirror.com/")).unwrap();
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), "registry=https://registry.npmmirror.com/\n");
+
+        let url = resolve_preset_url(npm, "tencent", None).unwrap();
+        apply_mirror_to(npm, &config, &backups, url.as_deref()).unwrap();
+        let current = read_key_value(&std::fs::read_to_string(&config).unwrap(), "registry");
+        assert_eq!(match_preset(npm, current.as_deref()), Some("tencent"));
+        assert_eq!(list_backups(&backups).len(), 2);
+
+        restore_latest_backup(&config, &backups).unwrap();
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), "registry=https://registry.npmmirror.com/\n");
+        restore_latest_backup(&config, &backups).unwrap();
+        assert!(!config.exists());
+        assert!(restore_latest_backup(&config, &backups).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn apply_keeps_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dotfiles = dir.path().join("dotfiles");
+        std::fs::create_dir(&dotfiles).unwrap();
+        let real = dotfiles.join("npmrc");
+        std::fs::write(&real, "//registry.npmjs.org/:_authToken=secret\n").unwrap();
+        std::fs::set_permissions(&real, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let config = dir.path().join(".npmrc");
+        std::os::unix::fs::symlink(&real, &config).unwrap();
+
+        let ecosystems = mirror_ecosystems();
+        let npm = find_ecosystem(&ecosystems, "npm").unwrap();
+        apply_mirror_to(npm, &config, &dir.path().join("backups"), Some("https://registry.npmmirror.com/")).unwrap();
+
+        assert!(std::fs::symlink_metadata(&config).unwrap().file_type().is_symlink());
+        assert!(std::fs::read_to_string(&real).unwrap().contains("registry=https://registry.npmmirror.com/"));
+        assert_eq!(std::fs::metadata(&real).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn preset_resolution_validates_custom_urls() {
+        let ecosystems = mirror_ecosystems();
+        let pip = find_ecosystem(&ecosystems, "pip").unwrap();
+        assert_eq!(resolve_preset_url(pip, MIRROR_PRESET_OFFICIAL, None).unwrap(), None);
+        assert!(resolve_preset_url(pip, "npmmirror", None).is_err());
+        assert!(resolve_preset_url(pip, MIRROR_PRESET_CUSTOM, Some("nexus.corp/simple")).is_err());
+        assert_eq!(
+            resolve_preset_url(pip, MIRROR_PRESET_CUSTOM, Some(" https://nexus.corp/simple ")).unwrap().as_deref(),
+            Some("https://nexus.corp/simple")
+        );
+        assert_eq!(match_preset(pip, Some("https://mirrors.aliyun.com/pypi/simple")), Some("aliyun"));
+        assert_eq!(match_preset(pip, None), Some(MIRROR_PRESET_OFFICIAL));
+    }
+}
@@ -0,0 +1,388 @@
+pub mod parsers;
+
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::contracts::{GlobalPackage, GlobalPackageActionResult, PackageEcosystemInventory};
+use crate::process_runner::execute_process_with_timeout;
+use crate::tools::detect::first_line;
+use crate::tools::managers::validate_argument;
+
+use parsers::*;
+
+pub const PACKAGE_LIST_TIMEOUT_MS: u64 = 15_000;
+pub const PACKAGE_ACTION_TIMEOUT_MS: u64 = 10 * 60 * 1_000;
+
+pub const PACKAGE_ACTION_UNINSTALL: &str = "uninstall";
+pub const PACKAGE_ACTION_UPGRADE: &str = "upgrade";
+
+/// 解析出的一条全局包记录；source 为升级时需要的来源（如 go 的模块路径）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PackageRecord {
+    pub name: String,
+    pub version: Option<String>,
+    pub location: Option<String>,
+    pub source: Option<String>,
+}
+
+/// 参数中的 `{name}`、`{source}` 在执行前替换；None 表示不支持此操作
+pub struct PackageEcosystemSpec {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub command: &'static str,
+    pub list_args: &'static [&'static str],
+    pub uninstall_args: Option<&'static [&'static str]>,
+    pub upgrade_args: Option<&'static [&'static str]>,
+    pub parse: fn(&str) -> Result<Vec<PackageRecord>, String>,
+}
+
+pub fn package_ecosystem_specs() -> Vec<PackageEcosystemSpec> {
+    vec![
+        PackageEcosystemSpec {
+            key: "npm",
+            name: "npm",
+            command: "npm",
+            list_args: &["ls", "-g", "--json", "--long", "--depth=0"],
+            uninstall_args: Some(&["uninstall", "-g", "{name}"]),
+            upgrade_args: Some(&["install", "-g", "{name}@latest"]),
+            parse: parse_npm_list,
+        },
+        PackageEcosystemSpec {
+            key: "pnpm",
+            name: "pnpm",
+            command: "pnpm",
+            list_args: &["ls", "-g", "--json"],
+            uninstall_args: Some(&["remove", "-g", "{name}"]),
+            upgrade_args: Some(&["add", "-g", "{name}@latest"]),
+            parse: parse_pnpm_list,
+        },
+        PackageEcosystemSpec {
+            key: "pipx",
+            name: "pipx",
+            command: "pipx",
+            list_args: &["list", "--json"],
+            uninstall_args: Some(&["uninstall", "{name}"]),
+            upgrade_args: Some(&["upgrade", "{name}"]),
+            parse: parse_pipx_list,
+        },
+        PackageEcosystemSpec {
+            key: "uv",
+            name: "uv tool",
+            command: "uv",
+            list_args: &["tool", "list", "--show-paths"],
+            uninstall_args: Some(&["tool", "uninstall", "{name}"]),
+            upgrade_args: Some(&["tool", "upgrade", "{name}"]),
+            parse: parse_uv_tool_list,
+        },
+        PackageEcosystemSpec {
+            key: "cargo",
+            name: "cargo install",
+            command: "cargo",
+            list_args: &["install", "--list"],
+            uninstall_args: Some(&["uninstall", "{name}"]),
+            // 已是最新版本时 cargo install 不会重复编译；git / 本地路径来源由 cargo_upgrade_args 补上
+            upgrade_args: Some(&["install", "{name}"]),
+            parse: parse_cargo_install_list,
+        },
+        PackageEcosystemSpec {
+            key: "go",
+            name: "go install",
+            command: "go",
+            // {gobin} 在列出前解析为 GOBIN 或 GOPATH/bin
+            list_args: &["version", "-m", "{gobin}"],
+            // go 没有卸载命令，由 remove_go_binary 直接删除 GOBIN 中的文件
+            uninstall_args: None,
+            upgrade_args: Some(&["install", "{source}@latest"]),
+            parse: parse_go_version_m,
+        },
+        PackageEcosystemSpec {
+            key: "dotnet",
+            name: "dotnet tool",
+            command: "dotnet",
+            list_args: &["tool", "list", "-g"],
+            uninstall_args: Some(&["tool", "uninstall", "-g", "{name}"]),
+            upgrade_args: Some(&["tool", "update", "-g", "{name}"]),
+            parse: parse_dotnet_tool_list,
+        },
+        PackageEcosystemSpec {
+            key: "vscode",
+            name: "VS Code 扩展",
+            command: "code",
+            list_args: &["--list-extensions", "--show-versions"],
+            uninstall_args: Some(&["--uninstall-extension", "{name}"]),
+            upgrade_args: Some(&["--install-extension", "{name}", "--force"]),
+            parse: parse_code_extensions,
+        },
+    ]
+}
+
+/// 列出各生态的全局包；ecosystem 为空时列出全部
+pub fn list_global_packages_internal(ecosystem: Option<&str>) -> Result<Vec<PackageEcosystemInventory>, String> {
+    let specs = package_ecosystem_specs();
+    let selected: Vec<&PackageEcosystemSpec> = match ecosystem.map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => vec![find_ecosystem(&specs, key)?],
+        None => specs.iter().collect(),
+    };
+
+    Ok(selected.par_iter().map(|spec| list_ecosystem(spec)).collect())
+}
+
+fn find_ecosystem<'a>(specs: &'a [PackageEcosystemSpec], key: &str) -> Result<&'a PackageEcosystemSpec, String> {
+    specs
+        .iter()
+        .find(|spec| spec.key.eq_ignore_ascii_case(key))
+        .ok_or_else(|| format!("未知的包生态：{}", key))
+}
+
+fn list_ecosystem(spec: &PackageEcosystemSpec) -> PackageEcosystemInventory {
+    let mut inventory = PackageEcosystemInventory {
+        ecosystem: spec.key.to_string(),
+        name: spec.name.to_string(),
+        available: false,
+        packages: Vec::new(),
+        error: None,
+    };
+
+    match list_records(spec) {
+        Ok(records) => {
+            inventory.available = true;
+            inventory.packages = records.into_iter().map(|record| to_package(spec, record)).collect();
+        }
+        Err(error) => inventory.error = Some(error),
+    }
+    inventory
+}
+
+fn list_records(spec: &PackageEcosystemSpec) -> Result<Vec<PackageRecord>, String> {
+    let gobin = if spec.key == "go" { Some(go_bin_dir()?) } else { None };
+    if gobin.as_ref().is_some_and(|dir| !dir.is_dir()) {
+        return Ok(Vec::new());
+    }
+
+    let args: Vec<String> = spec
+        .list_args
+        .iter()
+        .map(|arg| match &gobin {
+            Some(dir) => arg.replace("{gobin}", &dir.to_string_lossy()),
+            None => arg.to_string(),
+        })
+        .collect();
+    let output = execute_process_with_timeout(spec.command, &args, PACKAGE_LIST_TIMEOUT_MS)?;
+    // npm ls 在存在无效依赖时返回非 0，但 JSON 仍然完整
+    if output.exit_code != 0 && !(spec.key == "npm" && output.stdout.trim_start().starts_with('{')) {
+        return Err(first_line(&output.stderr).unwrap_or_else(|| format!("返回码 {}", output.exit_code)));
+    }
+
+    let mut records = (spec.parse)(&output.stdout)?;
+    for record in records.iter_mut().filter(|record| record.location.is_none()) {
+        record.location = default_location(spec.key, record);
+    }
+    Ok(records)
+}
+
+fn to_package(spec: &PackageEcosystemSpec, record: PackageRecord) -> GlobalPackage {
+    let mut actions = Vec::new();
+    if spec.uninstall_args.is_some() || spec.key == "go" {
+        actions.push(PACKAGE_ACTION_UNINSTALL.to_string());
+    }
+    let upgradable = match spec.key {
+        "go" => record.source.is_some(),
+        "cargo" => cargo_upgrade_args(&record).is_some(),
+        _ => true,
+    };
+    if spec.upgrade_args.is_some() && upgradable {
+        actions.push(PACKAGE_ACTION_UPGRADE.to_string());
+    }
+
+    GlobalPackage {
+        ecosystem: spec.key.to_string(),
+        name: record.name,
+        version: record.version,
+        location: record.location,
+        source: record.source,
+        actions,
+    }
+}
+
+/// 输出中不带路径的生态按默认安装目录补全
+fn default_location(ecosystem: &str, record: &PackageRecord) -> Option<String> {
+    let home = dirs::home_dir()?;
+    let path = match ecosystem {
+        "cargo" => std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".cargo"))
+            .join("bin"),
+        "dotnet" => home.join(".dotnet").join("tools"),
+        "vscode" => {
+            let version = record.version.as_deref()?;
+            home.join(".vscode")
+                .join("extensions")
+                .join(format!("{}-{}", record.name.to_lowercase(), version))
+        }
+        _ => return None,
+    };
+    path.exists().then(|| path.to_string_lossy().to_string())
+}
+
+fn go_bin_dir() -> Result<PathBuf, String> {
+    let output = execute_process_with_timeout(
+        "go",
+        &["env".to_string(), "GOBIN".to_string(), "GOPATH".to_string()],
+        PACKAGE_LIST_TIMEOUT_MS,
+    )?;
+    if output.exit_code != 0 {
+        return Err(first_line(&output.stderr).unwrap_or_else(|| format!("返回码 {}", output.exit_code)));
+    }
+    resolve_go_bin(&output.stdout).ok_or_else(|| "无法确定 GOBIN 目录".to_string())
+}
+
+/// `go env GOBIN GOPATH` 每行一个值；GOBIN 为空时取 GOPATH 第一项下的 bin（go install 只用第一项）
+pub fn resolve_go_bin(output: &str) -> Option<PathBuf> {
+    let mut lines = output.lines().map(str::trim);
+    let gobin = lines.next().unwrap_or_default();
+    if !gobin.is_empty() {
+        return Some(PathBuf::from(gobin));
+    }
+    let gopath = lines.next()?;
+    let first = std::env::split_paths(gopath).next().filter(|first| !first.as_os_str().is_empty())?;
+    Some(first.join("bin"))
+}
+
+/// 从 git 或本地路径安装的 crate 要按原来源升级，否则会被 crates.io 上的同名包替换；
+/// 无法识别的来源返回 None，不提供升级
+pub fn cargo_upgrade_args(record: &PackageRecord) -> Option<Vec<String>> {
+    let mut args = vec!["install".to_string()];
+    match record.source.as_deref() {
+        None => {}
+        Some(source) if source.starts_with('-') => return None,
+        Some(source) if source.contains("://") => {
+            // `https://github.com/o/r?branch=dev#1a2b3c4d`：# 后是已安装的提交，升级时去掉
+            let url = source.split('#').next().unwrap_or(source);
+            let (url, query) = url.split_once('?').unwrap_or((url, ""));
+            args.extend(["--git".to_string(), url.to_string()]);
+            for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+                if matches!(key, "branch" | "tag" | "rev") && !value.starts_with('-') {
+                    args.extend([format!("--{}", key), value.to_string()]);
+                }
+            }
+        }
+        Some(source) if Path::new(source).is_absolute() => {
+            args.extend(["--path".to_string(), source.to_string()]);
+        }
+        Some(_) => return None,
+    }
+    args.push(record.name.clone());
+    Some(args)
+}
+
+/// 先重新列出该生态确认包存在，再执行对应的卸载或升级命令
+pub fn run_global_package_action(ecosystem: &str, action: &str, name: &str) -> Result<GlobalPackageActionResult, String> {
+    let specs = package_ecosystem_specs();
+    let spec = find_ecosystem(&specs, ecosystem)?;
+    validate_argument(name, "包名")?;
+
+    let record = list_records(spec)?
+        .into_iter()
+        .find(|record| record.name == name)
+        .ok_or_else(|| format!("{} 中没有找到全局包 {}", spec.name, name))?;
+
+    if spec.key == "go" && action == PACKAGE_ACTION_UNINSTALL {
+        return remove_go_binary(&record);
+    }
+
+    let template = match action {
+        PACKAGE_ACTION_UNINSTALL => spec.uninstall_args,
+        PACKAGE_ACTION_UPGRADE => spec.upgrade_args,
+        _ => return Err(format!("未知的操作：{}", action)),
+    }
+    .ok_or_else(|| format!("{} 不支持该操作：{}", spec.name, action))?;
+
+    let source = record.source.clone().unwrap_or_default();
+    if template.iter().any(|arg| arg.contains("{source}")) {
+        validate_argument(&source, "包来源")?;
+    }
+    let args: Vec<String> = if spec.key == "cargo" && action == PACKAGE_ACTION_UPGRADE {
+        cargo_upgrade_args(&record).ok_or_else(|| format!("无法按来源升级 {}：{}", name, source))?
+    } else {
+        template
+            .iter()
+            .map(|arg| arg.replace("{name}", name).replace("{source}", &source))
+            .collect()
+    };
+    let output = execute_process_with_timeout(spec.command, &args, PACKAGE_ACTION_TIMEOUT_MS)?;
+
+    Ok(GlobalPackageActionResult {
+        ecosystem: spec.key.to_string(),
+        action: action.to_string(),
+        name: name.to_string(),
+        command: format!("{} {}", spec.command, args.join(" ")),
+        stdout: output.stdout,
+        stderr: output.stderr,
+        exit_code: output.exit_code,
+    })
+}
+
+fn remove_go_binary(record: &PackageRecord) -> Result<GlobalPackageActionResult, String> {
+    let location = record
+        .location
+        .as_deref()
+        .ok_or_else(|| format!("无法确定 {} 的安装位置", record.name))?;
+    let path = Path::new(location);
+    let (exit_code, stderr) = match std::fs::remove_file(path) {
+        Ok(()) => (0, String::new()),
+        Err(error) => (1, format!("删除 {} 失败: {}", path.display(), error)),
+    };
+
+    Ok(GlobalPackageActionResult {
+        ecosystem: "go".to_string(),
+        action: PACKAGE_ACTION_UNINSTALL.to_string(),
+        name: record.name.clone(),
+        command: format!("rm {}", path.display()),
+        stdout: String::new(),
+        stderr,
+        exit_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cargo_record(source: Option<&str>) -> PackageRecord {
+        PackageRecord {
+            name: "cargo-edit".to_string(),
+            version: Some("0.12.2".to_string()),
+            location: None,
+            source: source.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn cargo_upgrades_keep_git_and_path_sources() {
+        assert_eq!(cargo_upgrade_args(&cargo_record(None)).unwrap(), vec!["install", "cargo-edit"]);
+        assert_eq!(
+            cargo_upgrade_args(&cargo_record(Some("https://github.com/killercup/cargo-edit?branch=dev#1a2b3c4d"))).unwrap(),
+            vec!["install", "--git", "https://github.com/killercup/cargo-edit", "--branch", "dev", "cargo-edit"]
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert_eq!(
+            cargo_upgrade_args(&cargo_record(Some(&path))).unwrap(),
+            vec!["install".to_string(), "--path".to_string(), path, "cargo-edit".to_string()]
+        );
+        assert!(cargo_upgrade_args(&cargo_record(Some("registry `internal`"))).is_none());
+
+        let spec = package_ecosystem_specs().into_iter().find(|spec| spec.key == "cargo").unwrap();
+        assert_eq!(to_package(&spec, cargo_record(Some("registry `internal`"))).actions, vec![PACKAGE_ACTION_UNINSTALL]);
+    }
+
+    #[test]
+    fn go_bin_falls_back_to_first_gopath_entry() {
+        assert_eq!(resolve_go_bin("/opt/gobin\n/home/dev/go\n"), Some(PathBuf::from("/opt/gobin")));
+        let gopath = std::env::join_paths(["/home/dev/go", "/srv/go"]).unwrap();
+        let output = format!("\n{}\n", gopath.to_string_lossy());
+        assert_eq!(resolve_go_bin(&output), Some(Path::new("/home/dev/go").join("bin")));
+        assert_eq!(resolve_go_bin("\n\n"), None);
+    }
+}
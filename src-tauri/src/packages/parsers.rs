@@ -0,0 +1,273 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::PackageRecord;
+
+fn parse_json(stdout: &str, label: &str) -> Result<Value, String> {
+    serde_json::from_str(stdout.trim()).map_err(|error| format!("无法解析 {} 输出: {}", label, error))
+}
+
+fn json_text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(ToString::to_string)
+}
+
+fn strip_v(version: &str) -> String {
+    version.strip_prefix('v').unwrap_or(version).to_string()
+}
+
+/// `dependencies` 对象：包名 -> { version, path }
+fn dependency_records(dependencies: Option<&Value>) -> Vec<PackageRecord> {
+    let Some(Value::Object(dependencies)) = dependencies else {
+        return Vec::new();
+    };
+    dependencies
+        .iter()
+        .map(|(name, info)| PackageRecord {
+            name: name.clone(),
+            version: json_text(info, "version"),
+            location: json_text(info, "path"),
+            source: None,
+        })
+        .collect()
+}
+
+/// `npm ls -g --json --long --depth=0`
+pub fn parse_npm_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    let document = parse_json(stdout, "npm ls")?;
+    Ok(dependency_records(document.get("dependencies")))
+}
+
+/// `pnpm ls -g --json` 返回数组，每个全局目录一项
+pub fn parse_pnpm_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let document = parse_json(stdout, "pnpm ls")?;
+    let roots = match &document {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    Ok(roots
+        .into_iter()
+        .flat_map(|root| dependency_records(root.get("dependencies")))
+        .collect())
+}
+
+/// `pipx list --json`：位置取第一个应用所在虚拟环境的目录
+pub fn parse_pipx_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    let document = parse_json(stdout, "pipx list")?;
+    let Some(Value::Object(venvs)) = document.get("venvs") else {
+        return Ok(Vec::new());
+    };
+
+    Ok(venvs
+        .iter()
+        .map(|(venv, info)| {
+            let main = info.pointer("/metadata/main_package");
+            let app = main
+                .and_then(|main| main.get("app_paths"))
+                .and_then(Value::as_array)
+                .and_then(|paths| paths.first())
+                .and_then(|path| json_text(path, "__Path__"));
+            PackageRecord {
+                name: main.and_then(|main| json_text(main, "package")).unwrap_or_else(|| venv.clone()),
+                version: main.and_then(|main| json_text(main, "package_version")),
+                location: app.and_then(|app| {
+                    Path::new(&app)
+                        .parent()
+                        .and_then(Path::parent)
+                        .map(|dir| dir.to_string_lossy().to_string())
+                }),
+                source: None,
+            }
+        })
+        .collect())
+}
+
+fn trailing_parenthesized(line: &str) -> Option<String> {
+    let line = line.trim_end();
+    let inner = line.strip_suffix(')')?;
+    let start = inner.rfind('(')?;
+    Some(inner[start + 1..].to_string())
+}
+
+/// `uv tool list --show-paths`：`black v24.2.0 (/path)`，以 `-` 开头的是该工具提供的命令
+pub fn parse_uv_tool_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    Ok(stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('-') && !line.starts_with(' '))
+        .filter(|line| !line.starts_with("warning:") && !line.starts_with("No tools"))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let version = parts.next().filter(|part| part.starts_with('v')).map(strip_v);
+            Some(PackageRecord {
+                name,
+                version,
+                location: trailing_parenthesized(line),
+                source: None,
+            })
+        })
+        .collect())
+}
+
+/// `cargo install --list`：`ripgrep v14.1.0:`，缩进行是该包安装的可执行文件
+pub fn parse_cargo_install_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    Ok(stdout
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let line = line.trim().strip_suffix(':')?;
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let version = parts.next().map(strip_v);
+            Some(PackageRecord {
+                name,
+                version,
+                location: None,
+                source: trailing_parenthesized(line),
+            })
+        })
+        .collect())
+}
+
+/// `go version -m <GOBIN>`：每个可执行文件一段，path 行为主模块，mod 行带版本
+pub fn parse_go_version_m(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    let mut records: Vec<PackageRecord> = Vec::new();
+    for line in stdout.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let Some((path, _go)) = line.rsplit_once(": go") else {
+                continue;
+            };
+            let path = path.trim();
+            // 输出可能来自 Windows，按两种分隔符取文件名
+            let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+            let name = file_name.strip_suffix(".exe").unwrap_or(file_name).to_string();
+            records.push(PackageRecord {
+                name,
+                version: None,
+                location: Some(path.to_string()),
+                source: None,
+            });
+            continue;
+        }
+
+        let Some(record) = records.last_mut() else {
+            continue;
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["path", module, ..] => record.source = Some(module.to_string()),
+            ["mod", _module, version, ..] => record.version = Some(strip_v(version)),
+            _ => {}
+        }
+    }
+    Ok(records)
+}
+
+/// `dotnet tool list -g`：表头与分隔线之后每行 `包 ID  版本  命令`
+pub fn parse_dotnet_tool_list(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    Ok(stdout
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            Some(PackageRecord {
+                name,
+                version: parts.next().map(ToString::to_string),
+                location: None,
+                source: None,
+            })
+        })
+        .collect())
+}
+
+/// `code --list-extensions --show-versions`：每行 `publisher.name@version`
+pub fn parse_code_extensions(stdout: &str) -> Result<Vec<PackageRecord>, String> {
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.trim().rsplit_once('@')?;
+            name.contains('.').then(|| PackageRecord {
+                name: name.to_string(),
+                version: Some(version.to_string()),
+                location: None,
+                source: None,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(records: &[PackageRecord]) -> Vec<(&str, Option<&str>)> {
+        records.iter().map(|record| (record.name.as_str(), record.version.as_deref())).collect()
+    }
+
+    #[test]
+    fn json_listings_are_normalized() {
+        let npm = parse_npm_list(
+            r#"{"name":"lib","dependencies":{"@vue/cli":{"version":"5.0.8"},"typescript":{"version":"5.4.2","path":"/usr/lib/node_modules/typescript"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(summary(&npm), vec![("@vue/cli", Some("5.0.8")), ("typescript", Some("5.4.2"))]);
+        assert_eq!(npm[1].location.as_deref(), Some("/usr/lib/node_modules/typescript"));
+
+        let pnpm = parse_pnpm_list(r#"[{"path":"/pnpm/global/5","dependencies":{"vercel":{"version":"33.5.0"}}}]"#).unwrap();
+        assert_eq!(summary(&pnpm), vec![("vercel", Some("33.5.0"))]);
+        assert!(parse_pnpm_list("").unwrap().is_empty());
+
+        let pipx = parse_pipx_list(
+            r#"{"venvs":{"black":{"metadata":{"main_package":{"package":"black","package_version":"24.1.0",
+                "app_paths":[{"__type__":"Path","__Path__":"/home/dev/.local/pipx/venvs/black/bin/black"}]}}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(summary(&pipx), vec![("black", Some("24.1.0"))]);
+        assert_eq!(pipx[0].location.as_deref(), Some("/home/dev/.local/pipx/venvs/black"));
+    }
+
+    #[test]
+    fn text_listings_are_normalized() {
+        let uv = parse_uv_tool_list(
+            "ruff v0.3.0 (/home/dev/.local/share/uv/tools/ruff)\n- ruff (/home/dev/.local/bin/ruff)\nhttpie v3.2.2\n- http\n",
+        )
+        .unwrap();
+        assert_eq!(summary(&uv), vec![("ruff", Some("0.3.0")), ("httpie", Some("3.2.2"))]);
+        assert_eq!(uv[0].location.as_deref(), Some("/home/dev/.local/share/uv/tools/ruff"));
+
+        let cargo = parse_cargo_install_list(
+            "cargo-edit v0.12.2 (/home/dev/src/cargo-edit):\n    cargo-add\nripgrep v14.1.0:\n    rg\n",
+        )
+        .unwrap();
+        assert_eq!(summary(&cargo), vec![("cargo-edit", Some("0.12.2")), ("ripgrep", Some("14.1.0"))]);
+
+        let dotnet = parse_dotnet_tool_list(
+            "Package Id      Version      Commands\n-------------------------------------\ndotnet-ef       8.0.1        dotnet-ef\n",
+        )
+        .unwrap();
+        assert_eq!(summary(&dotnet), vec![("dotnet-ef", Some("8.0.1"))]);
+
+        let code = parse_code_extensions("ms-python.python@2024.0.1\nrust-lang.rust-analyzer@0.3.1850\n").unwrap();
+        assert_eq!(
+            summary(&code),
+            vec![("ms-python.python", Some("2024.0.1")), ("rust-lang.rust-analyzer", Some("0.3.1850"))]
+        );
+    }
+
+    #[test]
+    fn go_binaries_carry_module_path() {
+        let go = parse_go_version_m(
+            "/home/dev/go/bin/gopls: go1.22.0\n\tpath\tgolang.org/x/tools/gopls\n\tmod\tgolang.org/x/tools/gopls\tv0.15.0\th1:abc=\n\
+             \tdep\tgolang.org/x/mod\tv0.15.0\th1:def=\nC:\\Users\\dev\\go\\bin\\dlv.exe: go1.21.6\n\tpath\tgithub.com/go-delve/delve/cmd/dlv\n",
+        )
+        .unwrap();
+        assert_eq!(summary(&go), vec![("gopls", Some("0.15.0")), ("dlv", None)]);
+        assert_eq!(go[0].source.as_deref(), Some("golang.org/x/tools/gopls"));
+        assert_eq!(go[1].source.as_deref(), Some("github.com/go-delve/delve/cmd/dlv"));
+    }
+}
@@ -1,8 +1,9 @@
 use encoding_rs::GBK;
 use std::fmt::Display;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -22,6 +23,9 @@ pub struct ProcessCapture {
 /// 超时退出码：进程被杀但已有部分输出
 pub const TIMEOUT_EXIT_CODE: i32 = -1000;
 
+pub const STREAM_STDOUT: &str = "stdout";
+pub const STREAM_STDERR: &str = "stderr";
+
 pub fn run_command_with_timeout(
     command: &str,
     args: &[&str],
@@ -122,6 +126,80 @@ pub fn execute_process_with_timeout_in_dir(
     }
 }
 
+/// 逐行回调输出（stream 为 stdout / stderr），适合安装等长时间运行的命令；
+/// 超时后结束进程，返回已收到的输出与 TIMEOUT_EXIT_CODE
+pub fn execute_process_streaming(
+    command: &str,
+    args: &[String],
+    timeout_ms: u64,
+    mut on_line: impl FnMut(&'static str, &str),
+) -> Result<ProcessCapture, String> {
+    let mut child = create_command_with_args(command, args, None)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(display_error)?;
+
+    let (sender, receiver) = mpsc::channel::<(&'static str, Vec<u8>)>();
+    let pipes: [(&'static str, Option<Box<dyn Read + Send>>); 2] = [
+        (STREAM_STDOUT, child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>)),
+        (STREAM_STDERR, child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>)),
+    ];
+    for (stream, pipe) in pipes {
+        let Some(pipe) = pipe else { continue };
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(pipe);
+            let mut line = Vec::new();
+            while matches!(reader.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+                if sender.send((stream, std::mem::take(&mut line))).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(1));
+    let mut stdout: Vec<String> = Vec::new();
+    let mut stderr: Vec<String> = Vec::new();
+    let mut timed_out = false;
+    let mut handle_line = |stream: &'static str, bytes: &[u8]| {
+        let line = decode_bytes(bytes);
+        if line.is_empty() {
+            return;
+        }
+        on_line(stream, &line);
+        if stream == STREAM_STDOUT { stdout.push(line) } else { stderr.push(line) }
+    };
+
+    // 两个管道都关闭（读取线程退出）即视为输出结束
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            timed_out = true;
+            let _ = child.kill();
+            break;
+        }
+        match receiver.recv_timeout(remaining.min(Duration::from_millis(200))) {
+            Ok((stream, bytes)) => handle_line(stream, &bytes),
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = child.wait().map_err(display_error)?;
+    for (stream, bytes) in receiver.try_iter() {
+        handle_line(stream, &bytes);
+    }
+
+    Ok(ProcessCapture {
+        stdout: stdout.join("\n"),
+        stderr: stderr.join("\n"),
+        exit_code: if timed_out { TIMEOUT_EXIT_CODE } else { status.code().unwrap_or(-1) },
+    })
+}
+
 fn needs_cmd_wrapper(command: &str) -> bool {
     #[cfg(target_os = "windows")]
     {
@@ -231,4 +309,29 @@ mod tests {
         assert!(result.is_err());
         assert!(result.err().unwrap_or_default().contains("命令执行超时"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_process_streaming_reports_lines_in_order() {
+        let mut lines = Vec::new();
+        let capture = execute_process_streaming(
+            "sh",
+            &["-c".to_string(), "echo one; echo two >&2; echo three".to_string()],
+            5_000,
+            |stream, line| lines.push(format!("{}:{}", stream, line)),
+        )
+        .expect("should execute");
+
+        assert_eq!(capture.exit_code, 0);
+        assert_eq!(capture.stdout, "one\nthree");
+        assert_eq!(capture.stderr, "two");
+        assert!(lines.contains(&"stderr:two".to_string()));
+        let stdout_lines: Vec<&String> = lines.iter().filter(|line| line.starts_with("stdout:")).collect();
+        assert_eq!(stdout_lines, vec!["stdout:one", "stdout:three"]);
+
+        let capture = execute_process_streaming("sh", &["-c".to_string(), "echo start; sleep 5".to_string()], 300, |_, _| {})
+            .expect("should execute");
+        assert_eq!(capture.exit_code, TIMEOUT_EXIT_CODE);
+        assert_eq!(capture.stdout, "start");
+    }
 }
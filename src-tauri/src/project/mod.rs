@@ -0,0 +1,19 @@
+pub mod pins;
+pub mod requirements;
+
+pub use pins::check_project_pins_internal;
+pub use requirements::check_project_requirements_internal;
+
+use crate::contracts::ToolStatus;
+use crate::tools::{detect_tools_parallel, ToolSpec};
+
+/// 只探测被引用的工具，同一工具被多个键引用时只探测一次
+fn detect_resolved_specs(resolved: &[Option<&ToolSpec>]) -> Result<Vec<ToolStatus>, String> {
+    let mut needed: Vec<ToolSpec> = Vec::new();
+    for spec in resolved.iter().flatten() {
+        if !needed.iter().any(|item| item.name == spec.name) {
+            needed.push((*spec).clone());
+        }
+    }
+    detect_tools_parallel(&needed)
+}
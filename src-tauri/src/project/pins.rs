@@ -0,0 +1,473 @@
+use std::path::Path;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::contracts::{ProjectPinsReport, ToolchainPinCheck};
+use crate::deploy::ensure_existing_dir;
+use crate::tools::{asdf_plugin_command, find_tool_spec, load_tool_catalog};
+
+use super::detect_resolved_specs;
+use super::requirements::{
+    evaluate_requirement, Requirement, REQUIREMENT_MISSING, REQUIREMENT_PASS,
+};
+
+pub const PIN_MATCH: &str = "match";
+pub const PIN_MISMATCH: &str = "mismatch";
+pub const PIN_MISSING: &str = "missing";
+/// 渠道名（stable、lts/*）或无法换算为版本范围的写法
+pub const PIN_UNKNOWN: &str = "unknown";
+
+/// 从生态配置文件中读到的版本声明；range 为换算后的 semver 范围
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolchainPin {
+    /// 与工具目录匹配的键（命令名），如 node、python、rustc
+    pub tool: String,
+    pub pinned: String,
+    pub range: Option<String>,
+    pub source: String,
+}
+
+pub fn check_project_pins_internal(project_path: &str) -> Result<ProjectPinsReport, String> {
+    let project_dir = ensure_existing_dir(project_path, "项目目录")?;
+    let pins = collect_project_pins(&project_dir);
+
+    let catalog = load_tool_catalog(Some(&project_dir));
+    let resolved: Vec<_> = pins.iter().map(|pin| find_tool_spec(&catalog.specs, &pin.tool)).collect();
+    let statuses = detect_resolved_specs(&resolved)?;
+
+    let checks: Vec<ToolchainPinCheck> = pins
+        .iter()
+        .zip(resolved)
+        .map(|(pin, spec)| {
+            let status = spec.and_then(|spec| statuses.iter().find(|status| status.name == spec.name));
+            let display_name = spec.map(|spec| spec.name.clone()).unwrap_or_else(|| pin.tool.clone());
+
+            let Some(range) = pin.range.clone() else {
+                return ToolchainPinCheck {
+                    tool: pin.tool.clone(),
+                    tool_name: spec.map(|spec| spec.name.clone()),
+                    source: pin.source.clone(),
+                    pinned: pin.pinned.clone(),
+                    range: None,
+                    installed_version: status.and_then(|status| status.version.clone()),
+                    install_key: spec.and_then(|spec| spec.install_key.clone()),
+                    status: PIN_UNKNOWN.to_string(),
+                    message: Some(format!("{} 不是具体版本，无法比较", pin.pinned)),
+                };
+            };
+
+            let requirement = Requirement { tool: pin.tool.clone(), range: Ok(range.clone()) };
+            let check = evaluate_requirement(&requirement, spec, status);
+            let (status, message) = match check.status.as_str() {
+                REQUIREMENT_PASS => (PIN_MATCH, None),
+                REQUIREMENT_MISSING => (
+                    PIN_MISSING,
+                    Some(format!("项目要求 {} {}（{}），但未检测到该工具", display_name, pin.pinned, pin.source)),
+                ),
+                _ if spec.is_none() || check.installed_version.is_none() => (PIN_UNKNOWN, check.message.clone()),
+                _ => (
+                    PIN_MISMATCH,
+                    Some(format!(
+                        "项目要求 {} {}（{}），PATH 中为 {}",
+                        display_name,
+                        pin.pinned,
+                        pin.source,
+                        check.installed_version.as_deref().unwrap_or("--")
+                    )),
+                ),
+            };
+
+            ToolchainPinCheck {
+                tool: pin.tool.clone(),
+                tool_name: check.tool_name,
+                source: pin.source.clone(),
+                pinned: pin.pinned.clone(),
+                range: Some(range),
+                installed_version: check.installed_version,
+                install_key: check.install_key,
+                status: status.to_string(),
+                message,
+            }
+        })
+        .collect();
+
+    Ok(ProjectPinsReport {
+        project_path: project_dir.to_string_lossy().to_string(),
+        mismatches: checks
+            .iter()
+            .filter(|check| check.status == PIN_MISMATCH || check.status == PIN_MISSING)
+            .count(),
+        checks,
+    })
+}
+
+type PinParser = fn(&str, &str) -> Vec<ToolchainPin>;
+
+/// 读取目录下所有可识别的版本声明文件，解析失败的文件直接跳过
+pub fn collect_project_pins(project_dir: &Path) -> Vec<ToolchainPin> {
+    let parsers: [(&str, PinParser); 12] = [
+        (".nvmrc", parse_node_version_file),
+        (".node-version", parse_node_version_file),
+        ("package.json", parse_package_json),
+        ("rust-toolchain.toml", parse_rust_toolchain_toml),
+        ("rust-toolchain", parse_rust_toolchain_legacy),
+        (".python-version", parse_python_version_file),
+        ("pyproject.toml", parse_pyproject),
+        ("go.mod", parse_go_mod),
+        ("global.json", parse_global_json),
+        (".sdkmanrc", parse_sdkmanrc),
+        (".tool-versions", parse_tool_versions),
+        (".java-version", parse_java_version_file),
+    ];
+
+    let mut pins = Vec::new();
+    for (file_name, parser) in parsers {
+        if let Ok(content) = std::fs::read_to_string(project_dir.join(file_name)) {
+            pins.extend(parser(&content, file_name));
+        }
+    }
+    pins
+}
+
+fn pin(tool: &str, pinned: &str, range: Option<String>, source: &str) -> ToolchainPin {
+    ToolchainPin {
+        tool: tool.to_string(),
+        pinned: pinned.to_string(),
+        range,
+        source: source.to_string(),
+    }
+}
+
+/// 具体版本换算为范围：三段精确匹配，两段匹配次版本，一段匹配主版本
+pub fn version_to_range(text: &str) -> Option<String> {
+    let regex = Regex::new(r"(\d+)(?:\.(\d+))?(?:\.(\d+))?").ok()?;
+    let captures = regex.captures(text)?;
+    let parts: Vec<&str> = (1..=3).filter_map(|index| captures.get(index)).map(|value| value.as_str()).collect();
+
+    Some(match parts.len() {
+        3 => format!("={}", parts.join(".")),
+        2 => format!("~{}", parts.join(".")),
+        _ => format!("^{}", parts[0]),
+    })
+}
+
+/// 首个非空、非注释行
+fn first_value_line(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn parse_node_version_file(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Some(value) = first_value_line(content) else {
+        return Vec::new();
+    };
+
+    // lts/*、lts/hydrogen、node、stable 等别名无法换算为版本
+    let range = if value.starts_with(|ch: char| ch.is_ascii_digit() || ch == 'v') {
+        version_to_range(value)
+    } else {
+        None
+    };
+    vec![pin("node", value, range, source)]
+}
+
+fn parse_package_json(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Ok(document) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+
+    let mut pins = Vec::new();
+    if let Some(engines) = document.get("engines").and_then(Value::as_object) {
+        for tool in ["node", "npm", "pnpm", "yarn", "bun"] {
+            if let Some(range) = engines.get(tool).and_then(Value::as_str) {
+                pins.push(pin(tool, range, Some(range.trim().to_string()), source));
+            }
+        }
+    }
+
+    // "pnpm@9.1.0+sha512.xxx"，由 corepack 精确锁定
+    if let Some(package_manager) = document.get("packageManager").and_then(Value::as_str) {
+        if let Some((tool, version)) = package_manager.split_once('@') {
+            let version = version.split('+').next().unwrap_or(version);
+            pins.push(pin(tool, version, version_to_range(version), &format!("{} packageManager", source)));
+        }
+    }
+
+    pins
+}
+
+fn parse_rust_toolchain_toml(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Ok(document) = toml::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+
+    document
+        .pointer("/toolchain/channel")
+        .and_then(Value::as_str)
+        .map(|channel| rust_channel_pin(channel, source))
+        .into_iter()
+        .collect()
+}
+
+fn parse_rust_toolchain_legacy(content: &str, source: &str) -> Vec<ToolchainPin> {
+    first_value_line(content)
+        .map(|channel| rust_channel_pin(channel, source))
+        .into_iter()
+        .collect()
+}
+
+/// stable / beta / nightly-2024-01-01 为渠道名，只有 1.77.0 这类才可比较
+fn rust_channel_pin(channel: &str, source: &str) -> ToolchainPin {
+    let range = if channel.starts_with(|ch: char| ch.is_ascii_digit()) {
+        version_to_range(channel)
+    } else {
+        None
+    };
+    pin("rustc", channel, range, source)
+}
+
+fn parse_python_version_file(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Some(value) = first_value_line(content) else {
+        return Vec::new();
+    };
+
+    // pyenv 允许 "pypy3.10-7.3.12"、"miniconda3-latest" 等，只比较纯 CPython 版本
+    let range = if value.starts_with(|ch: char| ch.is_ascii_digit()) {
+        version_to_range(value)
+    } else {
+        None
+    };
+    vec![pin("python", value, range, source)]
+}
+
+fn parse_pyproject(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Ok(document) = toml::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+
+    let requires = document
+        .pointer("/project/requires-python")
+        .or_else(|| document.pointer("/tool/poetry/dependencies/python"))
+        .and_then(Value::as_str);
+
+    requires
+        .map(|requires| pin("python", requires, pep440_to_range(requires), source))
+        .into_iter()
+        .collect()
+}
+
+/// 常见 PEP 440 写法换算为 semver 范围；含 != 等无法表达的写法返回 None
+pub fn pep440_to_range(specifier: &str) -> Option<String> {
+    let mut comparators = Vec::new();
+    for part in specifier.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let comparator = if let Some(version) = part.strip_prefix("~=") {
+            let version = version.trim();
+            if version.split('.').count() >= 3 {
+                format!("~{}", version)
+            } else {
+                format!("^{}", version)
+            }
+        } else if let Some(version) = part.strip_prefix("==") {
+            format!("={}", version.trim().trim_end_matches(".*"))
+        } else if part.starts_with("!=") || part.starts_with("===") {
+            return None;
+        } else {
+            part.to_string()
+        };
+        comparators.push(comparator);
+    }
+
+    if comparators.is_empty() {
+        None
+    } else {
+        Some(comparators.join(" "))
+    }
+}
+
+fn parse_go_mod(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let mut go_directive = None;
+    let mut toolchain = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(version) = line.strip_prefix("go ") {
+            go_directive = Some(version.trim());
+        } else if let Some(version) = line.strip_prefix("toolchain ") {
+            toolchain = Some(version.trim());
+        }
+    }
+
+    // Go 1.21 起 go 指令表示最低版本，toolchain 表示建议使用的版本，优先按后者比较
+    match (toolchain, go_directive) {
+        (Some(toolchain), _) => {
+            let version = toolchain.trim_start_matches("go");
+            vec![pin("go", toolchain, Some(format!(">={}", version)), &format!("{} toolchain", source))]
+        }
+        (None, Some(version)) => vec![pin("go", version, Some(format!(">={}", version)), source)],
+        (None, None) => Vec::new(),
+    }
+}
+
+fn parse_global_json(content: &str, source: &str) -> Vec<ToolchainPin> {
+    let Ok(document) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let Some(version) = document.pointer("/sdk/version").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+
+    // rollForward 缺省为 latestPatch：同一功能段（8.0.1xx）内更高补丁均可
+    let roll_forward = document
+        .pointer("/sdk/rollForward")
+        .and_then(Value::as_str)
+        .unwrap_or("latestPatch");
+    let range = match roll_forward {
+        "disable" => Some(format!("={}", version)),
+        "latestMajor" => Some(format!(">={}", version)),
+        "major" | "latestMinor" | "minor" | "latestFeature" | "feature" => {
+            let major = version.split('.').next().unwrap_or(version);
+            Some(format!(">={} <{}", version, major.parse::<u64>().map(|value| value + 1).unwrap_or(0)))
+        }
+        _ => dotnet_feature_band(version).map(|upper| format!(">={} <{}", version, upper)),
+    };
+
+    vec![pin("dotnet", version, range, source)]
+}
+
+/// 8.0.100 → 8.0.200（下一个功能段）
+fn dotnet_feature_band(version: &str) -> Option<String> {
+    let parts: Vec<u64> = version
+        .split('.')
+        .take(3)
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.len() != 3 {
+        return None;
+    }
+    Some(format!("{}.{}.{}", parts[0], parts[1], (parts[2] / 100 + 1) * 100))
+}
+
+fn parse_sdkmanrc(content: &str, source: &str) -> Vec<ToolchainPin> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(candidate, version)| {
+            let version = version.trim();
+            pin(sdk_candidate_command(candidate.trim()), version, version_to_range(version), source)
+        })
+        .collect()
+}
+
+fn parse_java_version_file(content: &str, source: &str) -> Vec<ToolchainPin> {
+    first_value_line(content)
+        .map(|version| pin("java", version, version_to_range(version), source))
+        .into_iter()
+        .collect()
+}
+
+fn parse_tool_versions(content: &str, source: &str) -> Vec<ToolchainPin> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let plugin = parts.next()?;
+            // 多个版本时第一个为默认版本
+            let version = parts.next()?;
+            let range = if version == "system" || version.starts_with("ref:") || version.starts_with("path:") {
+                None
+            } else {
+                // temurin-21.0.2+13.0.LTS、corretto-17.0.9.8.1 等发行版前缀
+                let numeric = version
+                    .rsplit('-')
+                    .find(|part| part.starts_with(|ch: char| ch.is_ascii_digit()))
+                    .unwrap_or(version);
+                version_to_range(numeric)
+            };
+            Some(pin(asdf_plugin_command(plugin), version, range, source))
+        })
+        .collect()
+}
+
+fn sdk_candidate_command(candidate: &str) -> &str {
+    match candidate {
+        "maven" => "mvn",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools(pins: &[ToolchainPin]) -> Vec<(&str, Option<&str>)> {
+        pins.iter().map(|pin| (pin.tool.as_str(), pin.range.as_deref())).collect()
+    }
+
+    #[test]
+    fn node_ecosystem_files() {
+        assert_eq!(tools(&parse_node_version_file("v18\n", ".nvmrc")), vec![("node", Some("^18"))]);
+        assert_eq!(tools(&parse_node_version_file("lts/hydrogen\n", ".nvmrc")), vec![("node", None)]);
+
+        let package = r#"{"engines":{"node":">=18 <21","pnpm":"^9"},"packageManager":"pnpm@9.1.0+sha512.abc"}"#;
+        assert_eq!(
+            tools(&parse_package_json(package, "package.json")),
+            vec![("node", Some(">=18 <21")), ("pnpm", Some("^9")), ("pnpm", Some("=9.1.0"))]
+        );
+    }
+
+    #[test]
+    fn rust_python_go_and_dotnet_files() {
+        let toolchain = "[toolchain]\nchannel = \"1.77.0\"\ncomponents = [\"clippy\"]\n";
+        assert_eq!(tools(&parse_rust_toolchain_toml(toolchain, "rust-toolchain.toml")), vec![("rustc", Some("=1.77.0"))]);
+        assert_eq!(tools(&parse_rust_toolchain_legacy("nightly-2024-01-01\n", "rust-toolchain")), vec![("rustc", None)]);
+
+        assert_eq!(tools(&parse_python_version_file("3.12\n", ".python-version")), vec![("python", Some("~3.12"))]);
+        let pyproject = "[project]\nname = \"demo\"\nrequires-python = \">=3.10, <3.13\"\n";
+        assert_eq!(tools(&parse_pyproject(pyproject, "pyproject.toml")), vec![("python", Some(">=3.10 <3.13"))]);
+        assert_eq!(pep440_to_range("~=3.10"), Some("^3.10".to_string()));
+        assert_eq!(pep440_to_range(">=3.8,!=3.9.0"), None);
+
+        let go_mod = "module example.com/demo\n\ngo 1.22\n\ntoolchain go1.22.1\n";
+        assert_eq!(tools(&parse_go_mod(go_mod, "go.mod")), vec![("go", Some(">=1.22.1"))]);
+
+        let global = r#"{"sdk":{"version":"8.0.100"}}"#;
+        assert_eq!(tools(&parse_global_json(global, "global.json")), vec![("dotnet", Some(">=8.0.100 <8.0.200"))]);
+    }
+
+    #[test]
+    fn sdkman_and_tool_versions_files() {
+        let sdkmanrc = "# Enable auto-env\njava=21.0.2-tem\nmaven=3.9.6\n";
+        assert_eq!(
+            tools(&parse_sdkmanrc(sdkmanrc, ".sdkmanrc")),
+            vec![("java", Some("=21.0.2")), ("mvn", Some("=3.9.6"))]
+        );
+
+        let tool_versions = "nodejs 20.11.1 18.19.0\njava temurin-21.0.2+13.0.LTS\npython system\ngolang 1.22.1 # pinned\n";
+        assert_eq!(
+            tools(&parse_tool_versions(tool_versions, ".tool-versions")),
+            vec![
+                ("node", Some("=20.11.1")),
+                ("java", Some("=21.0.2")),
+                ("python", None),
+                ("go", Some("=1.22.1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_reads_all_known_files_in_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".nvmrc"), "18\n").unwrap();
+        std::fs::write(dir.path().join("go.mod"), "module demo\n\ngo 1.21\n").unwrap();
+        std::fs::write(dir.path().join("package.json"), "{ not json").unwrap();
+
+        let pins = collect_project_pins(dir.path());
+        assert_eq!(tools(&pins), vec![("node", Some("^18")), ("go", Some(">=1.21"))]);
+        assert_eq!(pins[0].source, ".nvmrc");
+    }
+}
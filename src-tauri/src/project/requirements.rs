@@ -0,0 +1,264 @@
+use std::path::Path;
+
+use semver::{Comparator, Op, Version, VersionReq};
+use serde_json::Value;
+
+use crate::contracts::{ProjectRequirementsReport, RequirementCheck, ToolStatus};
+use crate::deploy::ensure_existing_dir;
+use crate::tools::{find_project_config, find_tool_spec, load_tool_catalog, read_config_document, ToolSpec};
+
+use super::detect_resolved_specs;
+
+pub const REQUIREMENT_PASS: &str = "pass";
+pub const REQUIREMENT_FAIL: &str = "fail";
+pub const REQUIREMENT_MISSING: &str = "missing";
+pub const REQUIREMENT_OUTDATED: &str = "outdated";
+
+/// 配置中的一条要求；值不是字符串时保留错误信息，在报告中标记为 fail
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub tool: String,
+    pub range: Result<String, String>,
+}
+
+pub fn check_project_requirements_internal(project_path: &str) -> Result<ProjectRequirementsReport, String> {
+    let project_dir = ensure_existing_dir(project_path, "项目目录")?;
+    let config_path = find_project_config(&project_dir)
+        .ok_or_else(|| format!("项目目录中未找到 .devenvprobe.toml: {}", project_dir.display()))?;
+    let requirements = read_requirements(&config_path)?;
+
+    let catalog = load_tool_catalog(Some(&project_dir));
+    let resolved: Vec<Option<&ToolSpec>> = requirements
+        .iter()
+        .map(|requirement| find_tool_spec(&catalog.specs, &requirement.tool))
+        .collect();
+
+    let statuses = detect_resolved_specs(&resolved)?;
+
+    let checks: Vec<RequirementCheck> = requirements
+        .iter()
+        .zip(resolved)
+        .map(|(requirement, spec)| {
+            let status = spec.and_then(|spec| statuses.iter().find(|status| status.name == spec.name));
+            evaluate_requirement(requirement, spec, status)
+        })
+        .collect();
+
+    Ok(ProjectRequirementsReport {
+        project_path: project_dir.to_string_lossy().to_string(),
+        config_path: config_path.to_string_lossy().to_string(),
+        passed: checks.iter().all(|check| check.status == REQUIREMENT_PASS),
+        checks,
+    })
+}
+
+pub fn read_requirements(config_path: &Path) -> Result<Vec<Requirement>, String> {
+    let document = read_config_document(config_path)?;
+    let table = match document.get("requirements") {
+        None => return Ok(Vec::new()),
+        Some(Value::Object(table)) => table,
+        Some(_) => return Err("requirements 必须是表（TOML 中写作 [requirements]）".to_string()),
+    };
+
+    Ok(table
+        .iter()
+        .map(|(tool, value)| Requirement {
+            tool: tool.clone(),
+            range: value
+                .as_str()
+                .map(|range| range.trim().to_string())
+                .ok_or_else(|| "版本范围必须是字符串".to_string()),
+        })
+        .collect())
+}
+
+/// 支持 npm 风格写法：空格分隔的比较器（`>=20 <21`）与 `||` 备选
+pub fn parse_version_range(text: &str) -> Result<Vec<VersionReq>, String> {
+    text.split("||")
+        .map(|alternative| {
+            let mut comparators: Vec<String> = Vec::new();
+            let mut pending = String::new();
+            for token in alternative.split_whitespace() {
+                let token = token.trim_end_matches(',');
+                pending.push_str(token);
+                if token.chars().all(|ch| matches!(ch, '<' | '>' | '=' | '^' | '~')) {
+                    continue;
+                }
+                comparators.push(std::mem::take(&mut pending));
+            }
+            if !pending.is_empty() {
+                comparators.push(pending);
+            }
+
+            if comparators.is_empty() {
+                return Err(format!("版本范围为空: {}", text));
+            }
+
+            VersionReq::parse(&comparators.join(", ")).map_err(|error| format!("版本范围无效 {}: {}", text, error))
+        })
+        .collect()
+}
+
+pub fn evaluate_requirement(
+    requirement: &Requirement,
+    spec: Option<&ToolSpec>,
+    status: Option<&ToolStatus>,
+) -> RequirementCheck {
+    let mut check = RequirementCheck {
+        tool: requirement.tool.clone(),
+        range: requirement.range.clone().unwrap_or_default(),
+        status: REQUIREMENT_FAIL.to_string(),
+        tool_name: spec.map(|spec| spec.name.clone()),
+        installed_version: None,
+        install_key: spec.and_then(|spec| spec.install_key.clone()),
+        message: None,
+    };
+
+    let range = match &requirement.range {
+        Ok(range) => range,
+        Err(error) => {
+            check.message = Some(error.clone());
+            return check;
+        }
+    };
+
+    if spec.is_none() {
+        check.message = Some(format!("工具目录中没有 {}，可在 [[tools]] 中定义", requirement.tool));
+        return check;
+    }
+
+    let requirements = match parse_version_range(range) {
+        Ok(requirements) => requirements,
+        Err(error) => {
+            check.message = Some(error);
+            return check;
+        }
+    };
+
+    let Some(status) = status.filter(|status| status.installed) else {
+        check.status = REQUIREMENT_MISSING.to_string();
+        check.message = status.and_then(|status| status.details.clone());
+        return check;
+    };
+
+    let parsed = status.parsed_version.as_ref().and_then(|version| version.semver.clone());
+    check.installed_version = parsed.clone().or_else(|| status.version.clone());
+
+    let Some(version) = parsed.as_deref().and_then(|value| Version::parse(value).ok()) else {
+        check.message = Some("无法解析已安装版本".to_string());
+        return check;
+    };
+
+    if requirements.iter().any(|requirement| requirement.matches(&version)) {
+        check.status = REQUIREMENT_PASS.to_string();
+    } else if is_below_range(&version, &requirements) {
+        check.status = REQUIREMENT_OUTDATED.to_string();
+        check.message = Some(format!("已安装 {}，需要 {}", version, range));
+    } else {
+        check.message = Some(format!("已安装 {}，不满足 {}", version, range));
+    }
+
+    check
+}
+
+/// 版本低于每个备选范围的下限时视为过旧，其余不满足的情况（如版本过新）为 fail
+fn is_below_range(version: &Version, requirements: &[VersionReq]) -> bool {
+    requirements.iter().all(|requirement| {
+        requirement
+            .comparators
+            .iter()
+            .any(|comparator| lower_bound(comparator).is_some_and(|minimum| version < &minimum))
+    })
+}
+
+fn lower_bound(comparator: &Comparator) -> Option<Version> {
+    match comparator.op {
+        Op::Exact | Op::Greater | Op::GreaterEq | Op::Tilde | Op::Caret | Op::Wildcard => {
+            let mut minimum = Version::new(
+                comparator.major,
+                comparator.minor.unwrap_or(0),
+                comparator.patch.unwrap_or(0),
+            );
+            minimum.pre = comparator.pre.clone();
+            Some(minimum)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(tool: &str, range: &str) -> Requirement {
+        Requirement { tool: tool.to_string(), range: Ok(range.to_string()) }
+    }
+
+    fn installed(spec: &ToolSpec, semver: &str) -> ToolStatus {
+        ToolStatus {
+            category: spec.category.clone(),
+            install_key: spec.install_key.clone(),
+            ..ToolStatus::for_test(&spec.name, &spec.command, Some(semver)).with_semver(semver)
+        }
+    }
+
+    #[test]
+    fn npm_style_ranges_are_accepted() {
+        let node = parse_version_range(">=20 <21").unwrap();
+        assert!(node[0].matches(&Version::new(20, 11, 1)));
+        assert!(!node[0].matches(&Version::new(21, 0, 0)));
+
+        let java = parse_version_range("21").unwrap();
+        assert!(java[0].matches(&Version::new(21, 0, 2)));
+
+        let either = parse_version_range("^18 || >= 20").unwrap();
+        assert_eq!(either.len(), 2);
+
+        assert!(parse_version_range("latest").is_err());
+        assert!(parse_version_range(" || ^9").is_err());
+    }
+
+    #[test]
+    fn evaluate_classifies_pass_outdated_fail_and_missing() {
+        let node = ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", Some("nodejs-lts"));
+        let requirement = requirement("node", ">=20 <21");
+
+        let check = evaluate_requirement(&requirement, Some(&node), Some(&installed(&node, "20.11.1")));
+        assert_eq!(check.status, REQUIREMENT_PASS);
+
+        let check = evaluate_requirement(&requirement, Some(&node), Some(&installed(&node, "18.19.0")));
+        assert_eq!(check.status, REQUIREMENT_OUTDATED);
+        assert_eq!(check.install_key.as_deref(), Some("nodejs-lts"));
+
+        let check = evaluate_requirement(&requirement, Some(&node), Some(&installed(&node, "22.1.0")));
+        assert_eq!(check.status, REQUIREMENT_FAIL);
+
+        let mut absent = installed(&node, "0.0.0");
+        absent.installed = false;
+        let check = evaluate_requirement(&requirement, Some(&node), Some(&absent));
+        assert_eq!(check.status, REQUIREMENT_MISSING);
+        assert_eq!(check.install_key.as_deref(), Some("nodejs-lts"));
+
+        let check = evaluate_requirement(&self::requirement("bazel", "^7"), None, None);
+        assert_eq!(check.status, REQUIREMENT_FAIL);
+    }
+
+    #[test]
+    fn read_requirements_keeps_invalid_values_as_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".devenvprobe.toml");
+        std::fs::write(&path, "[requirements]\nnode = \">=20 <21\"\npnpm = \"^9\"\ndocker = 24\n").unwrap();
+
+        let requirements = read_requirements(&path).unwrap();
+        assert_eq!(requirements.len(), 3);
+        let docker = requirements.iter().find(|item| item.tool == "docker").unwrap();
+        assert!(docker.range.is_err());
+
+        let specs = vec![
+            ToolSpec::builtin("Docker", "docker", &["--version"], "Container", Some("docker-desktop")),
+            ToolSpec::builtin("Docker Compose", "docker", &["compose", "version"], "Container", Some("docker-desktop")),
+        ];
+        assert_eq!(find_tool_spec(&specs, "docker").map(|spec| spec.name.as_str()), Some("Docker"));
+        assert_eq!(find_tool_spec(&specs, "docker compose").map(|spec| spec.name.as_str()), Some("Docker Compose"));
+    }
+}
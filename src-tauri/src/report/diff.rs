@@ -0,0 +1,344 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use semver::Version;
+
+use crate::contracts::{EnvironmentReport, EnvironmentReportDiff, FieldDiff, ToolDiff, ToolStatus};
+
+use super::ENVIRONMENT_REPORT_SCHEMA;
+
+pub const TOOL_ADDED: &str = "added";
+pub const TOOL_REMOVED: &str = "removed";
+pub const TOOL_CHANGED: &str = "changed";
+
+pub const VERSION_UPGRADE: &str = "upgrade";
+pub const VERSION_DOWNGRADE: &str = "downgrade";
+pub const VERSION_UNKNOWN: &str = "unknown";
+
+const HTML_REPORT_MARKER: &str = "id=\"devenvprobe-report\"";
+
+/// 参数可以是报告文件路径，也可以是 JSON / HTML 报告内容
+pub fn load_environment_report(source: &str) -> Result<EnvironmentReport, String> {
+    let trimmed = source.trim();
+    let content = if trimmed.starts_with('{') || trimmed.starts_with('<') {
+        trimmed.to_string()
+    } else {
+        std::fs::read_to_string(Path::new(trimmed)).map_err(|error| format!("读取报告失败 {}: {}", trimmed, error))?
+    };
+
+    let json = if content.trim_start().starts_with('<') { extract_embedded_json(&content)? } else { content.as_str() };
+    let report: EnvironmentReport =
+        serde_json::from_str(json).map_err(|error| format!("报告格式无效（仅支持 JSON 或 HTML 报告）: {}", error))?;
+    if report.schema != ENVIRONMENT_REPORT_SCHEMA {
+        return Err(format!("不是 DevEnvProbe 环境报告：schema = {}", report.schema));
+    }
+    Ok(report)
+}
+
+fn extract_embedded_json(html: &str) -> Result<&str, String> {
+    let missing = || "HTML 报告中没有内嵌的 JSON 数据".to_string();
+    let marker = html.find(HTML_REPORT_MARKER).ok_or_else(missing)?;
+    let start = marker + html[marker..].find('>').ok_or_else(missing)? + 1;
+    let end = start + html[start..].find("</script>").ok_or_else(missing)?;
+    Ok(&html[start..end])
+}
+
+/// 以 before 为基准比较 after：工具增删、版本升降、路径、系统与环境变量差异
+pub fn diff_reports(before: &EnvironmentReport, after: &EnvironmentReport) -> EnvironmentReportDiff {
+    let tools = diff_tools(&before.tools, &after.tools);
+    let system = diff_system(before, after);
+    let env = diff_env(before, after);
+
+    let mut diff = EnvironmentReportDiff {
+        before_label: report_label(before),
+        after_label: report_label(after),
+        identical: tools.is_empty() && system.is_empty() && env.is_empty(),
+        tools,
+        system,
+        env,
+        text: String::new(),
+    };
+    diff.text = render_diff_text(&diff);
+    diff
+}
+
+fn report_label(report: &EnvironmentReport) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(report.generated_at_ms as i64)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| report.generated_at_ms.to_string());
+    format!("{} @ {}", report.system.host_name, time)
+}
+
+/// 未安装的工具视为不存在，按名称对齐两份报告
+fn installed_tools(tools: &[ToolStatus]) -> BTreeMap<&str, &ToolStatus> {
+    tools
+        .iter()
+        .filter(|tool| tool.installed)
+        .map(|tool| (tool.name.as_str(), tool))
+        .collect()
+}
+
+fn diff_tools(before: &[ToolStatus], after: &[ToolStatus]) -> Vec<ToolDiff> {
+    let before = installed_tools(before);
+    let after = installed_tools(after);
+    let mut names: Vec<&str> = before.keys().chain(after.keys()).copied().collect();
+    names.sort_unstable();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let old = before.get(name).copied();
+            let new = after.get(name).copied();
+            let version_changed = old.map(|tool| &tool.version) != new.map(|tool| &tool.version);
+            let path_changed = old.map(|tool| &tool.install_path) != new.map(|tool| &tool.install_path);
+
+            let change = match (old, new) {
+                (None, Some(_)) => TOOL_ADDED,
+                (Some(_), None) => TOOL_REMOVED,
+                _ if version_changed || path_changed => TOOL_CHANGED,
+                _ => return None,
+            };
+
+            Some(ToolDiff {
+                name: name.to_string(),
+                change: change.to_string(),
+                before_version: old.and_then(|tool| tool.version.clone()),
+                after_version: new.and_then(|tool| tool.version.clone()),
+                version_direction: match (old, new) {
+                    (Some(old), Some(new)) if version_changed => Some(version_direction(old, new).to_string()),
+                    _ => None,
+                },
+                path_changed: change == TOOL_CHANGED && path_changed,
+                before_path: old.and_then(|tool| tool.install_path.clone()),
+                after_path: new.and_then(|tool| tool.install_path.clone()),
+            })
+        })
+        .collect()
+}
+
+fn tool_semver(tool: &ToolStatus) -> Option<Version> {
+    let semver = tool.parsed_version.as_ref()?.semver.as_deref()?;
+    Version::parse(semver).ok()
+}
+
+pub fn version_direction(before: &ToolStatus, after: &ToolStatus) -> &'static str {
+    match (tool_semver(before), tool_semver(after)) {
+        (Some(old), Some(new)) => match new.cmp_precedence(&old) {
+            Ordering::Greater => VERSION_UPGRADE,
+            Ordering::Less => VERSION_DOWNGRADE,
+            // 仅构建号等不参与排序的部分不同
+            Ordering::Equal => VERSION_UNKNOWN,
+        },
+        _ => VERSION_UNKNOWN,
+    }
+}
+
+fn field(name: &str, before: &str, after: &str) -> Option<FieldDiff> {
+    (before != after).then(|| FieldDiff {
+        name: name.to_string(),
+        before: Some(before.to_string()).filter(|value| !value.is_empty()),
+        after: Some(after.to_string()).filter(|value| !value.is_empty()),
+    })
+}
+
+/// 只比较稳定的属性，CPU 占用、内存用量等实时数据不参与比较
+fn diff_system(before: &EnvironmentReport, after: &EnvironmentReport) -> Vec<FieldDiff> {
+    let (old, new) = (&before.system, &after.system);
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    [
+        field("hostName", &old.host_name, &new.host_name),
+        field("osName", &old.os_name, &new.os_name),
+        field("osVersion", &old.os_version, &new.os_version),
+        field("buildNumber", &old.build_number, &new.build_number),
+        field("architecture", &old.architecture, &new.architecture),
+        field("cpuModel", &old.cpu_model, &new.cpu_model),
+        field("cpuLogicalCores", &old.cpu_logical_cores.to_string(), &new.cpu_logical_cores.to_string()),
+        field("totalMemoryGb", &format!("{:.1}", old.total_memory_gb), &format!("{:.1}", new.total_memory_gb)),
+        field("environmentKind", &optional(&old.environment_kind), &optional(&new.environment_kind)),
+        field(
+            "dockerServerVersion",
+            &optional(&before.docker.server_version),
+            &optional(&after.docker.server_version),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn diff_env(before: &EnvironmentReport, after: &EnvironmentReport) -> Vec<FieldDiff> {
+    let old: BTreeMap<&str, &str> = before.env.iter().map(|entry| (entry.name.as_str(), entry.value.as_str())).collect();
+    let new: BTreeMap<&str, &str> = after.env.iter().map(|entry| (entry.name.as_str(), entry.value.as_str())).collect();
+    let mut names: Vec<&str> = old.keys().chain(new.keys()).copied().collect();
+    names.sort_unstable();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (before, after) = (old.get(name).copied(), new.get(name).copied());
+            (before != after).then(|| FieldDiff {
+                name: name.to_string(),
+                before: before.map(ToString::to_string),
+                after: after.map(ToString::to_string),
+            })
+        })
+        .collect()
+}
+
+fn or_none(value: Option<&str>) -> &str {
+    value.unwrap_or("(无)")
+}
+
+/// 纯文本形式，便于直接粘贴到问题单
+pub fn render_diff_text(diff: &EnvironmentReportDiff) -> String {
+    let mut lines = vec![format!("环境对比：{}  →  {}", diff.before_label, diff.after_label)];
+    if diff.identical {
+        lines.push("两份报告没有差异".to_string());
+        return lines.join("\n");
+    }
+
+    if !diff.tools.is_empty() {
+        lines.push(String::new());
+        lines.push("[工具]".to_string());
+        for tool in &diff.tools {
+            let before = or_none(tool.before_version.as_deref());
+            let after = or_none(tool.after_version.as_deref());
+            match tool.change.as_str() {
+                TOOL_ADDED => lines.push(format!("+ {} {}", tool.name, after)),
+                TOOL_REMOVED => lines.push(format!("- {} {}", tool.name, before)),
+                _ => {
+                    if let Some(direction) = &tool.version_direction {
+                        let label = match direction.as_str() {
+                            VERSION_UPGRADE => "升级",
+                            VERSION_DOWNGRADE => "降级",
+                            _ => "变化",
+                        };
+                        lines.push(format!("~ {} {} → {}（{}）", tool.name, before, after, label));
+                    }
+                    if tool.path_changed {
+                        lines.push(format!(
+                            "~ {} 路径 {} → {}",
+                            tool.name,
+                            or_none(tool.before_path.as_deref()),
+                            or_none(tool.after_path.as_deref())
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    for (title, fields) in [("[系统]", &diff.system), ("[环境变量]", &diff.env)] {
+        if fields.is_empty() {
+            continue;
+        }
+        lines.push(String::new());
+        lines.push(title.to_string());
+        for field in fields {
+            lines.push(format!(
+                "~ {}: {} → {}",
+                field.name,
+                or_none(field.before.as_deref()),
+                or_none(field.after.as_deref())
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::{DockerSummary, EnvVarEntry};
+
+    fn tool(name: &str, version: &str, path: &str) -> ToolStatus {
+        ToolStatus {
+            install_path: Some(path.to_string()),
+            ..ToolStatus::for_test(name, name, Some(version)).with_semver(version)
+        }
+    }
+
+    fn report(tools: Vec<ToolStatus>, env: &[(&str, &str)]) -> EnvironmentReport {
+        EnvironmentReport {
+            schema: ENVIRONMENT_REPORT_SCHEMA.to_string(),
+            schema_version: 1,
+            generated_at_ms: 0,
+            app_version: "0.1.0".to_string(),
+            system: crate::system::build_placeholder_snapshot(),
+            tools,
+            docker: DockerSummary::default(),
+            env: env
+                .iter()
+                .map(|(name, value)| EnvVarEntry {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    redacted: false,
+                })
+                .collect(),
+            requirements: None,
+        }
+    }
+
+    #[test]
+    fn tools_and_env_differences_are_listed() {
+        let before = report(
+            vec![
+                tool("Node.js", "20.11.1", "/usr/bin/node"),
+                tool("Go", "1.22.0", "/usr/bin/go"),
+                tool("Java", "21.0.2", "/opt/jdk"),
+            ],
+            &[("JAVA_HOME", "/opt/jdk"), ("GOPROXY", "direct")],
+        );
+        let mut after = report(
+            vec![
+                tool("Node.js", "22.1.0", "/usr/bin/node"),
+                tool("Java", "17.0.9", "/opt/jdk17"),
+                tool("Rust", "1.78.0", "~/.cargo/bin/rustc"),
+            ],
+            &[("JAVA_HOME", "/opt/jdk17"), ("GOPROXY", "direct")],
+        );
+        after.system.os_version = "24.04".to_string();
+
+        let diff = diff_reports(&before, &after);
+        let summary: Vec<(&str, &str, Option<&str>)> = diff
+            .tools
+            .iter()
+            .map(|tool| (tool.name.as_str(), tool.change.as_str(), tool.version_direction.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Go", TOOL_REMOVED, None),
+                ("Java", TOOL_CHANGED, Some(VERSION_DOWNGRADE)),
+                ("Node.js", TOOL_CHANGED, Some(VERSION_UPGRADE)),
+                ("Rust", TOOL_ADDED, None),
+            ]
+        );
+        assert!(diff.tools[1].path_changed);
+        assert!(!diff.tools[2].path_changed);
+        assert_eq!(diff.env.len(), 1);
+        assert_eq!(diff.system[0].name, "osVersion");
+        assert!(diff.text.contains("~ Node.js 20.11.1 → 22.1.0（升级）"));
+        assert!(diff.text.contains("- Go 1.22.0"));
+
+        assert!(diff_reports(&before, &before).identical);
+    }
+
+    #[test]
+    fn reports_load_from_json_and_html() {
+        let original = report(vec![tool("Git", "2.44.0", "/usr/bin/git")], &[("PATH", "</script>")]);
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(load_environment_report(&json).unwrap().tools.len(), 1);
+
+        let html = crate::report::render::render_html(&original).unwrap();
+        let loaded = load_environment_report(&html).unwrap();
+        assert_eq!(loaded.env[0].value, "</script>");
+
+        let foreign = json.replace(ENVIRONMENT_REPORT_SCHEMA, "other");
+        assert!(load_environment_report(&foreign).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use crate::contracts::{EnvironmentReport, ToolStatus};
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,'Segoe UI','Microsoft YaHei',sans-serif;margin:24px;color:#1f2328}\
+h1{font-size:22px}h2{font-size:17px;margin-top:28px;border-bottom:1px solid #d0d7de;padding-bottom:4px}\
+table{border-collapse:collapse;width:100%;font-size:13px}th,td{border:1px solid #d0d7de;padding:4px 8px;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}code{font-family:Consolas,monospace;font-size:12px}.missing{color:#cf222e}.ok{color:#1a7f37}";
+
+fn format_time(ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms as i64)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+fn tool_state(tool: &ToolStatus) -> &'static str {
+    if tool.installed { "已安装" } else { "未安装" }
+}
+
+fn or_dash(value: Option<&str>) -> &str {
+    value.filter(|value| !value.is_empty()).unwrap_or("--")
+}
+
+/// 表格行：(标题, 值)
+fn system_rows(report: &EnvironmentReport) -> Vec<(&'static str, String)> {
+    let system = &report.system;
+    vec![
+        ("主机名", system.host_name.clone()),
+        ("操作系统", format!("{} {} ({})", system.os_name, system.os_version, system.build_number)),
+        ("架构", system.architecture.clone()),
+        ("CPU", format!("{}（{} 核 / {} 线程）", system.cpu_model, system.cpu_cores, system.cpu_logical_cores)),
+        (
+            "内存",
+            format!("{:.1} / {:.1} GB（{:.0}%）", system.used_memory_gb, system.total_memory_gb, system.memory_usage_percent),
+        ),
+        ("运行环境", or_dash(system.environment_kind.as_deref()).to_string()),
+    ]
+}
+
+fn docker_rows(report: &EnvironmentReport) -> Vec<(&'static str, String)> {
+    let docker = &report.docker;
+    let count = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_else(|| "--".to_string());
+    vec![
+        ("CLI", or_dash(docker.cli_version.as_deref()).to_string()),
+        ("守护进程版本", or_dash(docker.server_version.as_deref()).to_string()),
+        ("操作系统", or_dash(docker.operating_system.as_deref()).to_string()),
+        ("存储驱动", or_dash(docker.storage_driver.as_deref()).to_string()),
+        ("CPU / 内存", format!(
+            "{} / {}",
+            count(docker.cpus),
+            docker
+                .memory_bytes
+                .map(|bytes| format!("{:.1} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0))
+                .unwrap_or_else(|| "--".to_string())
+        )),
+        ("容器（运行中 / 全部）", format!("{} / {}", count(docker.containers_running), count(docker.containers))),
+        ("镜像", count(docker.images)),
+        ("错误", or_dash(docker.error.as_deref()).to_string()),
+    ]
+}
+
+/// PATH 类变量按分隔符拆成多行，便于阅读
+fn env_lines(value: &str) -> Vec<&str> {
+    let separator = if value.contains(';') { ';' } else { ':' };
+    if value.contains("://") && separator == ':' {
+        return vec![value];
+    }
+    value.split(separator).filter(|item| !item.is_empty()).collect()
+}
+
+fn is_path_list(name: &str) -> bool {
+    matches!(name, "PATH" | "Path" | "PATHEXT")
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+pub fn render_markdown(report: &EnvironmentReport) -> String {
+    let mut lines = vec![
+        "# DevEnvProbe 环境报告".to_string(),
+        String::new(),
+        format!("- 生成时间：{}", format_time(report.generated_at_ms)),
+        format!("- DevEnvProbe 版本：{}", report.app_version),
+        format!("- 报告格式：{} v{}", report.schema, report.schema_version),
+        String::new(),
+        "## 系统".to_string(),
+        String::new(),
+        "| 项目 | 值 |".to_string(),
+        "| --- | --- |".to_string(),
+    ];
+    for (label, value) in system_rows(report) {
+        lines.push(format!("| {} | {} |", label, markdown_cell(&value)));
+    }
+
+    lines.extend([
+        String::new(),
+        "## 工具".to_string(),
+        String::new(),
+        "| 工具 | 状态 | 版本 | 路径 |".to_string(),
+        "| --- | --- | --- | --- |".to_string(),
+    ]);
+    for tool in &report.tools {
+        lines.push(format!(
+            "| {} | {} | {} | {} |",
+            markdown_cell(&tool.name),
+            tool_state(tool),
+            markdown_cell(or_dash(tool.version.as_deref())),
+            markdown_cell(or_dash(tool.install_path.as_deref()))
+        ));
+    }
+
+    lines.extend([
+        String::new(),
+        "## Docker".to_string(),
+        String::new(),
+    ]);
+    if report.docker.cli_installed {
+        lines.extend(["| 项目 | 值 |".to_string(), "| --- | --- |".to_string()]);
+        for (label, value) in docker_rows(report) {
+            lines.push(format!("| {} | {} |", label, markdown_cell(&value)));
+        }
+    } else {
+        lines.push("未安装 Docker CLI".to_string());
+    }
+
+    lines.extend([String::new(), "## 环境变量".to_string(), String::new()]);
+    for entry in &report.env {
+        let suffix = if entry.redacted { "（已脱敏）" } else { "" };
+        if is_path_list(&entry.name) {
+            lines.push(format!("- `{}`{}：", entry.name, suffix));
+            for item in env_lines(&entry.value) {
+                lines.push(format!("  - `{}`", item));
+            }
+        } else {
+            lines.push(format!("- `{}`{}：`{}`", entry.name, suffix, entry.value));
+        }
+    }
+
+    if let Some(requirements) = &report.requirements {
+        lines.extend([
+            String::new(),
+            "## 项目要求".to_string(),
+            String::new(),
+            format!("项目：`{}`，结果：{}", requirements.project_path, if requirements.passed { "通过" } else { "未通过" }),
+            String::new(),
+            "| 工具 | 要求 | 已安装 | 结果 | 说明 |".to_string(),
+            "| --- | --- | --- | --- | --- |".to_string(),
+        ]);
+        for check in &requirements.checks {
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} |",
+                markdown_cell(&check.tool),
+                markdown_cell(&check.range),
+                markdown_cell(or_dash(check.installed_version.as_deref())),
+                check.status,
+                markdown_cell(or_dash(check.message.as_deref()))
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn html_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let head: String = headers.iter().map(|header| format!("<th>{}</th>", escape_html(header))).collect();
+    let body: String = rows
+        .into_iter()
+        .map(|row| {
+            let cells: String = row.iter().map(|cell| format!("<td>{}</td>", cell)).collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect();
+    format!("<table><thead><tr>{}</tr></thead><tbody>{}</tbody></table>", head, body)
+}
+
+/// 单文件 HTML：样式内联，并嵌入完整 JSON，便于之后重新导入对比
+pub fn render_html(report: &EnvironmentReport) -> Result<String, String> {
+    let json = serde_json::to_string(report).map_err(|error| format!("序列化报告失败: {}", error))?;
+    // 防止 JSON 中的 </script> 提前结束脚本块
+    let embedded = json.replace("</", "<\\/");
+
+    let label_rows = |rows: Vec<(&'static str, String)>| {
+        rows.into_iter()
+            .map(|(label, value)| vec![escape_html(label), escape_html(&value)])
+            .collect::<Vec<_>>()
+    };
+
+    let tool_rows = report
+        .tools
+        .iter()
+        .map(|tool| {
+            let class = if tool.installed { "ok" } else { "missing" };
+            vec![
+                escape_html(&tool.name),
+                format!("<span class=\"{}\">{}</span>", class, tool_state(tool)),
+                escape_html(or_dash(tool.version.as_deref())),
+                format!("<code>{}</code>", escape_html(or_dash(tool.install_path.as_deref()))),
+            ]
+        })
+        .collect();
+
+    let env_rows = report
+        .env
+        .iter()
+        .map(|entry| {
+            let value = if is_path_list(&entry.name) {
+                env_lines(&entry.value)
+                    .iter()
+                    .map(|item| format!("<code>{}</code>", escape_html(item)))
+                    .collect::<Vec<_>>()
+                    .join("<br>")
+            } else {
+                format!("<code>{}</code>", escape_html(&entry.value))
+            };
+            let name = if entry.redacted { format!("{}（已脱敏）", entry.name) } else { entry.name.clone() };
+            vec![escape_html(&name), value]
+        })
+        .collect();
+
+    let mut sections = vec![
+        format!(
+            "<h1>DevEnvProbe 环境报告</h1><p>生成时间：{} · 版本 {} · {} v{}</p>",
+            escape_html(&format_time(report.generated_at_ms)),
+            escape_html(&report.app_version),
+            escape_html(&report.schema),
+            report.schema_version
+        ),
+        format!("<h2>系统</h2>{}", html_table(&["项目", "值"], label_rows(system_rows(report)))),
+        format!("<h2>工具</h2>{}", html_table(&["工具", "状态", "版本", "路径"], tool_rows)),
+    ];
+
+    if report.docker.cli_installed {
+        sections.push(format!("<h2>Docker</h2>{}", html_table(&["项目", "值"], label_rows(docker_rows(report)))));
+    } else {
+        sections.push("<h2>Docker</h2><p>未安装 Docker CLI</p>".to_string());
+    }
+    sections.push(format!("<h2>环境变量</h2>{}", html_table(&["变量", "值"], env_rows)));
+
+    if let Some(requirements) = &report.requirements {
+        let rows = requirements
+            .checks
+            .iter()
+            .map(|check| {
+                vec![
+                    escape_html(&check.tool),
+                    escape_html(&check.range),
+                    escape_html(or_dash(check.installed_version.as_deref())),
+                    escape_html(&check.status),
+                    escape_html(or_dash(check.message.as_deref())),
+                ]
+            })
+            .collect();
+        sections.push(format!(
+            "<h2>项目要求</h2><p><code>{}</code></p>{}",
+            escape_html(&requirements.project_path),
+            html_table(&["工具", "要求", "已安装", "结果", "说明"], rows)
+        ));
+    }
+
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\"><head><meta charset=\"utf-8\"><title>DevEnvProbe 环境报告</title><style>{}</style></head>\n<body>\n{}\n<script type=\"application/json\" id=\"devenvprobe-report\">{}</script>\n</body></html>\n",
+        HTML_STYLE,
+        sections.join("\n"),
+        embedded
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::DockerSummary;
+    use crate::report::{ENVIRONMENT_REPORT_SCHEMA, ENVIRONMENT_REPORT_SCHEMA_VERSION};
+    use crate::system::build_placeholder_snapshot;
+
+    fn sample_report() -> EnvironmentReport {
+        EnvironmentReport {
+            schema: ENVIRONMENT_REPORT_SCHEMA.to_string(),
+            schema_version: ENVIRONMENT_REPORT_SCHEMA_VERSION,
+            generated_at_ms: 1_700_000_000_000,
+            app_version: "0.1.0".to_string(),
+            system: build_placeholder_snapshot(),
+            tools: vec![ToolStatus {
+                install_key: Some("nodejs-lts".to_string()),
+                install_path: Some("~/.nvm/versions/node/v20.11.1/bin/node".to_string()),
+                ..ToolStatus::for_test("Node.js <LTS>", "node", Some("v20.11.1"))
+            }],
+            docker: DockerSummary::default(),
+            env: vec![crate::contracts::EnvVarEntry {
+                name: "PATH".to_string(),
+                value: "/usr/local/bin:/usr/bin".to_string(),
+                redacted: false,
+            }],
+            requirements: None,
+        }
+    }
+
+    #[test]
+    fn markdown_lists_tools_and_splits_path() {
+        let markdown = render_markdown(&sample_report());
+        assert!(markdown.contains("| Node.js <LTS> | 已安装 | v20.11.1 | ~/.nvm/versions/node/v20.11.1/bin/node |"));
+        assert!(markdown.contains("  - `/usr/local/bin`\n  - `/usr/bin`"));
+        assert!(markdown.contains("未安装 Docker CLI"));
+    }
+
+    #[test]
+    fn html_escapes_content_and_embeds_json() {
+        let html = render_html(&sample_report()).unwrap();
+        assert!(html.contains("Node.js &lt;LTS&gt;"));
+        assert!(!html.contains("<link") && !html.contains("src=\"http"));
+
+        let start = html.find("id=\"devenvprobe-report\">").unwrap() + "id=\"devenvprobe-report\">".len();
+        let end = html[start..].find("</script>").unwrap() + start;
+        let embedded: EnvironmentReport = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(embedded.schema_version, ENVIRONMENT_REPORT_SCHEMA_VERSION);
+        assert_eq!(embedded.tools[0].name, "Node.js <LTS>");
+    }
+}
@@ -1,8 +1,10 @@
+use serde::Serialize;
+use std::future::Future;
 use std::sync::{Arc, RwLock};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::contracts::{SystemSnapshot, SystemRealtimeSnapshot};
+use crate::contracts::{CommandResponse, SystemSnapshot, SystemRealtimeSnapshot};
 
 #[derive(Debug, Default)]
 pub struct RuntimeSampleCache {
@@ -37,6 +39,7 @@ impl AppRuntimeState {
             total_memory_gb: snapshot.total_memory_gb,
             used_memory_gb: snapshot.used_memory_gb,
             memory_usage_percent: snapshot.memory_usage_percent,
+            thermal_throttling: snapshot.sensors.as_ref().map(|sensors| sensors.thermal_throttling),
             sample_mode: snapshot.sample_mode.clone(),
             sampled_at_ms: snapshot.sampled_at_ms,
             is_stale: snapshot.is_stale,
@@ -66,6 +69,9 @@ impl AppRuntimeState {
                 snapshot.used_memory_gb = realtime.used_memory_gb;
                 snapshot.memory_usage_percent = realtime.memory_usage_percent;
                 snapshot.uptime_seconds = realtime.uptime_seconds;
+                if let (Some(sensors), Some(throttling)) = (snapshot.sensors.as_mut(), realtime.thermal_throttling) {
+                    sensors.thermal_throttling = throttling;
+                }
                 snapshot.sample_mode = realtime.sample_mode.clone();
                 snapshot.sampled_at_ms = realtime.sampled_at_ms;
                 snapshot.is_stale = realtime.is_stale;
@@ -76,6 +82,40 @@ impl AppRuntimeState {
     }
 }
 
+/// 在阻塞线程池中执行同步操作，供 Tauri 命令与本地 API 共用
+pub async fn run_blocking<T, F>(operation: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(operation)
+        .await
+        .map_err(|error| format!("后台任务执行失败: {}", error))?
+}
+
+/// 执行操作并包装为前端统一的 CommandResponse，附带耗时
+pub async fn with_timing_async<T, Fut>(operation: Fut) -> CommandResponse<T>
+where
+    T: Serialize,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let start = Instant::now();
+    match operation.await {
+        Ok(data) => CommandResponse {
+            ok: true,
+            data: Some(data),
+            error: None,
+            elapsed_ms: start.elapsed().as_millis(),
+        },
+        Err(error) => CommandResponse {
+            ok: false,
+            data: None,
+            error: Some(error),
+            elapsed_ms: start.elapsed().as_millis(),
+        },
+    }
+}
+
 pub fn current_timestamp_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
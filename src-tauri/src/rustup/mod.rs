@@ -0,0 +1,185 @@
+pub mod parsers;
+
+use std::path::Path;
+
+use crate::contracts::{RustActiveToolchain, RustupActionResult, RustupOutputLine, RustupStatus};
+use crate::process_runner::{execute_process_streaming, execute_process_with_timeout_in_dir};
+use crate::tools::detect::first_line;
+use crate::tools::managers::validate_argument;
+
+use parsers::*;
+
+pub const RUSTUP_LIST_TIMEOUT_MS: u64 = 10_000;
+/// 安装工具链需要下载数百 MB
+pub const RUSTUP_ACTION_TIMEOUT_MS: u64 = 30 * 60 * 1_000;
+/// 操作执行期间逐行推送输出
+pub const RUSTUP_OUTPUT_EVENT: &str = "rustup-output";
+
+pub const RUSTUP_KIND_TOOLCHAIN: &str = "toolchain";
+pub const RUSTUP_KIND_TARGET: &str = "target";
+pub const RUSTUP_KIND_COMPONENT: &str = "component";
+
+pub const RUSTUP_ACTION_INSTALL: &str = "install";
+pub const RUSTUP_ACTION_REMOVE: &str = "remove";
+
+fn run_rustup(args: &[&str], current_dir: Option<&Path>) -> Result<String, String> {
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    let output = execute_process_with_timeout_in_dir("rustup", &args, RUSTUP_LIST_TIMEOUT_MS, current_dir)?;
+    if output.exit_code != 0 {
+        return Err(first_line(&output.stderr).unwrap_or_else(|| format!("返回码 {}", output.exit_code)));
+    }
+    Ok(output.stdout)
+}
+
+/// 列出工具链、覆盖设置，以及所选工具链（默认为当前默认工具链）的目标和组件；
+/// project_path 用于查询该目录实际生效的工具链
+pub fn get_rustup_status_internal(toolchain: Option<&str>, project_path: Option<&str>) -> Result<RustupStatus, String> {
+    let toolchains = parse_toolchain_list(&run_rustup(&["toolchain", "list"], None)?);
+    let toolchain = match toolchain.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => {
+            validate_argument(name, "工具链")?;
+            Some(name.to_string())
+        }
+        None => toolchains
+            .iter()
+            .find(|toolchain| toolchain.is_default)
+            .or_else(|| toolchains.first())
+            .map(|toolchain| toolchain.name.clone()),
+    };
+
+    let mut status = RustupStatus {
+        toolchains,
+        toolchain,
+        targets: Vec::new(),
+        targets_error: None,
+        components: Vec::new(),
+        components_error: None,
+        overrides: Vec::new(),
+        overrides_error: None,
+        active: active_toolchain(project_path)?,
+    };
+
+    // 指定的工具链未安装时 target / component list 会失败，只让对应部分降级
+    if let Some(name) = status.toolchain.clone() {
+        match run_rustup(&["target", "list", "--toolchain", &name], None) {
+            Ok(output) => status.targets = parse_target_list(&output),
+            Err(error) => status.targets_error = Some(error),
+        }
+        match run_rustup(&["component", "list", "--toolchain", &name], None) {
+            Ok(output) => status.components = parse_component_list(&output, &status.targets),
+            Err(error) => status.components_error = Some(error),
+        }
+    }
+    match run_rustup(&["override", "list"], None) {
+        Ok(output) => status.overrides = parse_override_list(&output),
+        Err(error) => status.overrides_error = Some(error),
+    }
+
+    Ok(status)
+}
+
+/// 未设置默认工具链时 rustup 返回非 0，视为没有生效的工具链
+fn active_toolchain(project_path: Option<&str>) -> Result<Option<RustActiveToolchain>, String> {
+    let directory = project_path.map(str::trim).filter(|path| !path.is_empty());
+    if let Some(path) = directory {
+        if !Path::new(path).is_dir() {
+            return Err(format!("项目目录不存在：{}", path));
+        }
+    }
+
+    let output = execute_process_with_timeout_in_dir(
+        "rustup",
+        &["show".to_string(), "active-toolchain".to_string()],
+        RUSTUP_LIST_TIMEOUT_MS,
+        directory.map(Path::new),
+    )?;
+    if output.exit_code != 0 {
+        return Ok(None);
+    }
+    Ok(parse_active_toolchain(&output.stdout, directory))
+}
+
+/// 组装 rustup 参数；目标与组件可指定工具链，否则作用于当前默认工具链
+pub fn rustup_action_args(kind: &str, action: &str, name: &str, toolchain: Option<&str>) -> Result<Vec<String>, String> {
+    validate_argument(name, "名称")?;
+    if let Some(toolchain) = toolchain {
+        validate_argument(toolchain, "工具链")?;
+    }
+
+    let verb = match (kind, action) {
+        (RUSTUP_KIND_TOOLCHAIN, RUSTUP_ACTION_INSTALL) => "install",
+        (RUSTUP_KIND_TOOLCHAIN, RUSTUP_ACTION_REMOVE) => "uninstall",
+        (RUSTUP_KIND_TARGET | RUSTUP_KIND_COMPONENT, RUSTUP_ACTION_INSTALL) => "add",
+        (RUSTUP_KIND_TARGET | RUSTUP_KIND_COMPONENT, RUSTUP_ACTION_REMOVE) => "remove",
+        (RUSTUP_KIND_TOOLCHAIN | RUSTUP_KIND_TARGET | RUSTUP_KIND_COMPONENT, _) => {
+            return Err(format!("未知的操作：{}", action))
+        }
+        _ => return Err(format!("未知的 rustup 类型：{}", kind)),
+    };
+
+    let mut args = vec![kind.to_string(), verb.to_string(), name.to_string()];
+    if let Some(toolchain) = toolchain.filter(|_| kind != RUSTUP_KIND_TOOLCHAIN) {
+        args.push("--toolchain".to_string());
+        args.push(toolchain.to_string());
+    }
+    Ok(args)
+}
+
+/// 安装或移除工具链、目标、组件，输出逐行交给 on_line
+pub fn run_rustup_action_internal(
+    kind: &str,
+    action: &str,
+    name: &str,
+    toolchain: Option<&str>,
+    mut on_line: impl FnMut(RustupOutputLine),
+) -> Result<RustupActionResult, String> {
+    let name = name.trim();
+    let toolchain = toolchain.map(str::trim).filter(|toolchain| !toolchain.is_empty());
+    let args = rustup_action_args(kind, action, name, toolchain)?;
+
+    let output = execute_process_streaming("rustup", &args, RUSTUP_ACTION_TIMEOUT_MS, |stream, line| {
+        on_line(RustupOutputLine {
+            kind: kind.to_string(),
+            action: action.to_string(),
+            name: name.to_string(),
+            stream: stream.to_string(),
+            line: line.to_string(),
+        })
+    })?;
+
+    Ok(RustupActionResult {
+        kind: kind.to_string(),
+        action: action.to_string(),
+        name: name.to_string(),
+        toolchain: toolchain.map(ToString::to_string),
+        command: format!("rustup {}", args.join(" ")),
+        stdout: output.stdout,
+        stderr: output.stderr,
+        exit_code: output.exit_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_args_follow_rustup_subcommands() {
+        assert_eq!(
+            rustup_action_args(RUSTUP_KIND_TOOLCHAIN, RUSTUP_ACTION_REMOVE, "nightly", Some("stable")).unwrap(),
+            vec!["toolchain", "uninstall", "nightly"]
+        );
+        assert_eq!(
+            rustup_action_args(RUSTUP_KIND_TARGET, RUSTUP_ACTION_INSTALL, "wasm32-unknown-unknown", Some("nightly"))
+                .unwrap(),
+            vec!["target", "add", "wasm32-unknown-unknown", "--toolchain", "nightly"]
+        );
+        assert_eq!(
+            rustup_action_args(RUSTUP_KIND_COMPONENT, RUSTUP_ACTION_REMOVE, "rust-analyzer", None).unwrap(),
+            vec!["component", "remove", "rust-analyzer"]
+        );
+        assert!(rustup_action_args(RUSTUP_KIND_COMPONENT, "upgrade", "clippy", None).is_err());
+        assert!(rustup_action_args("profile", RUSTUP_ACTION_INSTALL, "minimal", None).is_err());
+        assert!(rustup_action_args(RUSTUP_KIND_TARGET, RUSTUP_ACTION_INSTALL, "--force", None).is_err());
+    }
+}
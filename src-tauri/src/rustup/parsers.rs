@@ -0,0 +1,168 @@
+use crate::contracts::{RustActiveToolchain, RustComponent, RustOverride, RustTarget, RustToolchain};
+
+/// 行尾括号中的标记，如 `(active, default)`、`(installed)`
+fn split_flags(line: &str) -> (&str, Vec<&str>) {
+    let line = line.trim();
+    match line.split_once(" (") {
+        Some((name, rest)) => (
+            name.trim(),
+            rest.trim_end_matches(')').split(',').map(str::trim).collect(),
+        ),
+        None => (line, Vec::new()),
+    }
+}
+
+/// `rustup toolchain list`：`stable-x86_64-pc-windows-msvc (active, default)`；旧版本只标 `(default)`
+pub fn parse_toolchain_list(stdout: &str) -> Vec<RustToolchain> {
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("no installed toolchains"))
+        .map(|line| {
+            let (name, flags) = split_flags(line);
+            RustToolchain {
+                name: name.to_string(),
+                is_default: flags.contains(&"default"),
+                is_active: flags.contains(&"active"),
+            }
+        })
+        .collect()
+}
+
+/// `rustup target list`：已安装的目标带 `(installed)`
+pub fn parse_target_list(stdout: &str) -> Vec<RustTarget> {
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, flags) = split_flags(line);
+            RustTarget {
+                name: name.to_string(),
+                installed: flags.contains(&"installed"),
+            }
+        })
+        .collect()
+}
+
+/// `rustup component list`：名称带目标三元组后缀，按已知目标拆开；
+/// 未安装的 rust-std 与目标一一对应，已在目标列表中体现，这里不再列出
+pub fn parse_component_list(stdout: &str, targets: &[RustTarget]) -> Vec<RustComponent> {
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let (full_name, flags) = split_flags(line);
+            let installed = flags.contains(&"installed");
+            let (name, target) = targets
+                .iter()
+                .find_map(|target| {
+                    let name = full_name.strip_suffix(target.name.as_str())?.strip_suffix('-')?;
+                    Some((name, Some(target.name.clone())))
+                })
+                .unwrap_or((full_name, None));
+            if name == "rust-std" && !installed {
+                return None;
+            }
+            Some(RustComponent {
+                name: name.to_string(),
+                target,
+                installed,
+            })
+        })
+        .collect()
+}
+
+/// `rustup override list`：`<目录>  <工具链>`，目录中可能有空格
+pub fn parse_override_list(stdout: &str) -> Vec<RustOverride> {
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty() && line.trim() != "no overrides")
+        .filter_map(|line| {
+            let (path, toolchain) = line.trim().rsplit_once(char::is_whitespace)?;
+            Some(RustOverride {
+                path: path.trim().to_string(),
+                toolchain: toolchain.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `rustup show active-toolchain`：`nightly-... (directory override for '/p')`、`stable-... (default)`
+pub fn parse_active_toolchain(stdout: &str, directory: Option<&str>) -> Option<RustActiveToolchain> {
+    let line = stdout.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let (toolchain, reason) = match line.split_once(" (") {
+        Some((toolchain, reason)) => (toolchain, Some(reason.trim_end_matches(')').to_string())),
+        None => (line, None),
+    };
+    Some(RustActiveToolchain {
+        toolchain: toolchain.trim().to_string(),
+        reason,
+        directory: directory.map(ToString::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolchains_and_overrides_are_parsed() {
+        let toolchains = parse_toolchain_list(
+            "stable-x86_64-unknown-linux-gnu (active, default)\nnightly-x86_64-unknown-linux-gnu\n1.75.0-x86_64-unknown-linux-gnu (override)\n",
+        );
+        let summary: Vec<(&str, bool, bool)> = toolchains
+            .iter()
+            .map(|toolchain| (toolchain.name.as_str(), toolchain.is_default, toolchain.is_active))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("stable-x86_64-unknown-linux-gnu", true, true),
+                ("nightly-x86_64-unknown-linux-gnu", false, false),
+                ("1.75.0-x86_64-unknown-linux-gnu", false, false),
+            ]
+        );
+        assert!(parse_toolchain_list("no installed toolchains\n").is_empty());
+
+        let overrides = parse_override_list("/home/dev/my project                nightly-x86_64-unknown-linux-gnu\n");
+        assert_eq!(overrides[0].path, "/home/dev/my project");
+        assert_eq!(overrides[0].toolchain, "nightly-x86_64-unknown-linux-gnu");
+        assert!(parse_override_list("no overrides\n").is_empty());
+
+        let active = parse_active_toolchain(
+            "nightly-x86_64-unknown-linux-gnu (directory override for '/home/dev/app')\n",
+            Some("/home/dev/app"),
+        )
+        .unwrap();
+        assert_eq!(active.toolchain, "nightly-x86_64-unknown-linux-gnu");
+        assert_eq!(active.reason.as_deref(), Some("directory override for '/home/dev/app'"));
+    }
+
+    #[test]
+    fn components_are_split_from_target_suffix() {
+        let targets = parse_target_list(
+            "wasm32-unknown-unknown (installed)\nx86_64-pc-windows-gnu\nx86_64-unknown-linux-gnu (installed)\n",
+        );
+        assert_eq!(targets.iter().filter(|target| target.installed).count(), 2);
+
+        let components = parse_component_list(
+            "cargo-x86_64-unknown-linux-gnu (installed)\nclippy-x86_64-unknown-linux-gnu (installed)\n\
+             rust-analyzer-x86_64-unknown-linux-gnu\nrust-src (installed)\nrust-std-wasm32-unknown-unknown (installed)\n\
+             rust-std-x86_64-pc-windows-gnu\n",
+            &targets,
+        );
+        let summary: Vec<(&str, Option<&str>, bool)> = components
+            .iter()
+            .map(|component| (component.name.as_str(), component.target.as_deref(), component.installed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("cargo", Some("x86_64-unknown-linux-gnu"), true),
+                ("clippy", Some("x86_64-unknown-linux-gnu"), true),
+                ("rust-analyzer", Some("x86_64-unknown-linux-gnu"), false),
+                ("rust-src", None, true),
+                ("rust-std", Some("wasm32-unknown-unknown"), true),
+            ]
+        );
+    }
+}
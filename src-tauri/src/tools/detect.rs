@@ -1,18 +1,51 @@
 use crate::contracts::ToolStatus;
 use crate::process_runner::{self, execute_process_with_timeout, run_command_with_timeout};
+use regex::Regex;
 use std::path::Path;
 
+use super::fingerprint::{read_fingerprint, FingerprintMatch};
+use super::resolver::resolve_executable;
 use super::specs::ToolSpec;
-use super::{TOOL_DETECT_TIMEOUT_MS, AI_TOOL_DETECT_TIMEOUT_MS};
+use super::version_rules::parse_tool_version;
+use super::{AI_TOOL_DETECT_TIMEOUT_MS, SLOW_START_COMMANDS, SLOW_TOOL_DETECT_TIMEOUT_MS, TOOL_DETECT_TIMEOUT_MS};
 
-pub fn detect_tool(spec: &ToolSpec) -> ToolStatus {
-    let args: Vec<String> = spec.args.iter().map(|arg| (*arg).to_string()).collect();
-    let timeout = if spec.category == "AI" {
+/// 返回 (单次超时, 重试次数)：目录中的设置优先，否则按分类与命令取默认值
+pub fn detect_policy(spec: &ToolSpec) -> (u64, u32) {
+    let slow_start = SLOW_START_COMMANDS.contains(&spec.command.as_str());
+    let timeout = spec.timeout_ms.unwrap_or(if spec.category == "AI" {
         AI_TOOL_DETECT_TIMEOUT_MS
+    } else if slow_start {
+        SLOW_TOOL_DETECT_TIMEOUT_MS
     } else {
         TOOL_DETECT_TIMEOUT_MS
-    };
-    let result = execute_process_with_timeout(spec.command, &args, timeout);
+    });
+    (timeout, spec.retries.unwrap_or(u32::from(slow_start)))
+}
+
+pub fn detect_tool(spec: &ToolSpec) -> ToolStatus {
+    if let Some(fingerprint) = read_fingerprint(spec) {
+        if let Some(status) = status_from_fingerprint(spec, fingerprint) {
+            return status;
+        }
+    }
+    let (timeout, retries) = detect_policy(spec);
+    detect_tool_with_budget(spec, timeout, retries)
+}
+
+/// 超时且没有任何输出时重试；第一次往往在预热运行时或磁盘缓存，重试通常更快
+pub fn detect_tool_with_budget(spec: &ToolSpec, timeout: u64, retries: u32) -> ToolStatus {
+    let mut result = execute_process_with_timeout(&spec.command, &spec.args, timeout);
+    for _ in 0..retries {
+        match &result {
+            Ok(output) if output.exit_code == process_runner::TIMEOUT_EXIT_CODE
+                && first_line(&output.stdout).is_none()
+                && first_line(&output.stderr).is_none() =>
+            {
+                result = execute_process_with_timeout(&spec.command, &spec.args, timeout);
+            }
+            _ => break,
+        }
+    }
 
     match result {
         Ok(output) => {
@@ -24,11 +57,12 @@ pub fn detect_tool(spec: &ToolSpec) -> ToolStatus {
                 stderr.clone()
             };
 
-            let installed = output.exit_code == 0
-                || (output.exit_code == process_runner::TIMEOUT_EXIT_CODE
-                    && first_line(&raw).is_some());
+            let timed_out = output.exit_code == process_runner::TIMEOUT_EXIT_CODE;
+            let installed = output.exit_code == 0 || (timed_out && first_line(&raw).is_some());
             let details = if installed {
                 None
+            } else if timed_out {
+                Some(format!("探测超时（{} ms 内无输出），可能仍在冷启动，稍后将自动重新探测", timeout))
             } else {
                 let is_command_not_found = is_missing_command_detail(&stderr)
                     || stderr.contains("不是内部或外部命令")
@@ -46,35 +80,54 @@ pub fn detect_tool(spec: &ToolSpec) -> ToolStatus {
             };
 
             ToolStatus {
-                name: spec.name.to_string(),
-                command: spec.command.to_string(),
-                category: spec.category.to_string(),
+                name: spec.name.clone(),
+                command: spec.command.clone(),
+                category: spec.category.clone(),
                 installed,
-                version: if installed { first_line(&raw) } else { None },
+                version: if installed { extract_version(spec, &raw) } else { None },
                 details,
-                install_key: spec.install_key.map(ToString::to_string),
-                install_path: if installed { resolve_tool_path(spec.command) } else { None },
+                install_key: spec.install_key.clone(),
+                install_path: if installed { resolve_tool_path(&spec.command) } else { None },
+                parsed_version: if installed { parse_tool_version(spec, &raw) } else { None },
+                version_manager: None,
+                lifecycle: None,
+                timed_out,
             }
         }
         Err(error) => detect_tool_with_fallback(spec, error),
     }
 }
 
+/// 指纹输出按命令输出的规则解析，解析不出版本号时视为未命中
+fn status_from_fingerprint(spec: &ToolSpec, fingerprint: FingerprintMatch) -> Option<ToolStatus> {
+    let parsed_version = parse_tool_version(spec, &fingerprint.output)?;
+    Some(ToolStatus {
+        name: spec.name.clone(),
+        command: spec.command.clone(),
+        category: spec.category.clone(),
+        installed: true,
+        version: extract_version(spec, &fingerprint.output),
+        details: Some(format!("版本读取自 {}", fingerprint.file.display())),
+        install_key: spec.install_key.clone(),
+        install_path: Some(fingerprint.executable.to_string_lossy().to_string()),
+        parsed_version: Some(parsed_version),
+        version_manager: None,
+        lifecycle: None,
+        timed_out: false,
+    })
+}
+
+/// 返回 PATH 中命令跟随符号链接后的实际文件路径
 pub fn resolve_tool_path(command: &str) -> Option<String> {
-    let args = vec![command.to_string()];
-    let result = execute_process_with_timeout("where", &args, TOOL_DETECT_TIMEOUT_MS).ok()?;
-    if result.exit_code != 0 {
-        return None;
-    }
-    first_line(&result.stdout)
+    resolve_executable(command).map(|resolved| resolved.target.to_string_lossy().to_string())
 }
 
 fn detect_tool_with_fallback(spec: &ToolSpec, detect_error: String) -> ToolStatus {
-    let install_key = spec.install_key.unwrap_or_default();
+    let install_key = spec.install_key.as_deref().unwrap_or_default();
 
     if install_key == "cmake" {
-        if let Some(path) = detect_windows_executable_path(
-            "cmake.exe",
+        if let Some(path) = detect_executable_path(
+            "cmake",
             &[
                 r"CMake\bin\cmake.exe",
                 r"Microsoft Visual Studio\2022\Community\Common7\IDE\CommonExtensions\Microsoft\CMake\CMake\bin\cmake.exe",
@@ -83,18 +136,23 @@ fn detect_tool_with_fallback(spec: &ToolSpec, detect_error: String) -> ToolStatu
                 r"Microsoft Visual Studio\2022\BuildTools\Common7\IDE\CommonExtensions\Microsoft\CMake\CMake\bin\cmake.exe",
             ],
         ) {
-            let version = detect_tool_version_from_path(&path, spec.args)
-                .or_else(|| Some("通过路径检测到已安装".to_string()));
+            let version = detect_tool_version_from_path(&path, &spec.args);
+            let parsed_version = version.as_deref().and_then(|value| parse_tool_version(spec, value));
+            let version = version.or_else(|| Some("通过路径检测到已安装".to_string()));
 
             return ToolStatus {
-                name: spec.name.to_string(),
-                command: spec.command.to_string(),
-                category: spec.category.to_string(),
+                name: spec.name.clone(),
+                command: spec.command.clone(),
+                category: spec.category.clone(),
                 installed: true,
                 version,
                 details: Some(format!("检测路径：{}", path)),
-                install_key: spec.install_key.map(ToString::to_string),
+                install_key: spec.install_key.clone(),
                 install_path: Some(path),
+                parsed_version,
+                version_manager: None,
+                lifecycle: None,
+                timed_out: false,
             };
         }
     }
@@ -102,14 +160,18 @@ fn detect_tool_with_fallback(spec: &ToolSpec, detect_error: String) -> ToolStatu
     if install_key == "mysql" {
         if let Some(service) = detect_windows_service_by_pattern("*mysql*") {
             return ToolStatus {
-                name: spec.name.to_string(),
-                command: spec.command.to_string(),
-                category: spec.category.to_string(),
+                name: spec.name.clone(),
+                command: spec.command.clone(),
+                category: spec.category.clone(),
                 installed: true,
                 version: Some("通过服务检测到已安装".to_string()),
                 details: Some(format!("检测到服务：{}", service)),
-                install_key: spec.install_key.map(ToString::to_string),
-                install_path: resolve_tool_path(spec.command),
+                install_key: spec.install_key.clone(),
+                install_path: resolve_tool_path(&spec.command),
+                parsed_version: None,
+                version_manager: None,
+                lifecycle: None,
+                timed_out: false,
             };
         }
     }
@@ -117,14 +179,18 @@ fn detect_tool_with_fallback(spec: &ToolSpec, detect_error: String) -> ToolStatu
     if install_key == "postgresql" {
         if let Some(service) = detect_windows_service_by_pattern("*postgres*") {
             return ToolStatus {
-                name: spec.name.to_string(),
-                command: spec.command.to_string(),
-                category: spec.category.to_string(),
+                name: spec.name.clone(),
+                command: spec.command.clone(),
+                category: spec.category.clone(),
                 installed: true,
                 version: Some("通过服务检测到已安装".to_string()),
                 details: Some(format!("检测到服务：{}", service)),
-                install_key: spec.install_key.map(ToString::to_string),
-                install_path: resolve_tool_path(spec.command),
+                install_key: spec.install_key.clone(),
+                install_path: resolve_tool_path(&spec.command),
+                parsed_version: None,
+                version_manager: None,
+                lifecycle: None,
+                timed_out: false,
             };
         }
     }
@@ -142,14 +208,18 @@ fn detect_tool_with_fallback(spec: &ToolSpec, detect_error: String) -> ToolStatu
     };
 
     ToolStatus {
-        name: spec.name.to_string(),
-        command: spec.command.to_string(),
-        category: spec.category.to_string(),
+        name: spec.name.clone(),
+        command: spec.command.clone(),
+        category: spec.category.clone(),
         installed: false,
         version: None,
         details,
-        install_key: spec.install_key.map(ToString::to_string),
+        install_key: spec.install_key.clone(),
         install_path: None,
+        parsed_version: None,
+        version_manager: None,
+        lifecycle: None,
+        timed_out: false,
     }
 }
 
@@ -176,15 +246,9 @@ fn detect_windows_service_by_pattern(pattern: &str) -> Option<String> {
     }
 }
 
-fn detect_windows_executable_path(executable: &str, fallback_sub_paths: &[&str]) -> Option<String> {
-    if !cfg!(target_os = "windows") {
-        return None;
-    }
-
-    if let Ok(where_output) = run_command_with_timeout("where", &[executable], TOOL_DETECT_TIMEOUT_MS) {
-        if let Some(path) = first_line(&where_output) {
-            return Some(path);
-        }
+fn detect_executable_path(executable: &str, fallback_sub_paths: &[&str]) -> Option<String> {
+    if let Some(path) = resolve_tool_path(executable) {
+        return Some(path);
     }
 
     for root_key in ["ProgramFiles", "ProgramFiles(x86)", "LocalAppData"] {
@@ -201,14 +265,33 @@ fn detect_windows_executable_path(executable: &str, fallback_sub_paths: &[&str])
     None
 }
 
-fn detect_tool_version_from_path(path: &str, args: &[&str]) -> Option<String> {
-    let normalized_args: Vec<String> = args.iter().map(|item| (*item).to_string()).collect();
-    let output = execute_process_with_timeout(path, &normalized_args, TOOL_DETECT_TIMEOUT_MS).ok()?;
+fn detect_tool_version_from_path(path: &str, args: &[String]) -> Option<String> {
+    let output = execute_process_with_timeout(path, args, TOOL_DETECT_TIMEOUT_MS).ok()?;
     let raw = if output.stdout.is_empty() { output.stderr } else { output.stdout };
     first_line(&raw)
 }
 
-fn first_line(raw: &str) -> Option<String> {
+/// 有版本正则时取匹配内容（优先第一个捕获组），未匹配则回退到首行输出
+pub fn extract_version(spec: &ToolSpec, raw: &str) -> Option<String> {
+    let matched = spec
+        .version_regex
+        .as_deref()
+        .and_then(|pattern| Regex::new(pattern).ok())
+        .and_then(|regex| {
+            regex.captures(raw).map(|captures| {
+                captures
+                    .get(1)
+                    .or_else(|| captures.get(0))
+                    .map(|value| value.as_str().trim().to_string())
+                    .unwrap_or_default()
+            })
+        })
+        .filter(|value| !value.is_empty());
+
+    matched.or_else(|| first_line(raw))
+}
+
+pub fn first_line(raw: &str) -> Option<String> {
     raw.lines()
         .map(str::trim)
         .find(|line| !line.is_empty())
@@ -224,3 +307,23 @@ fn is_missing_command_detail(detail: &str) -> bool {
         || lowered.contains("command not found")
         || lowered.contains("no such file or directory")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_prefers_catalog_settings_over_defaults() {
+        let node = ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", None);
+        assert_eq!(detect_policy(&node), (TOOL_DETECT_TIMEOUT_MS, 0));
+
+        let gradle = ToolSpec::builtin("Gradle", "gradle", &["-v"], "Build", None);
+        assert_eq!(detect_policy(&gradle), (SLOW_TOOL_DETECT_TIMEOUT_MS, 1));
+
+        let mut claude = ToolSpec::builtin("Claude Code", "claude", &["--version"], "AI", None);
+        assert_eq!(detect_policy(&claude), (AI_TOOL_DETECT_TIMEOUT_MS, 0));
+        claude.timeout_ms = Some(8_000);
+        claude.retries = Some(2);
+        assert_eq!(detect_policy(&claude), (8_000, 2));
+    }
+}
@@ -0,0 +1,359 @@
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde_json::Value;
+
+use super::resolver::resolve_executable;
+use super::specs::ToolSpec;
+
+/// 不启动进程、直接从安装目录中的文件读取版本的方式
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionFingerprint {
+    /// JDK 根目录的 `release` 文件（JAVA_VERSION="21.0.2"）
+    JdkRelease,
+    /// Go 根目录的 `VERSION` 文件
+    GoVersionFile,
+    /// `include/node/node_version.h` 中的版本宏
+    NodeVersionHeader,
+    /// 通过 npm 全局安装的包，读取包目录中的 package.json
+    NodePackage(&'static str),
+    /// venv 的 pyvenv.cfg、`patchlevel.h`，最后退回 Windows 上的 python3XX.dll 文件名
+    Python,
+    /// `sdk/*` 目录中最高的 SDK 版本
+    DotnetSdk,
+    /// google-cloud-sdk 根目录的 `VERSION` 文件
+    GcloudVersionFile,
+    /// Azure CLI 自带 Python 环境中的 `azure_cli-*.dist-info`
+    AzureCliDistInfo,
+}
+
+/// 命中的指纹：output 模拟版本命令的输出，交给原有的版本提取规则处理
+#[derive(Debug, Clone, PartialEq)]
+pub struct FingerprintMatch {
+    pub output: String,
+    pub file: PathBuf,
+    pub executable: PathBuf,
+}
+
+pub fn read_fingerprint(spec: &ToolSpec) -> Option<FingerprintMatch> {
+    let fingerprint = spec.fingerprint.as_ref()?;
+    let resolved = resolve_executable(&spec.command)?;
+    let (output, file) = fingerprint_from(fingerprint, &spec.command, &resolved.path, &resolved.target)?;
+    Some(FingerprintMatch {
+        output,
+        file,
+        executable: resolved.target,
+    })
+}
+
+/// path 为 PATH 中找到的原始路径，executable 为跟随符号链接后的实际文件
+pub fn fingerprint_from(
+    fingerprint: &VersionFingerprint,
+    command: &str,
+    path: &Path,
+    executable: &Path,
+) -> Option<(String, PathBuf)> {
+    let bin_dir = executable.parent()?;
+    // Windows 上 python.exe、dotnet.exe 等直接位于安装根目录，其余平台位于 bin 下
+    let roots: Vec<&Path> = [Some(bin_dir), bin_dir.parent()].into_iter().flatten().collect();
+
+    match fingerprint {
+        VersionFingerprint::JdkRelease => {
+            let (version, file) = first_capture(&roots, &["release"], r#"(?m)^JAVA_VERSION="([^"]+)""#)?;
+            let output = if command == "javac" {
+                format!("javac {}", version)
+            } else {
+                format!("openjdk version \"{}\"", version)
+            };
+            Some((output, file))
+        }
+        VersionFingerprint::GoVersionFile => {
+            let (version, file) = first_capture(&roots, &["VERSION"], r"^(go\d+(?:\.\d+)+)")?;
+            Some((format!("go version {}", version), file))
+        }
+        VersionFingerprint::GcloudVersionFile => {
+            let (version, file) = first_capture(&roots, &["VERSION"], r"^(\d+(?:\.\d+)+)\s*$")?;
+            Some((format!("Google Cloud SDK {}", version), file))
+        }
+        VersionFingerprint::NodeVersionHeader => node_header(&roots),
+        VersionFingerprint::NodePackage(package) => node_package(executable, &roots, package),
+        VersionFingerprint::Python => python_version(path, executable, &roots),
+        VersionFingerprint::DotnetSdk => dotnet_sdk(&roots),
+        VersionFingerprint::AzureCliDistInfo => azure_cli(&roots),
+    }
+}
+
+fn read_text(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+fn capture(content: &str, pattern: &str) -> Option<String> {
+    let regex = Regex::new(pattern).ok()?;
+    regex.captures(content)?.get(1).map(|value| value.as_str().trim().to_string())
+}
+
+fn first_capture(roots: &[&Path], relative: &[&str], pattern: &str) -> Option<(String, PathBuf)> {
+    roots.iter().find_map(|root| {
+        let file = relative.iter().fold(root.to_path_buf(), |path, part| path.join(part));
+        let version = capture(&read_text(&file)?, pattern)?;
+        Some((version, file))
+    })
+}
+
+fn child_names(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn node_header(roots: &[&Path]) -> Option<(String, PathBuf)> {
+    roots.iter().find_map(|root| {
+        let file = root.join("include").join("node").join("node_version.h");
+        let content = read_text(&file)?;
+        let part = |name: &str| capture(&content, &format!(r"(?m)^#define NODE_{}_VERSION (\d+)", name));
+        let version = format!("v{}.{}.{}", part("MAJOR")?, part("MINOR")?, part("PATCH")?);
+        Some((version, file))
+    })
+}
+
+/// 依次查找：符号链接目标所在的包目录（Unix 全局安装）、`<prefix>/node_modules`（Windows 的 .cmd 垫片）、`<prefix>/lib/node_modules`
+fn node_package(executable: &Path, roots: &[&Path], package: &str) -> Option<(String, PathBuf)> {
+    let mut candidates: Vec<PathBuf> = executable
+        .ancestors()
+        .skip(1)
+        .take(4)
+        .map(|dir| dir.join("package.json"))
+        .collect();
+    for root in roots {
+        candidates.push(root.join("node_modules").join(package).join("package.json"));
+        candidates.push(root.join("lib").join("node_modules").join(package).join("package.json"));
+    }
+
+    candidates.into_iter().find_map(|file| {
+        let document: Value = serde_json::from_str(&read_text(&file)?).ok()?;
+        if document.get("name").and_then(Value::as_str) != Some(package) {
+            return None;
+        }
+        let version = document.get("version").and_then(Value::as_str)?.to_string();
+        Some((version, file))
+    })
+}
+
+fn python_version(path: &Path, executable: &Path, roots: &[&Path]) -> Option<(String, PathBuf)> {
+    // venv 中的 python 是指向基础解释器的链接，pyvenv.cfg 位于链接所在的 venv 中，而不是链接目标旁
+    let venv_roots: Vec<&Path> = path.ancestors().skip(1).take(2).collect();
+    if let Some((version, file)) =
+        first_capture(&venv_roots, &["pyvenv.cfg"], r"(?m)^version(?:_info)?\s*=\s*(\d+\.\d+\.\d+)")
+    {
+        return Some((format!("Python {}", version), file));
+    }
+
+    // 头文件目录需与解释器对应：Unix 上按带版本号的文件名（python3.12 → include/python3.12），
+    // Windows 安装目录的 include 下直接是 patchlevel.h；文件名不带版本号时无法对应，交给版本命令
+    let name = executable.file_name()?.to_string_lossy().to_string();
+    let header_dir = if name.eq_ignore_ascii_case("python.exe") {
+        Some(PathBuf::from("include"))
+    } else {
+        Regex::new(r"^python\d+\.\d+t?$")
+            .ok()?
+            .is_match(&name)
+            .then(|| Path::new("include").join(&name))
+    };
+    let header = header_dir.and_then(|header_dir| {
+        roots.iter().find_map(|root| {
+            let file = root.join(&header_dir).join("patchlevel.h");
+            Some((capture(&read_text(&file)?, r#"#define PY_VERSION\s+"([^"]+)""#)?, file))
+        })
+    });
+    if let Some((version, file)) = header {
+        return Some((format!("Python {}", version), file));
+    }
+
+    // 只能确定主次版本
+    let dll = Regex::new(r"(?i)^python3(\d+)\.dll$").ok()?;
+    roots.iter().find_map(|root| {
+        child_names(root).into_iter().find_map(|name| {
+            let minor = dll.captures(&name)?.get(1)?.as_str().to_string();
+            Some((format!("Python 3.{}", minor), root.join(&name)))
+        })
+    })
+}
+
+fn numeric_key(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// `dotnet --version` 默认使用最高的 SDK；有正式版时不取预览版
+fn dotnet_sdk(roots: &[&Path]) -> Option<(String, PathBuf)> {
+    roots.iter().find_map(|root| {
+        let sdk_dir = root.join("sdk");
+        let versions: Vec<String> = child_names(&sdk_dir)
+            .into_iter()
+            .filter(|name| name.starts_with(|ch: char| ch.is_ascii_digit()) && sdk_dir.join(name).is_dir())
+            .collect();
+        let stable: Vec<&String> = versions.iter().filter(|name| !name.contains('-')).collect();
+        let pool = if stable.is_empty() { versions.iter().collect() } else { stable };
+        let latest = pool.into_iter().max_by_key(|name| numeric_key(name))?.clone();
+        Some((latest.clone(), sdk_dir.join(latest)))
+    })
+}
+
+fn azure_cli(roots: &[&Path]) -> Option<(String, PathBuf)> {
+    let dist_info = Regex::new(r"^azure_cli-(\d+(?:\.\d+)+)\.dist-info$").ok()?;
+    let mut site_packages: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        site_packages.push(root.join("Lib").join("site-packages"));
+        for name in child_names(&root.join("lib")).into_iter().filter(|name| name.starts_with("python3")) {
+            site_packages.push(root.join("lib").join(name).join("site-packages"));
+        }
+    }
+
+    site_packages.into_iter().find_map(|dir| {
+        child_names(&dir).into_iter().find_map(|name| {
+            let version = dist_info.captures(&name)?.get(1)?.as_str().to_string();
+            Some((format!("azure-cli {}", version), dir.join(&name)))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::resolver::resolve_symlinks;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// 不经过符号链接的情况，PATH 中的路径即实际文件
+    fn probe(fingerprint: &VersionFingerprint, command: &str, executable: &Path) -> Option<(String, PathBuf)> {
+        fingerprint_from(fingerprint, command, executable, executable)
+    }
+
+    #[test]
+    fn release_and_version_files_are_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let jdk = dir.path().join("jdk-21");
+        write(&jdk.join("release"), "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21.0.2\"\n");
+        let (output, file) = probe(&VersionFingerprint::JdkRelease, "java", &jdk.join("bin").join("java")).unwrap();
+        assert_eq!(output, "openjdk version \"21.0.2\"");
+        assert_eq!(file, jdk.join("release"));
+
+        let go = dir.path().join("go");
+        write(&go.join("VERSION"), "go1.22.4\ntime 2024-05-30T19:26:07Z\n");
+        let (output, _) = probe(&VersionFingerprint::GoVersionFile, "go", &go.join("bin").join("go")).unwrap();
+        assert_eq!(output, "go version go1.22.4");
+
+        let node = dir.path().join("node");
+        write(
+            &node.join("include").join("node").join("node_version.h"),
+            "#define NODE_MAJOR_VERSION 20\n#define NODE_MINOR_VERSION 11\n#define NODE_PATCH_VERSION 1\n",
+        );
+        let (output, _) = probe(&VersionFingerprint::NodeVersionHeader, "node", &node.join("bin").join("node")).unwrap();
+        assert_eq!(output, "v20.11.1");
+
+        assert!(probe(&VersionFingerprint::GoVersionFile, "go", &jdk.join("bin").join("go")).is_none());
+    }
+
+    #[test]
+    fn npm_packages_are_found_from_symlink_target_and_windows_shim() {
+        let dir = tempfile::tempdir().unwrap();
+        let npm = dir.path().join("lib").join("node_modules").join("npm");
+        write(&npm.join("package.json"), r#"{"name":"npm","version":"10.5.0"}"#);
+        let (output, _) =
+            probe(&VersionFingerprint::NodePackage("npm"), "npm", &npm.join("bin").join("npm-cli.js")).unwrap();
+        assert_eq!(output, "10.5.0");
+
+        let prefix = dir.path().join("AppData").join("npm");
+        write(
+            &prefix.join("node_modules").join("@google").join("gemini-cli").join("package.json"),
+            r#"{"name":"@google/gemini-cli","version":"0.1.9"}"#,
+        );
+        let fingerprint = VersionFingerprint::NodePackage("@google/gemini-cli");
+        let (output, _) = probe(&fingerprint, "gemini", &prefix.join("gemini.cmd")).unwrap();
+        assert_eq!(output, "0.1.9");
+    }
+
+    #[test]
+    fn python_prefers_full_versions_over_dll_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let venv = dir.path().join("venv");
+        write(&venv.join("pyvenv.cfg"), "home = /usr/bin\ninclude-system-site-packages = false\nversion = 3.12.1\n");
+        let (output, _) = probe(&VersionFingerprint::Python, "python", &venv.join("bin").join("python")).unwrap();
+        assert_eq!(output, "Python 3.12.1");
+
+        let windows = dir.path().join("Python311");
+        write(&windows.join("python311.dll"), "");
+        let (output, _) = probe(&VersionFingerprint::Python, "python", &windows.join("python.exe")).unwrap();
+        assert_eq!(output, "Python 3.11");
+
+        write(&windows.join("include").join("patchlevel.h"), "#define PY_VERSION      \"3.11.9\"\n");
+        let (output, _) = probe(&VersionFingerprint::Python, "python", &windows.join("python.exe")).unwrap();
+        assert_eq!(output, "Python 3.11.9");
+    }
+
+    #[test]
+    fn python_headers_must_match_the_interpreter() {
+        let dir = tempfile::tempdir().unwrap();
+        let usr = dir.path().join("usr");
+        write(&usr.join("include").join("python3.9").join("patchlevel.h"), "#define PY_VERSION \"3.9.18\"\n");
+        write(&usr.join("include").join("python3.10").join("patchlevel.h"), "#define PY_VERSION \"3.10.14\"\n");
+
+        let (output, _) = probe(&VersionFingerprint::Python, "python3", &usr.join("bin").join("python3.10")).unwrap();
+        assert_eq!(output, "Python 3.10.14");
+        let (output, _) = probe(&VersionFingerprint::Python, "python3", &usr.join("bin").join("python3.9")).unwrap();
+        assert_eq!(output, "Python 3.9.18");
+        // 解释器文件名不带版本号时，残留的头文件不能代表它的版本
+        assert!(probe(&VersionFingerprint::Python, "python3", &usr.join("bin").join("python3")).is_none());
+        assert!(probe(&VersionFingerprint::Python, "python3", &usr.join("bin").join("python3.12")).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_venv_reads_pyvenv_cfg_next_to_the_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base");
+        write(&base.join("bin").join("python3.12"), "");
+        write(&base.join("include").join("python3.12").join("patchlevel.h"), "#define PY_VERSION \"3.12.0\"\n");
+        let venv = dir.path().join("venv");
+        write(&venv.join("pyvenv.cfg"), "home = /usr/bin\nversion = 3.12.1\n");
+        let link = venv.join("bin").join("python");
+        std::fs::create_dir_all(link.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(base.join("bin").join("python3.12"), &link).unwrap();
+
+        let target = resolve_symlinks(&link);
+        assert_ne!(target, link);
+        let (output, file) = fingerprint_from(&VersionFingerprint::Python, "python", &link, &target).unwrap();
+        assert_eq!(output, "Python 3.12.1");
+        assert_eq!(file, venv.join("pyvenv.cfg"));
+
+        let (output, _) = probe(&VersionFingerprint::Python, "python", &target).unwrap();
+        assert_eq!(output, "Python 3.12.0");
+    }
+
+    #[test]
+    fn dotnet_and_azure_cli_are_read_from_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let dotnet = dir.path().join("dotnet");
+        for version in ["6.0.420", "8.0.204", "8.0.3", "9.0.100-preview.3.24204.13"] {
+            std::fs::create_dir_all(dotnet.join("sdk").join(version)).unwrap();
+        }
+        let (output, _) = probe(&VersionFingerprint::DotnetSdk, "dotnet", &dotnet.join("dotnet")).unwrap();
+        assert_eq!(output, "8.0.204");
+
+        let cli = dir.path().join("CLI2");
+        std::fs::create_dir_all(cli.join("Lib").join("site-packages").join("azure_cli-2.61.0.dist-info")).unwrap();
+        let (output, _) =
+            probe(&VersionFingerprint::AzureCliDistInfo, "az", &cli.join("wbin").join("az.cmd")).unwrap();
+        assert_eq!(output, "azure-cli 2.61.0");
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::{ToolHistoryEntry, ToolStatus};
+
+pub const TOOL_HISTORY_FILE: &str = "tool-history.json";
+/// 超出后丢弃最早的记录
+pub const MAX_TOOL_HISTORY_ENTRIES: usize = 5_000;
+
+pub const HISTORY_ADDED: &str = "added";
+pub const HISTORY_REMOVED: &str = "removed";
+pub const HISTORY_CHANGED: &str = "changed";
+
+/// 每个工具最近一次探测到的状态，用于和下一次结果比较
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SeenTool {
+    installed: bool,
+    version: Option<String>,
+    install_path: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolHistoryFile {
+    last_seen: HashMap<String, SeenTool>,
+    entries: Vec<ToolHistoryEntry>,
+}
+
+/// 工具版本变化记录，写入应用配置目录
+#[derive(Clone)]
+pub struct ToolHistoryStore {
+    path: Option<PathBuf>,
+    file: Arc<Mutex<ToolHistoryFile>>,
+}
+
+impl ToolHistoryStore {
+    /// 文件不存在或损坏时从空记录开始
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let file = path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+        }
+    }
+
+    /// 与上次状态比较并追加变化；某个工具第一次出现时只建立它的基准，不产生条目，
+    /// 因此只探测了部分工具（单个工具、后台重试、项目目录）也不会误记为新增
+    pub fn record(&self, tools: &[ToolStatus], now_ms: u64) -> Vec<ToolHistoryEntry> {
+        let mut file = self.file.lock().unwrap();
+        let mut changes = Vec::new();
+        let mut seen_changed = false;
+
+        // 超时的结果不可信，不参与比较
+        for tool in tools.iter().filter(|tool| !tool.timed_out) {
+            let key = tool.name.to_lowercase();
+            let current = SeenTool {
+                installed: tool.installed,
+                version: tool.version.clone().filter(|_| tool.installed),
+                install_path: tool.install_path.clone(),
+            };
+            let previous = file.last_seen.get(&key);
+            if previous == Some(&current) {
+                continue;
+            }
+            if let Some(previous) = previous {
+                changes.extend(history_entry(&tool.name, previous, &current, now_ms));
+            }
+            file.last_seen.insert(key, current);
+            seen_changed = true;
+        }
+
+        if seen_changed {
+            file.entries.extend(changes.iter().cloned());
+            let overflow = file.entries.len().saturating_sub(MAX_TOOL_HISTORY_ENTRIES);
+            file.entries.drain(..overflow);
+            // 写入失败不影响探测结果
+            let _ = self.save(&file);
+        }
+        changes
+    }
+
+    /// 按时间倒序返回；tool 为空时返回全部工具
+    pub fn entries(&self, tool: Option<&str>, since_ms: Option<u64>) -> Vec<ToolHistoryEntry> {
+        let file = self.file.lock().unwrap();
+        file.entries
+            .iter()
+            .rev()
+            .filter(|entry| tool.is_none_or(|tool| entry.tool.eq_ignore_ascii_case(tool.trim())))
+            .filter(|entry| since_ms.is_none_or(|since| entry.recorded_at_ms >= since))
+            .cloned()
+            .collect()
+    }
+
+    fn save(&self, file: &ToolHistoryFile) -> Result<(), String> {
+        let Some(path) = self.path.as_deref() else {
+            return Ok(());
+        };
+        let content = serde_json::to_string(file).map_err(|error| format!("序列化工具历史失败: {}", error))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| format!("创建配置目录失败: {}", error))?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, content).map_err(|error| format!("写入工具历史失败: {}", error))?;
+        std::fs::rename(&temp, path).map_err(|error| format!("写入工具历史失败: {}", error))
+    }
+}
+
+fn history_entry(tool: &str, previous: &SeenTool, current: &SeenTool, now_ms: u64) -> Option<ToolHistoryEntry> {
+    let change = match (previous.installed, current.installed) {
+        (false, true) => HISTORY_ADDED,
+        (true, false) => HISTORY_REMOVED,
+        (true, true) => HISTORY_CHANGED,
+        (false, false) => return None,
+    };
+
+    Some(ToolHistoryEntry {
+        tool: tool.to_string(),
+        change: change.to_string(),
+        previous_version: previous.version.clone(),
+        version: current.version.clone(),
+        previous_path: previous.install_path.clone(),
+        install_path: current.install_path.clone(),
+        recorded_at_ms: now_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, version: Option<&str>) -> ToolStatus {
+        ToolStatus {
+            install_path: version.map(|_| format!("/usr/bin/{}", name.to_lowercase())),
+            ..ToolStatus::for_test(name, &name.to_lowercase(), version)
+        }
+    }
+
+    #[test]
+    fn changes_are_recorded_after_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TOOL_HISTORY_FILE);
+        let store = ToolHistoryStore::load(Some(path.clone()));
+
+        assert!(store.record(&[tool("Node.js", Some("v20.11.1")), tool("Go", None)], 1).is_empty());
+        assert!(store.record(&[tool("Node.js", Some("v20.11.1"))], 2).is_empty());
+
+        let mut timed_out = tool("Go", None);
+        timed_out.timed_out = true;
+        let changes = store.record(
+            &[tool("Node.js", Some("v22.3.0")), tool("Go", Some("go1.22.4")), timed_out],
+            3,
+        );
+        let summary: Vec<(&str, &str, Option<&str>)> = changes
+            .iter()
+            .map(|entry| (entry.tool.as_str(), entry.change.as_str(), entry.previous_version.as_deref()))
+            .collect();
+        assert_eq!(summary, vec![("Node.js", HISTORY_CHANGED, Some("v20.11.1")), ("Go", HISTORY_ADDED, None)]);
+
+        store.record(&[tool("Node.js", None)], 4);
+
+        let reloaded = ToolHistoryStore::load(Some(path));
+        let node = reloaded.entries(Some("node.js"), None);
+        assert_eq!(node.len(), 2);
+        assert_eq!((node[0].change.as_str(), node[0].recorded_at_ms), (HISTORY_REMOVED, 4));
+        assert_eq!(node[1].version.as_deref(), Some("v22.3.0"));
+        assert_eq!(reloaded.entries(None, Some(4)).len(), 1);
+    }
+
+    #[test]
+    fn tools_first_seen_in_a_later_batch_only_seed_their_baseline() {
+        let store = ToolHistoryStore::load(None);
+
+        // 先只探测了单个工具，再完整探测
+        assert!(store.record(&[tool("Go", Some("go1.22.4"))], 1).is_empty());
+        assert!(store
+            .record(&[tool("Go", Some("go1.22.4")), tool("Node.js", Some("v20.11.1")), tool("Rust", None)], 2)
+            .is_empty());
+
+        let changes = store.record(&[tool("Rust", Some("rustc 1.79.0")), tool("Node.js", Some("v20.11.1"))], 3);
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|entry| (entry.tool.as_str(), entry.change.as_str()))
+            .collect();
+        assert_eq!(summary, vec![("Rust", HISTORY_ADDED)]);
+    }
+}
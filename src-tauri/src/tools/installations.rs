@@ -0,0 +1,243 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::contracts::{ToolInstallation, ToolInstallationsReport};
+use crate::process_runner::execute_process_with_timeout;
+
+use super::catalog::{find_tool_spec, load_tool_catalog};
+use super::detect::{extract_version, first_line};
+use super::resolver::{find_executables, path_dirs, path_extensions, resolve_symlinks};
+use super::specs::ToolSpec;
+use super::version_rules::parse_tool_version;
+use super::TOOL_DETECT_TIMEOUT_MS;
+
+/// 版本管理器与常见发行版的安装根目录；`~` 为用户目录，`*` 匹配一级子目录
+const INSTALL_ROOTS: &[(&str, &str)] = &[
+    ("NVM_HOME", "*"),
+    ("APPDATA", "nvm/*"),
+    ("FNM_DIR", "node-versions/*/installation"),
+    ("APPDATA", "fnm/node-versions/*/installation"),
+    ("LOCALAPPDATA", "Volta/tools/image/node/*"),
+    ("PYENV_ROOT", "versions/*"),
+    ("~", ".pyenv/pyenv-win/versions/*"),
+    ("LOCALAPPDATA", "Programs/Python/*"),
+    ("~", "miniconda3"),
+    ("~", "anaconda3"),
+    ("~", "miniforge3"),
+    ("~", "miniconda3/envs/*"),
+    ("~", "anaconda3/envs/*"),
+    ("ProgramFiles", "Java/*/bin"),
+    ("ProgramFiles", "Eclipse Adoptium/*/bin"),
+    ("ProgramFiles", "Microsoft/*/bin"),
+    ("ProgramFiles", "Go/bin"),
+    ("~", "sdk/*/bin"),
+    ("~", ".rustup/toolchains/*/bin"),
+    ("~", ".nvm/versions/node/*/bin"),
+    ("~", ".local/share/fnm/node-versions/*/installation/bin"),
+    ("~", ".volta/tools/image/node/*/bin"),
+    ("~", ".pyenv/versions/*/bin"),
+    ("~", "miniconda3/bin"),
+    ("~", "anaconda3/bin"),
+    ("~", "miniforge3/bin"),
+    ("~", ".sdkman/candidates/*/*/bin"),
+    ("~", ".asdf/installs/*/*/bin"),
+    ("~", ".local/share/mise/installs/*/*/bin"),
+];
+
+/// 列出 PATH 与常见安装目录中的全部同名可执行文件，PATH 中第一个为当前生效版本
+pub fn find_all_installations(tool: &str) -> Result<ToolInstallationsReport, String> {
+    let tool = tool.trim();
+    if tool.is_empty() {
+        return Err("工具名称不能为空".to_string());
+    }
+
+    let catalog = load_tool_catalog(None);
+    let spec = find_tool_spec(&catalog.specs, tool)
+        .cloned()
+        .unwrap_or_else(|| ToolSpec::builtin(tool, tool, &["--version"], "Custom", None));
+
+    let extensions = path_extensions();
+    let on_path = find_executables(&spec.command, &path_dirs(), &extensions);
+    let in_roots = find_executables(&spec.command, &expand_install_roots(), &extensions);
+
+    let mut seen = HashSet::new();
+    let candidates: Vec<(PathBuf, bool)> = on_path
+        .into_iter()
+        .map(|path| (path, true))
+        .chain(in_roots.into_iter().map(|path| (path, false)))
+        .filter(|(path, _)| seen.insert(dedup_key(path)))
+        .collect();
+
+    let installations: Vec<ToolInstallation> = candidates
+        .par_iter()
+        .enumerate()
+        .map(|(index, (path, on_path))| probe_installation(&spec, path, *on_path, index == 0 && *on_path))
+        .collect();
+
+    Ok(ToolInstallationsReport {
+        tool: spec.name.clone(),
+        command: spec.command.clone(),
+        active_path: installations
+            .iter()
+            .find(|installation| installation.active)
+            .map(|installation| installation.path.clone()),
+        installations,
+    })
+}
+
+fn probe_installation(spec: &ToolSpec, path: &Path, on_path: bool, active: bool) -> ToolInstallation {
+    let display_path = path.to_string_lossy().to_string();
+    let timeout = spec.timeout_ms.unwrap_or(TOOL_DETECT_TIMEOUT_MS);
+    let mut installation = ToolInstallation {
+        path: display_path.clone(),
+        source: classify_installation_source(&display_path).to_string(),
+        active,
+        on_path,
+        version: None,
+        parsed_version: None,
+        details: None,
+    };
+
+    match execute_process_with_timeout(&display_path, &spec.args, timeout) {
+        Ok(output) => {
+            let raw = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+            if output.exit_code == 0 {
+                installation.version = extract_version(spec, &raw);
+                installation.parsed_version = parse_tool_version(spec, &raw);
+            } else {
+                // 如 Microsoft Store 占位 python.exe：返回 9009 并提示去商店安装
+                installation.details = Some(match first_line(&raw) {
+                    Some(line) => format!("返回码 {}，{}", output.exit_code, line),
+                    None => format!("命令执行失败（返回码 {}）", output.exit_code),
+                });
+            }
+        }
+        Err(error) => installation.details = Some(error),
+    }
+
+    installation
+}
+
+fn expand_install_roots() -> Vec<PathBuf> {
+    INSTALL_ROOTS
+        .iter()
+        .filter_map(|(base, pattern)| {
+            let base = if *base == "~" {
+                dirs::home_dir()?
+            } else {
+                PathBuf::from(std::env::var_os(base)?)
+            };
+            Some(expand_pattern(&base, pattern))
+        })
+        .flatten()
+        .collect()
+}
+
+/// 将 `a/*/bin` 展开为实际存在的目录，子目录按名称排序
+pub fn expand_pattern(base: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut current = vec![base.to_path_buf()];
+    for segment in pattern.split('/').filter(|segment| !segment.is_empty()) {
+        current = current
+            .into_iter()
+            .flat_map(|dir| {
+                if segment != "*" {
+                    return vec![dir.join(segment)];
+                }
+                let mut children: Vec<PathBuf> = std::fs::read_dir(&dir)
+                    .map(|entries| {
+                        entries
+                            .filter_map(Result::ok)
+                            .map(|entry| entry.path())
+                            .filter(|path| path.is_dir())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                children.sort();
+                children
+            })
+            .collect();
+    }
+
+    current.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+fn dedup_key(path: &Path) -> String {
+    let key = resolve_symlinks(path).to_string_lossy().to_string();
+    if cfg!(target_os = "windows") {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// 按路径特征判断安装来源，无法识别的归为 manual
+pub fn classify_installation_source(path: &str) -> &'static str {
+    let lowered = path.to_lowercase().replace('\\', "/");
+    let rules: [(&[&str], &str); 14] = [
+        (&["/windowsapps/"], "store"),
+        (&["/winget/"], "winget"),
+        (&["/scoop/"], "scoop"),
+        (&["/chocolatey/"], "choco"),
+        (&["/nvm/", "/.nvm/"], "nvm"),
+        (&["/fnm/", "/fnm_multishells/"], "fnm"),
+        (&["/volta/", "/.volta/"], "volta"),
+        (&["/pyenv-win/", "/.pyenv/"], "pyenv"),
+        (&["/miniconda", "/anaconda", "/miniforge", "/conda/"], "conda"),
+        (&["/.sdkman/"], "sdkman"),
+        (&["/.rustup/", "/.cargo/"], "rustup"),
+        (&["/.asdf/"], "asdf"),
+        (&["/mise/"], "mise"),
+        (&["/homebrew/", "/linuxbrew/", "/cellar/"], "homebrew"),
+    ];
+
+    rules
+        .iter()
+        .find(|(needles, _)| needles.iter().any(|needle| lowered.contains(needle)))
+        .map(|(_, source)| *source)
+        .unwrap_or("manual")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_known_installation_sources() {
+        assert_eq!(
+            classify_installation_source(r"C:\Users\dev\AppData\Local\Microsoft\WindowsApps\python.exe"),
+            "store"
+        );
+        assert_eq!(classify_installation_source(r"C:\Users\dev\AppData\Roaming\nvm\v20.11.1\node.exe"), "nvm");
+        assert_eq!(classify_installation_source(r"C:\Users\dev\miniconda3\envs\ml\python.exe"), "conda");
+        assert_eq!(
+            classify_installation_source(r"C:\Users\dev\.pyenv\pyenv-win\versions\3.11.7\python.exe"),
+            "pyenv"
+        );
+        assert_eq!(
+            classify_installation_source(
+                r"C:\Users\dev\AppData\Local\Microsoft\WinGet\Packages\Git.Git_8wekyb3d8bbwe\bin\git.exe"
+            ),
+            "winget"
+        );
+        assert_eq!(classify_installation_source("/home/dev/.sdkman/candidates/java/21.0.2-tem/bin/java"), "sdkman");
+        assert_eq!(classify_installation_source(r"C:\Program Files\Python312\python.exe"), "manual");
+    }
+
+    #[test]
+    fn expand_pattern_lists_matching_directories_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in ["v20.11.1", "v18.19.0"] {
+            std::fs::create_dir_all(dir.path().join("nvm").join(version)).unwrap();
+        }
+        std::fs::write(dir.path().join("nvm").join("settings.txt"), "").unwrap();
+
+        let expanded = expand_pattern(dir.path(), "nvm/*");
+        assert_eq!(
+            expanded,
+            vec![dir.path().join("nvm").join("v18.19.0"), dir.path().join("nvm").join("v20.11.1")]
+        );
+        assert!(expand_pattern(dir.path(), "missing/*/bin").is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::contracts::{EolDatasetInfo, ToolLifecycle, ToolStatus};
+
+use super::catalog::app_config_dir;
+
+pub const ENDOFLIFE_API_URL: &str = "https://endoflife.date/api";
+pub const EOL_UPDATE_TIMEOUT_MS: u64 = 10_000;
+
+pub const LIFECYCLE_SUPPORTED: &str = "supported";
+pub const LIFECYCLE_SECURITY_ONLY: &str = "security_only";
+pub const LIFECYCLE_EOL: &str = "eol";
+
+const BUILTIN_DATASET: &str = include_str!("eol-dataset.json");
+const DATASET_FILE_NAME: &str = "eol-dataset.json";
+
+/// 与 endoflife.date 的 API 字段一致：support / eol / lts 可以是日期或布尔值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolCycle {
+    pub cycle: String,
+    #[serde(default)]
+    pub release_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<Value>,
+    #[serde(default)]
+    pub eol: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lts: Option<Value>,
+}
+
+/// key 为 endoflife.date 上的产品标识，commands 为对应的工具命令
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolProduct {
+    pub key: String,
+    pub name: String,
+    pub commands: Vec<String>,
+    pub cycles: Vec<EolCycle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolDataset {
+    pub updated_at: String,
+    pub products: Vec<EolProduct>,
+}
+
+fn dataset_override_path() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join(DATASET_FILE_NAME))
+}
+
+fn builtin_dataset() -> EolDataset {
+    serde_json::from_str(BUILTIN_DATASET).expect("内置生命周期数据集格式错误")
+}
+
+/// 应用配置目录中下载过的数据集比内置的新时优先使用；返回数据集与来源
+pub fn load_eol_dataset() -> (EolDataset, &'static str) {
+    let builtin = builtin_dataset();
+    let downloaded = dataset_override_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<EolDataset>(&content).ok())
+        .filter(|dataset| dataset.updated_at >= builtin.updated_at && !dataset.products.is_empty());
+
+    match downloaded {
+        Some(dataset) => (dataset, "downloaded"),
+        None => (builtin, "builtin"),
+    }
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn date_value(value: Option<&Value>) -> Option<String> {
+    value.and_then(Value::as_str).map(ToString::to_string)
+}
+
+/// 日期当天即视为结束；布尔值 true 表示已结束，false 表示尚未公布结束日期
+fn has_ended(value: Option<&Value>, today: &str) -> Option<bool> {
+    match value? {
+        Value::Bool(ended) => Some(*ended),
+        Value::String(date) => Some(date.as_str() <= today),
+        _ => None,
+    }
+}
+
+pub fn lifecycle_state(cycle: &EolCycle, today: &str) -> &'static str {
+    if has_ended(Some(&cycle.eol), today) == Some(true) {
+        return LIFECYCLE_EOL;
+    }
+    // 未提供 support 的产品（Go、Temurin 等）在 EOL 之前都算完整支持；support 为 false 表示只剩安全修复
+    match cycle.support.as_ref() {
+        Some(Value::Bool(false)) => LIFECYCLE_SECURITY_ONLY,
+        support if has_ended(support, today) == Some(true) => LIFECYCLE_SECURITY_ONLY,
+        _ => LIFECYCLE_SUPPORTED,
+    }
+}
+
+/// 从版本中取主/次版本号；Java 8 之前的 `1.8.0_392` 写法按 8 处理
+fn version_numbers(tool: &ToolStatus) -> Option<(u64, Option<u64>)> {
+    if let Some((Some(major), minor)) = tool.parsed_version.as_ref().map(|parsed| (parsed.major, parsed.minor)) {
+        return Some((major, minor));
+    }
+    let version = tool.version.as_deref()?;
+    let pattern = Regex::new(r"(\d+)(?:\.(\d+))?").ok()?;
+    let captures = pattern.captures(version)?;
+    let major = captures.get(1)?.as_str().parse().ok()?;
+    let minor = captures.get(2).and_then(|minor| minor.as_str().parse().ok());
+    Some((major, minor))
+}
+
+pub fn find_cycle(product: &EolProduct, major: u64, minor: Option<u64>) -> Option<&EolCycle> {
+    let (major, minor) = match (product.key.as_str(), major, minor) {
+        ("eclipse-temurin", 1, Some(minor)) => (minor, None),
+        _ => (major, minor),
+    };
+    let candidates = [minor.map(|minor| format!("{}.{}", major, minor)), Some(major.to_string())];
+    candidates
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| product.cycles.iter().find(|cycle| cycle.cycle == candidate))
+}
+
+pub fn lifecycle_for(dataset: &EolDataset, tool: &ToolStatus, today: &str) -> Option<ToolLifecycle> {
+    if !tool.installed {
+        return None;
+    }
+    let product = dataset
+        .products
+        .iter()
+        .find(|product| product.commands.iter().any(|command| command == &tool.command))?;
+    let (major, minor) = version_numbers(tool)?;
+    let cycle = find_cycle(product, major, minor)?;
+    let eol_date = date_value(Some(&cycle.eol));
+
+    Some(ToolLifecycle {
+        product: product.key.clone(),
+        product_name: product.name.clone(),
+        cycle: cycle.cycle.clone(),
+        state: lifecycle_state(cycle, today).to_string(),
+        release_date: cycle.release_date.clone(),
+        // 没有单独的完整支持期时，支持截止即 EOL
+        support_end: date_value(cycle.support.as_ref()).or_else(|| eol_date.clone()),
+        eol_date,
+        lts: matches!(&cycle.lts, Some(Value::Bool(true)) | Some(Value::String(_))),
+    })
+}
+
+/// 生命周期状态随日期变化，缓存命中的结果也要重新标注
+pub fn annotate_lifecycle<'a>(tools: impl IntoIterator<Item = &'a mut ToolStatus>) {
+    let (dataset, _) = load_eol_dataset();
+    let today = today();
+    for tool in tools {
+        tool.lifecycle = lifecycle_for(&dataset, tool, &today);
+    }
+}
+
+pub fn get_eol_dataset_info_internal() -> EolDatasetInfo {
+    let (dataset, source) = load_eol_dataset();
+    EolDatasetInfo {
+        updated_at: dataset.updated_at,
+        source: source.to_string(),
+        products: dataset.products.into_iter().map(|product| product.name).collect(),
+        errors: Vec::new(),
+    }
+}
+
+/// endoflife.date 返回的 cycle 可能是数字，统一转成字符串
+pub fn parse_api_cycles(body: &str) -> Result<Vec<EolCycle>, String> {
+    let items: Vec<Value> = serde_json::from_str(body).map_err(|error| format!("生命周期数据格式错误: {}", error))?;
+    items
+        .into_iter()
+        .map(|mut item| {
+            if let Some(number) = item.get("cycle").filter(|cycle| cycle.is_number()).map(ToString::to_string) {
+                item["cycle"] = Value::String(number);
+            }
+            serde_json::from_value(item).map_err(|error| format!("生命周期数据格式错误: {}", error))
+        })
+        .collect()
+}
+
+async fn fetch_product_cycles(client: &reqwest::Client, base_url: &str, key: &str) -> Result<Vec<EolCycle>, String> {
+    let url = format!("{}/{}.json", base_url.trim_end_matches('/'), key);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("请求 {} 失败: {}", url, error))?;
+    if !response.status().is_success() {
+        return Err(format!("请求 {} 失败: HTTP {}", url, response.status()));
+    }
+    let body = response.text().await.map_err(|error| format!("读取 {} 失败: {}", url, error))?;
+    let cycles = parse_api_cycles(&body)?;
+    if cycles.is_empty() {
+        return Err(format!("{} 没有返回任何发布周期", url));
+    }
+    Ok(cycles)
+}
+
+/// 逐个产品从 endoflife.date 拉取最新数据；单个产品失败时保留原有数据并记录错误
+pub async fn update_eol_dataset_from(base_url: &str) -> Result<EolDatasetInfo, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(EOL_UPDATE_TIMEOUT_MS))
+        .user_agent("DevEnvProbe/1.0")
+        .build()
+        .map_err(|error| format!("创建 HTTP 客户端失败: {}", error))?;
+
+    let (mut dataset, _) = load_eol_dataset();
+    let mut errors = Vec::new();
+    let mut updated = 0;
+    for product in dataset.products.iter_mut() {
+        match fetch_product_cycles(&client, base_url, &product.key).await {
+            Ok(cycles) => {
+                product.cycles = cycles;
+                updated += 1;
+            }
+            Err(error) => errors.push(format!("{}: {}", product.name, error)),
+        }
+    }
+    if updated == 0 {
+        return Err(format!("生命周期数据更新失败: {}", errors.join("; ")));
+    }
+
+    dataset.updated_at = today();
+    let path = dataset_override_path().ok_or_else(|| "无法定位应用配置目录".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| format!("创建配置目录失败: {}", error))?;
+    }
+    let content = serde_json::to_string_pretty(&dataset).map_err(|error| format!("序列化生命周期数据失败: {}", error))?;
+    std::fs::write(&path, content).map_err(|error| format!("写入 {} 失败: {}", path.display(), error))?;
+
+    Ok(EolDatasetInfo {
+        updated_at: dataset.updated_at,
+        source: "downloaded".to_string(),
+        products: dataset.products.into_iter().map(|product| product.name).collect(),
+        errors,
+    })
+}
+
+pub async fn update_eol_dataset_internal() -> Result<EolDatasetInfo, String> {
+    update_eol_dataset_from(ENDOFLIFE_API_URL).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::ToolVersion;
+
+    fn tool(command: &str, version: &str) -> ToolStatus {
+        ToolStatus::for_test(command, command, Some(version))
+    }
+
+    #[test]
+    fn builtin_dataset_covers_major_runtimes() {
+        let dataset = builtin_dataset();
+        for key in ["nodejs", "python", "eclipse-temurin", "go", "dotnet", "postgresql", "mysql", "kubernetes"] {
+            let product = dataset.products.iter().find(|product| product.key == key).unwrap();
+            assert!(!product.cycles.is_empty(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn states_follow_support_and_eol_dates() {
+        let dataset = builtin_dataset();
+        let today = "2025-06-15";
+
+        let node = lifecycle_for(&dataset, &tool("node", "v20.11.1"), today).unwrap();
+        assert_eq!((node.cycle.as_str(), node.state.as_str()), ("20", LIFECYCLE_SECURITY_ONLY));
+        assert_eq!(node.eol_date.as_deref(), Some("2026-04-30"));
+        assert!(node.lts);
+
+        let python = lifecycle_for(&dataset, &tool("python3", "Python 3.13.1"), today).unwrap();
+        assert_eq!((python.cycle.as_str(), python.state.as_str()), ("3.13", LIFECYCLE_SUPPORTED));
+
+        let legacy = lifecycle_for(&dataset, &tool("python", "Python 2.7.18"), today).unwrap();
+        assert_eq!(legacy.state, LIFECYCLE_EOL);
+
+        // Go 没有单独的安全维护期，支持截止即 EOL
+        let go = lifecycle_for(&dataset, &tool("go", "go version go1.22.4 linux/amd64"), today).unwrap();
+        assert_eq!((go.cycle.as_str(), go.state.as_str()), ("1.22", LIFECYCLE_EOL));
+        assert_eq!(go.support_end, go.eol_date);
+    }
+
+    #[test]
+    fn cycles_match_version_shapes() {
+        let dataset = builtin_dataset();
+        let mut java = tool("java", "1.8.0_392");
+        java.parsed_version = Some(ToolVersion {
+            raw: "1.8.0_392".to_string(),
+            semver: None,
+            major: Some(1),
+            minor: Some(8),
+            patch: Some(0),
+            build: None,
+        });
+        assert_eq!(lifecycle_for(&dataset, &java, "2025-01-01").unwrap().cycle, "8");
+        assert_eq!(lifecycle_for(&dataset, &tool("dotnet", "8.0.204"), "2025-01-01").unwrap().cycle, "8.0");
+        assert_eq!(lifecycle_for(&dataset, &tool("kubectl", "v1.30.2"), "2025-01-01").unwrap().cycle, "1.30");
+        assert!(lifecycle_for(&dataset, &tool("node", "v99.0.0"), "2025-01-01").is_none());
+        assert!(lifecycle_for(&dataset, &tool("git", "2.45.0"), "2025-01-01").is_none());
+    }
+
+    #[test]
+    fn api_cycles_accept_numeric_and_boolean_fields() {
+        let cycles = parse_api_cycles(
+            r#"[{"cycle":21,"releaseDate":"2023-09-19","eol":false,"lts":true,"latest":"21.0.4"},
+                {"cycle":"3.12","releaseDate":"2023-10-02","support":"2025-04-02","eol":"2028-10-31"}]"#,
+        )
+        .unwrap();
+        assert_eq!(cycles[0].cycle, "21");
+        assert_eq!(lifecycle_state(&cycles[0], "2030-01-01"), LIFECYCLE_SUPPORTED);
+        assert_eq!(lifecycle_state(&cycles[1], "2026-01-01"), LIFECYCLE_SECURITY_ONLY);
+        assert_eq!(lifecycle_state(&cycles[1], "2028-10-31"), LIFECYCLE_EOL);
+    }
+}
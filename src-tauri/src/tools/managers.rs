@@ -0,0 +1,665 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use rayon::prelude::*;
+use serde_json::Value;
+
+use crate::contracts::{ManagedToolInfo, ManagedVersion, ToolStatus, VersionManagerActionResult, VersionManagerStatus};
+use crate::process_runner::execute_process_with_timeout;
+
+use super::detect::first_line;
+use super::installations::classify_installation_source;
+use super::TOOL_DETECT_TIMEOUT_MS;
+
+pub const VERSION_MANAGER_LIST_TIMEOUT_MS: u64 = 5_000;
+pub const VERSION_MANAGER_INSTALL_TIMEOUT_MS: u64 = 20 * 60 * 1_000;
+
+pub const MANAGER_ACTION_INSTALL: &str = "install";
+pub const MANAGER_ACTION_UNINSTALL: &str = "uninstall";
+pub const MANAGER_ACTION_SET_DEFAULT: &str = "set_default";
+
+/// nvm(sh) 是 shell 函数，只能先 source nvm.sh 再调用；source 时会切换到 default 别名
+const NVM_INIT: &str = "source \"${NVM_DIR:-$HOME/.nvm}/nvm.sh\" && nvm";
+const NVM_VERSION_SCRIPT: &str = "source \"${NVM_DIR:-$HOME/.nvm}/nvm.sh\" && nvm --version";
+const NVM_LIST_SCRIPT: &str = "source \"${NVM_DIR:-$HOME/.nvm}/nvm.sh\" && nvm ls --no-colors --no-alias";
+/// SDKMAN 与 nvm(sh) 一样是 shell 函数，只能先 source 初始化脚本再调用
+const SDKMAN_INIT: &str = "source \"${SDKMAN_DIR:-$HOME/.sdkman}/bin/sdkman-init.sh\" && sdk";
+/// 直接读取 candidates 目录，避免每次列出版本都加载 sdkman-init.sh
+const SDKMAN_LIST_SCRIPT: &str = concat!(
+    "cd \"${SDKMAN_DIR:-$HOME/.sdkman}/candidates\" && ",
+    "for dir in */*/; do echo \"$dir\"; done; ",
+    "for link in */current; do echo \"$link -> $(readlink \"$link\")\"; done"
+);
+
+/// 参数中的 `{tool}`、`{version}` 在执行前替换；None 表示该管理器不支持此操作
+pub struct VersionManagerSpec {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub command: &'static str,
+    /// 只管理单一工具时为该工具的命令名；asdf / mise / SDKMAN 需要调用方指定
+    pub tool: Option<&'static str>,
+    pub version_args: &'static [&'static str],
+    pub list_args: &'static [&'static str],
+    pub install_args: Option<&'static [&'static str]>,
+    pub uninstall_args: Option<&'static [&'static str]>,
+    pub default_args: Option<&'static [&'static str]>,
+    pub parse_list: fn(&str) -> Vec<ManagedVersion>,
+}
+
+pub fn version_manager_specs() -> Vec<VersionManagerSpec> {
+    vec![
+        nvm_spec(),
+        VersionManagerSpec {
+            key: "fnm",
+            name: "fnm",
+            command: "fnm",
+            tool: Some("node"),
+            version_args: &["--version"],
+            list_args: &["list"],
+            install_args: Some(&["install", "{version}"]),
+            uninstall_args: Some(&["uninstall", "{version}"]),
+            default_args: Some(&["default", "{version}"]),
+            parse_list: parse_fnm_list,
+        },
+        VersionManagerSpec {
+            key: "volta",
+            name: "Volta",
+            command: "volta",
+            tool: Some("node"),
+            version_args: &["--version"],
+            list_args: &["list", "node", "--format", "plain"],
+            // volta fetch 只下载不切换，volta install 会同时设为默认
+            install_args: Some(&["fetch", "node@{version}"]),
+            uninstall_args: None,
+            default_args: Some(&["install", "node@{version}"]),
+            parse_list: parse_volta_list,
+        },
+        VersionManagerSpec {
+            key: "pyenv",
+            name: "pyenv",
+            command: "pyenv",
+            tool: Some("python"),
+            version_args: &["--version"],
+            list_args: &["versions"],
+            install_args: Some(&["install", "{version}"]),
+            uninstall_args: Some(&["uninstall", "-f", "{version}"]),
+            default_args: Some(&["global", "{version}"]),
+            parse_list: parse_pyenv_versions,
+        },
+        VersionManagerSpec {
+            key: "asdf",
+            name: "asdf",
+            command: "asdf",
+            tool: None,
+            version_args: &["--version"],
+            list_args: &["list"],
+            install_args: Some(&["install", "{tool}", "{version}"]),
+            uninstall_args: Some(&["uninstall", "{tool}", "{version}"]),
+            default_args: Some(&["global", "{tool}", "{version}"]),
+            parse_list: parse_asdf_list,
+        },
+        VersionManagerSpec {
+            key: "mise",
+            name: "mise",
+            command: "mise",
+            tool: None,
+            version_args: &["--version"],
+            list_args: &["ls", "--json"],
+            install_args: Some(&["install", "{tool}@{version}"]),
+            uninstall_args: Some(&["uninstall", "{tool}@{version}"]),
+            default_args: Some(&["use", "--global", "{tool}@{version}"]),
+            parse_list: parse_mise_list,
+        },
+        VersionManagerSpec {
+            key: "sdkman",
+            name: "SDKMAN!",
+            command: "bash",
+            tool: None,
+            version_args: &["-c", "cat \"${SDKMAN_DIR:-$HOME/.sdkman}/var/version\""],
+            list_args: &["-c", SDKMAN_LIST_SCRIPT],
+            install_args: Some(&["-c", "{init} install {tool} {version}"]),
+            uninstall_args: Some(&["-c", "{init} uninstall {tool} {version}"]),
+            default_args: Some(&["-c", "{init} default {tool} {version}"]),
+            parse_list: parse_sdkman_list,
+        },
+        VersionManagerSpec {
+            key: "rustup",
+            name: "rustup",
+            command: "rustup",
+            tool: Some("rustc"),
+            version_args: &["--version"],
+            list_args: &["toolchain", "list"],
+            install_args: Some(&["toolchain", "install", "{version}"]),
+            uninstall_args: Some(&["toolchain", "uninstall", "{version}"]),
+            default_args: Some(&["default", "{version}"]),
+            parse_list: parse_rustup_toolchains,
+        },
+    ]
+}
+
+/// Windows 上是 nvm-windows 可执行文件，其他平台上是需要 source 的 nvm(sh)，两者的来源都归类为 nvm
+fn nvm_spec() -> VersionManagerSpec {
+    if cfg!(target_os = "windows") {
+        VersionManagerSpec {
+            key: "nvm",
+            name: "nvm-windows",
+            command: "nvm",
+            tool: Some("node"),
+            version_args: &["version"],
+            list_args: &["list"],
+            install_args: Some(&["install", "{version}"]),
+            uninstall_args: Some(&["uninstall", "{version}"]),
+            // nvm-windows 没有 alias，use 会切换全局符号链接
+            default_args: Some(&["use", "{version}"]),
+            parse_list: parse_nvm_list,
+        }
+    } else {
+        VersionManagerSpec {
+            key: "nvm",
+            name: "nvm",
+            command: "bash",
+            tool: Some("node"),
+            version_args: &["-c", NVM_VERSION_SCRIPT],
+            list_args: &["-c", NVM_LIST_SCRIPT],
+            install_args: Some(&["-c", "{nvm} install {version}"]),
+            uninstall_args: Some(&["-c", "{nvm} uninstall {version}"]),
+            default_args: Some(&["-c", "{nvm} alias default {version}"]),
+            parse_list: parse_nvm_sh_list,
+        }
+    }
+}
+
+/// 探测所有版本管理器并列出各自已安装的版本
+pub fn detect_version_managers() -> Vec<VersionManagerStatus> {
+    version_manager_specs().par_iter().map(detect_version_manager).collect()
+}
+
+fn detect_version_manager(spec: &VersionManagerSpec) -> VersionManagerStatus {
+    let mut status = VersionManagerStatus {
+        key: spec.key.to_string(),
+        name: spec.name.to_string(),
+        installed: false,
+        version: None,
+        tools: spec.tool.map(|tool| vec![tool.to_string()]).unwrap_or_default(),
+        versions: Vec::new(),
+        details: None,
+    };
+
+    if spec.key == "sdkman" && !sdkman_dir().is_some_and(|dir| dir.is_dir()) {
+        status.details = Some("未检测到 SDKMAN 安装目录".to_string());
+        return status;
+    }
+    if spec.key == "nvm" && spec.command == "bash" && !nvm_dir().is_some_and(|dir| dir.join("nvm.sh").is_file()) {
+        status.details = Some("未检测到 nvm 安装目录".to_string());
+        return status;
+    }
+
+    let args: Vec<String> = spec.version_args.iter().map(ToString::to_string).collect();
+    match execute_process_with_timeout(spec.command, &args, TOOL_DETECT_TIMEOUT_MS) {
+        Ok(output) if output.exit_code == 0 => {
+            status.installed = true;
+            status.version = first_line(&output.stdout).or_else(|| first_line(&output.stderr));
+        }
+        Ok(output) => {
+            status.details = first_line(&output.stderr).or_else(|| Some(format!("返回码 {}", output.exit_code)));
+            return status;
+        }
+        Err(error) => {
+            status.details = Some(error);
+            return status;
+        }
+    }
+
+    match list_managed_versions(spec) {
+        Ok(versions) => {
+            if spec.tool.is_none() {
+                let mut tools: Vec<String> = versions.iter().map(|version| version.tool.clone()).collect();
+                tools.sort_unstable();
+                tools.dedup();
+                status.tools = tools;
+            }
+            status.versions = versions;
+        }
+        Err(error) => status.details = Some(error),
+    }
+
+    status
+}
+
+fn list_managed_versions(spec: &VersionManagerSpec) -> Result<Vec<ManagedVersion>, String> {
+    let args: Vec<String> = spec.list_args.iter().map(ToString::to_string).collect();
+    let output = execute_process_with_timeout(spec.command, &args, VERSION_MANAGER_LIST_TIMEOUT_MS)?;
+    if output.exit_code != 0 {
+        return Err(first_line(&output.stderr).unwrap_or_else(|| format!("返回码 {}", output.exit_code)));
+    }
+
+    Ok((spec.parse_list)(&output.stdout))
+}
+
+fn nvm_dir() -> Option<PathBuf> {
+    std::env::var_os("NVM_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")))
+}
+
+fn sdkman_dir() -> Option<PathBuf> {
+    std::env::var_os("SDKMAN_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".sdkman")))
+}
+
+/// 通过版本管理器自身的命令安装、卸载或设置全局默认版本
+pub fn run_version_manager_action(
+    manager: &str,
+    action: &str,
+    tool: Option<&str>,
+    version: &str,
+) -> Result<VersionManagerActionResult, String> {
+    let specs = version_manager_specs();
+    let spec = specs
+        .iter()
+        .find(|spec| spec.key == manager)
+        .ok_or_else(|| format!("未知的版本管理器：{}", manager))?;
+
+    let template = match action {
+        MANAGER_ACTION_INSTALL => spec.install_args,
+        MANAGER_ACTION_UNINSTALL => spec.uninstall_args,
+        MANAGER_ACTION_SET_DEFAULT => spec.default_args,
+        _ => return Err(format!("未知的操作：{}", action)),
+    }
+    .ok_or_else(|| format!("{} 不支持该操作：{}", spec.name, action))?;
+
+    let tool = match (spec.tool, tool.map(str::trim).filter(|tool| !tool.is_empty())) {
+        (Some(fixed), _) => fixed.to_string(),
+        (None, Some(tool)) => tool.to_string(),
+        (None, None) => return Err(format!("{} 需要指定工具名称", spec.name)),
+    };
+    validate_argument(&tool, "工具名称")?;
+    validate_argument(version, "版本号")?;
+
+    let args = render_manager_args(template, &tool, version);
+    let output = execute_process_with_timeout(spec.command, &args, VERSION_MANAGER_INSTALL_TIMEOUT_MS)?;
+
+    Ok(VersionManagerActionResult {
+        manager: spec.key.to_string(),
+        action: action.to_string(),
+        tool,
+        version: version.to_string(),
+        command: format!("{} {}", spec.command, args.join(" ")),
+        stdout: output.stdout,
+        stderr: output.stderr,
+        exit_code: output.exit_code,
+    })
+}
+
+pub fn render_manager_args(template: &[&str], tool: &str, version: &str) -> Vec<String> {
+    template
+        .iter()
+        .map(|arg| {
+            arg.replace("{init}", SDKMAN_INIT)
+                .replace("{nvm}", NVM_INIT)
+                .replace("{tool}", tool)
+                .replace("{version}", version)
+        })
+        .collect()
+}
+
+/// 版本号与工具名会拼进命令行（nvm(sh) 与 SDKMAN 经由 bash -c），只允许常见字符
+pub fn validate_argument(value: &str, label: &str) -> Result<(), String> {
+    let valid = !value.is_empty()
+        && !value.starts_with('-')
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_' | '+' | '/' | '@'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{}包含不支持的字符：{}", label, value))
+    }
+}
+
+/// 按探测到的路径判断工具是否由版本管理器提供，并附上该管理器中的其他版本
+/// 每个管理器的列表命令只执行一次，且并行执行，避免逐个等待超时拖慢工具探测
+pub fn annotate_managed_tools(tools: &mut [ToolStatus]) {
+    let specs = version_manager_specs();
+    let matched: Vec<Option<&VersionManagerSpec>> = tools.iter().map(|status| managing_spec(&specs, status)).collect();
+
+    let mut keys: Vec<&str> = matched.iter().flatten().map(|spec| spec.key).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    let listed: HashMap<&str, Vec<ManagedVersion>> = specs
+        .par_iter()
+        .filter(|spec| keys.contains(&spec.key))
+        .filter_map(|spec| list_managed_versions(spec).ok().map(|versions| (spec.key, versions)))
+        .collect();
+
+    for (status, spec) in tools.iter_mut().zip(matched) {
+        let Some(spec) = spec else {
+            continue;
+        };
+        if let Some(versions) = listed.get(spec.key) {
+            status.version_manager = link_managed_tool(status, spec, versions);
+        }
+    }
+}
+
+fn managing_spec<'a>(specs: &'a [VersionManagerSpec], status: &ToolStatus) -> Option<&'a VersionManagerSpec> {
+    let source = manager_source_for_path(status.install_path.as_deref()?);
+    specs
+        .iter()
+        .find(|spec| spec.key == source)
+        .filter(|spec| spec.tool.is_none_or(|tool| tool == status.command))
+}
+
+/// nvm-windows 通过 NVM_SYMLINK（默认 C:\Program Files\nodejs）切换版本，路径本身看不出来源
+fn manager_source_for_path(path: &str) -> &'static str {
+    let symlink = std::env::var("NVM_SYMLINK").ok().filter(|value| !value.trim().is_empty());
+    if symlink.is_some_and(|symlink| path.to_lowercase().starts_with(&symlink.to_lowercase())) {
+        return "nvm";
+    }
+    classify_installation_source(path)
+}
+
+pub fn link_managed_tool(status: &ToolStatus, spec: &VersionManagerSpec, versions: &[ManagedVersion]) -> Option<ManagedToolInfo> {
+    let versions: Vec<&ManagedVersion> = versions
+        .iter()
+        .filter(|version| spec.tool.is_some() || asdf_plugin_command(&version.tool) == status.command)
+        .collect();
+    if versions.is_empty() {
+        return None;
+    }
+
+    let detected = status
+        .parsed_version
+        .as_ref()
+        .and_then(|version| version.semver.clone())
+        .or_else(|| status.version.clone());
+    let active = versions
+        .iter()
+        .find(|version| detected.as_deref() == Some(version.version.as_str()))
+        .or_else(|| versions.iter().find(|version| version.is_default))
+        .map(|version| version.version.clone());
+
+    Some(ManagedToolInfo {
+        manager: spec.key.to_string(),
+        manager_name: spec.name.to_string(),
+        active_version: active.clone(),
+        other_versions: versions
+            .iter()
+            .filter(|version| Some(&version.version) != active.as_ref())
+            .map(|version| version.version.clone())
+            .collect(),
+    })
+}
+
+/// asdf / mise 插件名到命令名
+pub fn asdf_plugin_command(plugin: &str) -> &str {
+    match plugin {
+        "nodejs" => "node",
+        "golang" => "go",
+        "rust" => "rustc",
+        "maven" => "mvn",
+        "dotnet-core" => "dotnet",
+        other => other,
+    }
+}
+
+fn managed(tool: &str, version: &str, is_default: bool) -> ManagedVersion {
+    // v20.11.1 → 20.11.1，rustup 工具链名等保持原样
+    let version = match version.strip_prefix('v') {
+        Some(rest) if rest.starts_with(|ch: char| ch.is_ascii_digit()) => rest,
+        _ => version,
+    };
+    ManagedVersion {
+        tool: tool.to_string(),
+        version: version.to_string(),
+        is_default,
+    }
+}
+
+/// 每行一个版本，`*` 前缀或 default / currently using / set by 等标记表示当前默认
+fn parse_marked_lines(output: &str, tool: &str, star_is_default: bool) -> Vec<ManagedVersion> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let starred = line.starts_with('*');
+            let rest = line.trim_start_matches('*').trim();
+            let version = rest.split_whitespace().next()?;
+            // 跳过 system 与 "No installations recognized." 之类的提示
+            if !version.chars().any(|ch| ch.is_ascii_digit()) {
+                return None;
+            }
+
+            let lowered = rest.to_lowercase();
+            let is_default = (starred && star_is_default)
+                || lowered.contains("default")
+                || lowered.contains("currently using")
+                || lowered.contains("(set by");
+            Some(managed(tool, version, is_default))
+        })
+        .collect()
+}
+
+fn parse_nvm_list(output: &str) -> Vec<ManagedVersion> {
+    parse_marked_lines(output, "node", true)
+}
+
+/// nvm(sh) 用 `->` 标出当前版本；非交互 shell 中 source 后的当前版本即 default 别名
+fn parse_nvm_sh_list(output: &str) -> Vec<ManagedVersion> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let current = line.starts_with("->");
+            let version = line.trim_start_matches("->").split_whitespace().next()?;
+            if !version.starts_with('v') || !version.chars().any(|ch| ch.is_ascii_digit()) {
+                return None;
+            }
+            Some(managed("node", version, current))
+        })
+        .collect()
+}
+
+/// fnm 给每行都加 `*`，默认版本以 default 别名标出
+fn parse_fnm_list(output: &str) -> Vec<ManagedVersion> {
+    parse_marked_lines(output, "node", false)
+}
+
+fn parse_volta_list(output: &str) -> Vec<ManagedVersion> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("runtime ")?;
+            let (tool, rest) = rest.split_once('@')?;
+            let version = rest.split_whitespace().next()?;
+            Some(managed(tool, version, rest.contains("(default)")))
+        })
+        .collect()
+}
+
+fn parse_pyenv_versions(output: &str) -> Vec<ManagedVersion> {
+    parse_marked_lines(output, "python", true)
+}
+
+fn parse_rustup_toolchains(output: &str) -> Vec<ManagedVersion> {
+    parse_marked_lines(output, "rustc", false)
+}
+
+/// 不缩进的行为插件名，其下缩进行为该插件的版本
+fn parse_asdf_list(output: &str) -> Vec<ManagedVersion> {
+    let mut plugin = String::new();
+    let mut versions = Vec::new();
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        if !line.starts_with(char::is_whitespace) {
+            plugin = line.trim().to_string();
+            continue;
+        }
+        if !plugin.is_empty() {
+            versions.extend(parse_marked_lines(line, &plugin, true));
+        }
+    }
+    versions
+}
+
+fn parse_mise_list(output: &str) -> Vec<ManagedVersion> {
+    let Ok(Value::Object(tools)) = serde_json::from_str::<Value>(output) else {
+        return Vec::new();
+    };
+
+    tools
+        .iter()
+        .flat_map(|(tool, entries)| {
+            entries
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|entry| entry.get("installed").and_then(Value::as_bool).unwrap_or(true))
+                .filter_map(|entry| {
+                    let version = entry.get("version").and_then(Value::as_str)?;
+                    let active = entry.get("active").and_then(Value::as_bool).unwrap_or(false);
+                    Some(managed(tool, version, active))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// 输出为 `java/21.0.2-tem/` 形式的目录与 `java/current -> 21.0.2-tem` 形式的链接
+fn parse_sdkman_list(output: &str) -> Vec<ManagedVersion> {
+    let mut defaults: HashMap<&str, &str> = HashMap::new();
+    for line in output.lines() {
+        if let Some((link, target)) = line.split_once(" -> ") {
+            if let Some(tool) = link.trim().strip_suffix("/current") {
+                defaults.insert(tool, target.trim().trim_end_matches('/').rsplit('/').next().unwrap_or_default());
+            }
+        }
+    }
+
+    output
+        .lines()
+        .filter(|line| !line.contains(" -> "))
+        .filter_map(|line| {
+            let mut parts = line.trim().trim_end_matches('/').split('/');
+            let tool = parts.next()?;
+            let version = parts.next()?;
+            if version == "current" || tool.contains('*') {
+                return None;
+            }
+            Some(managed(tool, version, defaults.get(tool) == Some(&version)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[ManagedVersion]) -> Vec<(&str, &str, bool)> {
+        list.iter()
+            .map(|item| (item.tool.as_str(), item.version.as_str(), item.is_default))
+            .collect()
+    }
+
+    #[test]
+    fn parse_node_manager_listings() {
+        let nvm = "\n    * 20.11.1 (Currently using 64-bit executable)\n      18.19.0\n";
+        assert_eq!(versions(&parse_nvm_list(nvm)), vec![("node", "20.11.1", true), ("node", "18.19.0", false)]);
+        assert!(parse_nvm_list("\nNo installations recognized.\n").is_empty());
+
+        let nvm_sh = "->     v20.11.1 *\n       v18.19.0 *\n         system\n";
+        assert_eq!(
+            versions(&parse_nvm_sh_list(nvm_sh)),
+            vec![("node", "20.11.1", true), ("node", "18.19.0", false)]
+        );
+        assert!(parse_nvm_sh_list("            N/A\n").is_empty());
+
+        let fnm = "* v18.19.0\n* v20.11.1 default\n* system\n";
+        assert_eq!(versions(&parse_fnm_list(fnm)), vec![("node", "18.19.0", false), ("node", "20.11.1", true)]);
+
+        let volta = "runtime node@18.19.0\nruntime node@20.11.1 (default)\n";
+        assert_eq!(versions(&parse_volta_list(volta)), vec![("node", "18.19.0", false), ("node", "20.11.1", true)]);
+    }
+
+    #[test]
+    fn parse_python_rust_and_multi_tool_listings() {
+        let pyenv = "  3.10.11\n* 3.11.7 (set by C:\\Users\\dev\\.pyenv\\pyenv-win\\version)\n  system\n";
+        assert_eq!(
+            versions(&parse_pyenv_versions(pyenv)),
+            vec![("python", "3.10.11", false), ("python", "3.11.7", true)]
+        );
+
+        let rustup = "stable-x86_64-pc-windows-msvc (active, default)\nnightly-2024-01-01-x86_64-pc-windows-msvc\n";
+        assert_eq!(
+            versions(&parse_rustup_toolchains(rustup)),
+            vec![
+                ("rustc", "stable-x86_64-pc-windows-msvc", true),
+                ("rustc", "nightly-2024-01-01-x86_64-pc-windows-msvc", false),
+            ]
+        );
+
+        let asdf = "nodejs\n  18.19.0\n *20.11.1\npython\n  No versions installed\n";
+        assert_eq!(
+            versions(&parse_asdf_list(asdf)),
+            vec![("nodejs", "18.19.0", false), ("nodejs", "20.11.1", true)]
+        );
+
+        let mise = r#"{"node":[{"version":"20.11.1","installed":true,"active":true},{"version":"22.0.0","installed":false}],"python":[{"version":"3.12.2","installed":true}]}"#;
+        assert_eq!(
+            versions(&parse_mise_list(mise)),
+            vec![("node", "20.11.1", true), ("python", "3.12.2", false)]
+        );
+
+        let sdkman = "java/17.0.10-tem/\njava/21.0.2-tem/\nmaven/3.9.6/\njava/current -> /home/dev/.sdkman/candidates/java/21.0.2-tem\n*/current -> \n";
+        assert_eq!(
+            versions(&parse_sdkman_list(sdkman)),
+            vec![("java", "17.0.10-tem", false), ("java", "21.0.2-tem", true), ("maven", "3.9.6", false)]
+        );
+    }
+
+    #[test]
+    fn actions_render_arguments_and_reject_unsafe_values() {
+        let specs = version_manager_specs();
+        let mise = specs.iter().find(|spec| spec.key == "mise").unwrap();
+        assert_eq!(
+            render_manager_args(mise.default_args.unwrap(), "node", "20.11.1"),
+            vec!["use", "--global", "node@20.11.1"]
+        );
+
+        let sdkman = specs.iter().find(|spec| spec.key == "sdkman").unwrap();
+        let args = render_manager_args(sdkman.install_args.unwrap(), "java", "21.0.2-tem");
+        assert!(args[1].ends_with("&& sdk install java 21.0.2-tem"));
+
+        let nvm = specs.iter().find(|spec| spec.key == "nvm").unwrap();
+        let args = render_manager_args(nvm.default_args.unwrap(), "node", "20.11.1");
+        if cfg!(target_os = "windows") {
+            assert_eq!(args, vec!["use", "20.11.1"]);
+        } else {
+            assert!(args[1].starts_with("source ") && args[1].ends_with("&& nvm alias default 20.11.1"));
+        }
+
+        assert!(validate_argument("21.0.2-tem", "版本号").is_ok());
+        assert!(validate_argument("20; rm -rf ~", "版本号").is_err());
+        assert!(validate_argument("--force", "版本号").is_err());
+        assert!(run_version_manager_action("volta", MANAGER_ACTION_UNINSTALL, None, "20.11.1").is_err());
+        assert!(run_version_manager_action("asdf", MANAGER_ACTION_INSTALL, None, "20.11.1").is_err());
+    }
+
+    #[test]
+    fn link_managed_tool_counts_other_versions() {
+        let specs = version_manager_specs();
+        let fnm = specs.iter().find(|spec| spec.key == "fnm").unwrap();
+        let status = ToolStatus {
+            install_key: Some("nodejs-lts".to_string()),
+            install_path: Some(r"C:\Users\dev\AppData\Local\fnm_multishells\1234_5678\node.exe".to_string()),
+            ..ToolStatus::for_test("Node.js", "node", Some("v20.11.1")).with_semver("20.11.1")
+        };
+
+        let listed = parse_fnm_list("* v16.20.2\n* v18.19.0 default\n* v20.11.1\n* v22.1.0\n");
+        let info = link_managed_tool(&status, fnm, &listed).unwrap();
+        assert_eq!(info.manager, "fnm");
+        assert_eq!(info.active_version.as_deref(), Some("20.11.1"));
+        assert_eq!(info.other_versions, vec!["16.20.2", "18.19.0", "22.1.0"]);
+        assert_eq!(manager_source_for_path(status.install_path.as_deref().unwrap()), "fnm");
+    }
+}
@@ -1,8 +1,45 @@
 pub mod specs;
 pub mod detect;
+pub mod catalog;
+pub mod version_rules;
+pub mod resolver;
+pub mod installations;
+pub mod managers;
+pub mod cache;
+pub mod lifecycle;
+pub mod fingerprint;
+pub mod history;
+
+use crate::contracts::ToolStatus;
+use rayon::prelude::*;
 
 pub const TOOL_DETECT_TIMEOUT_MS: u64 = 1_500;
 pub const AI_TOOL_DETECT_TIMEOUT_MS: u64 = 4_500;
+/// JVM 类工具与 az / gcloud 冷启动常超过默认超时
+pub const SLOW_TOOL_DETECT_TIMEOUT_MS: u64 = 6_000;
+pub const SLOW_START_COMMANDS: [&str; 5] = ["mvn", "gradle", "az", "gcloud", "flutter"];
 
-pub use specs::default_tool_specs;
+pub use specs::{ToolSpec, UpstreamSource};
 pub use detect::detect_tool;
+pub use installations::find_all_installations;
+pub use cache::{detect_tools_cached, reprobe_timed_out_tools, ToolCacheState, TOOLS_REPROBED_EVENT};
+pub use lifecycle::{get_eol_dataset_info_internal, update_eol_dataset_internal};
+pub use managers::{asdf_plugin_command, detect_version_managers, run_version_manager_action};
+pub use catalog::{find_catalog_install_recipe, find_project_config, find_tool_spec, load_tool_catalog, read_config_document, ToolCatalog};
+
+pub fn detect_tools_parallel(specs: &[ToolSpec]) -> Result<Vec<ToolStatus>, String> {
+    let max_workers = std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(4)
+        .min(8);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(max_workers)
+        .build()
+        .map_err(|error| format!("初始化工具探测线程池失败: {}", error))?;
+
+    let mut tools = pool.install(|| specs.par_iter().map(detect_tool).collect::<Vec<_>>());
+    managers::annotate_managed_tools(&mut tools);
+    lifecycle::annotate_lifecycle(tools.iter_mut());
+    Ok(tools)
+}
@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// PATH 中找到的位置与跟随符号链接后的最终目标（如 Homebrew Cellar、nix store 路径）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedExecutable {
+    pub path: PathBuf,
+    pub target: PathBuf,
+}
+
+/// 在当前进程的 PATH 中解析命令，不启动 `where` / `which` 子进程
+pub fn resolve_executable(command: &str) -> Option<ResolvedExecutable> {
+    resolve_executable_in(command, &path_dirs(), &path_extensions())
+}
+
+/// 命令本身带目录（`./gradlew`、`C:\tools\protoc.exe`）时直接检查该路径，否则按目录顺序查找
+pub fn resolve_executable_in(command: &str, dirs: &[PathBuf], extensions: &[String]) -> Option<ResolvedExecutable> {
+    let command_path = Path::new(command);
+    let path = if command_path.components().count() > 1 || command_path.is_absolute() {
+        let parent = command_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let name = command_path.file_name()?.to_string_lossy().to_string();
+        find_executables(&name, &[parent], extensions).into_iter().next()?
+    } else {
+        find_executables(command, dirs, extensions).into_iter().next()?
+    };
+
+    Some(ResolvedExecutable {
+        target: resolve_symlinks(&path),
+        path,
+    })
+}
+
+pub fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|value| std::env::split_paths(&value).collect())
+        .unwrap_or_default()
+}
+
+/// Windows 按 PATHEXT 补全扩展名，其他平台只匹配原名
+pub fn path_extensions() -> Vec<String> {
+    if !cfg!(target_os = "windows") {
+        return vec![String::new()];
+    }
+
+    std::env::var("PATHEXT")
+        .unwrap_or_else(|_| DEFAULT_PATHEXT.to_string())
+        .split(';')
+        .map(|ext| ext.trim().to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect()
+}
+
+/// 按目录顺序逐个查找；命令已带扩展名（如 python.exe）时先按原名匹配
+pub fn find_executables(command: &str, dirs: &[PathBuf], extensions: &[String]) -> Vec<PathBuf> {
+    let lowered = command.to_ascii_lowercase();
+    let has_extension = extensions.iter().any(|ext| !ext.is_empty() && lowered.ends_with(ext.as_str()));
+
+    let mut names = Vec::new();
+    if has_extension || extensions.iter().any(String::is_empty) {
+        names.push(command.to_string());
+    }
+    if !has_extension {
+        names.extend(extensions.iter().filter(|ext| !ext.is_empty()).map(|ext| format!("{}{}", command, ext)));
+    }
+
+    dirs.iter()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .filter(|path| is_executable(path))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    // WindowsApps 下的应用执行别名是重解析点，metadata 可能读取失败
+    path.is_file() || path.symlink_metadata().is_ok_and(|metadata| !metadata.is_dir())
+}
+
+/// 跟随符号链接得到最终文件；应用执行别名等无法解析的链接保留原路径
+pub fn resolve_symlinks(path: &Path) -> PathBuf {
+    match std::fs::canonicalize(path) {
+        Ok(resolved) => strip_verbatim_prefix(resolved),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Windows 上 canonicalize 返回 `\\?\C:\...`，还原为常见写法便于展示与比较
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let text = path.to_string_lossy();
+    if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{}", rest));
+    }
+    if let Some(rest) = text.strip_prefix(r"\\?\") {
+        return PathBuf::from(rest);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_executable(path: &Path, mode: u32) {
+        std::fs::write(path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+    }
+
+    fn bin_dirs(root: &Path, names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| {
+                let dir = root.join(name);
+                std::fs::create_dir_all(&dir).unwrap();
+                dir
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_executables_skips_files_without_exec_bit() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = bin_dirs(dir.path(), &["first", "second", "third"]);
+        for (bin, mode) in dirs.iter().zip([0o755, 0o644, 0o755]) {
+            write_executable(&bin.join("python"), mode);
+        }
+
+        let found = find_executables("python", &dirs, &[String::new()]);
+        assert_eq!(found, vec![dirs[0].join("python"), dirs[2].join("python")]);
+
+        let resolved = resolve_executable_in("python", &dirs[1..], &[String::new()]).unwrap();
+        assert_eq!(resolved.path, dirs[2].join("python"));
+    }
+
+    #[test]
+    fn find_executables_applies_pathext() {
+        let dir = tempfile::tempdir().unwrap();
+        write_executable(&dir.path().join("npm.cmd"), 0o755);
+        write_executable(&dir.path().join("npm"), 0o755);
+
+        let extensions = vec![".exe".to_string(), ".cmd".to_string()];
+        let dirs = vec![dir.path().to_path_buf()];
+        assert_eq!(find_executables("npm", &dirs, &extensions), vec![dir.path().join("npm.cmd")]);
+        assert_eq!(find_executables("npm.cmd", &dirs, &extensions), vec![dir.path().join("npm.cmd")]);
+        assert!(find_executables("npm.exe", &dirs, &extensions).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_reports_final_symlink_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = bin_dirs(dir.path(), &["Cellar/node/22.1.0/bin", "bin"]);
+        let real = dirs[0].join("node");
+        write_executable(&real, 0o755);
+        // bin/node -> ../Cellar/node/22.1.0/bin/node，与 Homebrew 的布局一致
+        std::os::unix::fs::symlink("../Cellar/node/22.1.0/bin/node", dirs[1].join("node")).unwrap();
+
+        let resolved = resolve_executable_in("node", &dirs[1..], &[String::new()]).unwrap();
+        assert_eq!(resolved.path, dirs[1].join("node"));
+        assert_eq!(resolved.target, std::fs::canonicalize(&real).unwrap());
+
+        std::os::unix::fs::symlink(dir.path().join("missing"), dirs[1].join("dangling")).unwrap();
+        assert!(resolve_executable_in("dangling", &dirs[1..], &[String::new()]).is_none());
+    }
+
+    #[test]
+    fn explicit_paths_and_empty_path_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("gradlew");
+        write_executable(&script, 0o755);
+
+        let extensions = if cfg!(unix) { vec![String::new()] } else { vec![String::new(), ".bat".to_string()] };
+        let resolved = resolve_executable_in(&script.to_string_lossy(), &[], &extensions).unwrap();
+        assert_eq!(resolved.path, script);
+
+        assert!(resolve_executable_in("gradlew", &[PathBuf::new()], &extensions).is_none());
+        assert!(resolve_executable_in("gradlew", &[], &extensions).is_none());
+    }
+
+    #[test]
+    fn verbatim_prefixes_are_stripped() {
+        assert_eq!(
+            strip_verbatim_prefix(PathBuf::from(r"\\?\C:\Program Files\nodejs\node.exe")),
+            PathBuf::from(r"C:\Program Files\nodejs\node.exe")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(PathBuf::from(r"\\?\UNC\server\share\tool.exe")),
+            PathBuf::from(r"\\server\share\tool.exe")
+        );
+        assert_eq!(strip_verbatim_prefix(PathBuf::from("/usr/bin/git")), PathBuf::from("/usr/bin/git"));
+    }
+}
@@ -1,56 +1,191 @@
-#[derive(Clone, Copy)]
+use crate::contracts::ToolInstallRecipe;
+
+use super::fingerprint::VersionFingerprint;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ToolSpec {
-    pub name: &'static str,
-    pub command: &'static str,
-    pub args: &'static [&'static str],
-    pub category: &'static str,
-    pub install_key: Option<&'static str>,
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub category: String,
+    pub install_key: Option<String>,
+    /// 未设置时按分类取默认超时
+    pub timeout_ms: Option<u64>,
+    /// 超时且没有任何输出时的重试次数，未设置时冷启动慢的工具重试一次
+    pub retries: Option<u32>,
+    /// 从输出中提取版本号的正则，有捕获组时取第一组
+    pub version_regex: Option<String>,
+    /// 用户目录中定义的安装方式，内置工具走 install 模块的固定清单
+    pub install: Option<ToolInstallRecipe>,
+    /// 来自目录文件时记录文件路径，内置工具为空
+    pub source: Option<String>,
+    /// 先尝试从安装目录中的文件读取版本，未命中再执行命令
+    pub fingerprint: Option<VersionFingerprint>,
+    /// 检查更新时查询的上游，没有可靠发布源的工具为空
+    pub upstream: Option<UpstreamSource>,
+}
+
+/// 工具最新稳定版的发布来源；目录文件中写作 `github:owner/repo`、`nodejs`、`go` 或 `python`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UpstreamSource {
+    GithubRelease { owner: String, repo: String },
+    NodeDist,
+    GoDev,
+    PythonOrg,
+}
+
+impl UpstreamSource {
+    /// `none` 表示不检查更新，用于关闭内置工具的默认上游
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        let value = value.trim();
+        match value.to_ascii_lowercase().as_str() {
+            "none" => return Ok(None),
+            "nodejs" => return Ok(Some(Self::NodeDist)),
+            "go" => return Ok(Some(Self::GoDev)),
+            "python" => return Ok(Some(Self::PythonOrg)),
+            _ => {}
+        }
+
+        let repository = value
+            .strip_prefix("github:")
+            .ok_or_else(|| format!("upstream {} 无效，可选 github:owner/repo、nodejs、go、python 或 none", value))?;
+        match repository.trim().split_once('/') {
+            Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+                Ok(Some(Self::github(owner, repo)))
+            }
+            _ => Err(format!("upstream {} 无效，GitHub 仓库需写作 github:owner/repo", value)),
+        }
+    }
+
+    pub fn github(owner: &str, repo: &str) -> Self {
+        Self::GithubRelease {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        }
+    }
+
+    /// 与 parse 接受的写法一致，用于目录报告
+    pub fn to_config(&self) -> String {
+        match self {
+            Self::GithubRelease { owner, repo } => format!("github:{}/{}", owner, repo),
+            Self::NodeDist => "nodejs".to_string(),
+            Self::GoDev => "go".to_string(),
+            Self::PythonOrg => "python".to_string(),
+        }
+    }
+}
+
+/// 内置工具的默认上游，目录文件可通过 upstream 覆盖
+fn builtin_upstream(name: &str, command: &str) -> Option<UpstreamSource> {
+    let github = |owner: &str, repo: &str| Some(UpstreamSource::github(owner, repo));
+    match command {
+        "node" => Some(UpstreamSource::NodeDist),
+        "python" | "python3" => Some(UpstreamSource::PythonOrg),
+        "go" => Some(UpstreamSource::GoDev),
+        "npm" => github("npm", "cli"),
+        "pnpm" => github("pnpm", "pnpm"),
+        "bun" => github("oven-sh", "bun"),
+        "deno" => github("denoland", "deno"),
+        "pipx" => github("pypa", "pipx"),
+        "uv" => github("astral-sh", "uv"),
+        "gradle" => github("gradle", "gradle"),
+        // cargo 随 Rust 工具链发布，版本号一致
+        "rustc" | "cargo" => github("rust-lang", "rust"),
+        "gh" => github("cli", "cli"),
+        "docker" if name == "Docker Compose" => github("docker", "compose"),
+        "kubectl" => github("kubernetes", "kubernetes"),
+        "helm" => github("helm", "helm"),
+        "terraform" => github("hashicorp", "terraform"),
+        "pwsh" => github("PowerShell", "PowerShell"),
+        "cmake" => github("Kitware", "CMake"),
+        "mongosh" => github("mongodb-js", "mongosh"),
+        "gemini" => github("google-gemini", "gemini-cli"),
+        _ => None,
+    }
+}
+
+impl ToolSpec {
+    pub fn builtin(name: &str, command: &str, args: &[&str], category: &str, install_key: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: args.iter().map(|arg| (*arg).to_string()).collect(),
+            category: category.to_string(),
+            install_key: install_key.map(ToString::to_string),
+            timeout_ms: None,
+            retries: None,
+            version_regex: None,
+            install: None,
+            source: None,
+            fingerprint: None,
+            upstream: builtin_upstream(name, command),
+        }
+    }
+
+    pub fn with_fingerprint(mut self, fingerprint: VersionFingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
 }
 
 pub fn default_tool_specs() -> Vec<ToolSpec> {
     vec![
-        ToolSpec { name: "Node.js", command: "node", args: &["-v"], category: "Runtime", install_key: Some("nodejs-lts") },
-        ToolSpec { name: "npm", command: "npm", args: &["-v"], category: "Package", install_key: Some("nodejs-lts") },
-        ToolSpec { name: "pnpm", command: "pnpm", args: &["-v"], category: "Package", install_key: Some("pnpm") },
-        ToolSpec { name: "Yarn", command: "yarn", args: &["-v"], category: "Package", install_key: Some("yarn") },
-        ToolSpec { name: "Bun", command: "bun", args: &["--version"], category: "Runtime", install_key: Some("bun") },
-        ToolSpec { name: "Deno", command: "deno", args: &["--version"], category: "Runtime", install_key: Some("deno") },
-        ToolSpec { name: "Python", command: "python", args: &["--version"], category: "Language", install_key: Some("python") },
-        ToolSpec { name: "pip", command: "pip", args: &["--version"], category: "Package", install_key: Some("python") },
-        ToolSpec { name: "pipx", command: "pipx", args: &["--version"], category: "Package", install_key: Some("pipx") },
-        ToolSpec { name: "uv", command: "uv", args: &["--version"], category: "Package", install_key: Some("uv") },
-        ToolSpec { name: "Conda", command: "conda", args: &["--version"], category: "Runtime", install_key: Some("miniconda") },
-        ToolSpec { name: "Go", command: "go", args: &["version"], category: "Language", install_key: Some("go") },
-        ToolSpec { name: "Java", command: "java", args: &["-version"], category: "Language", install_key: Some("openjdk") },
-        ToolSpec { name: "Javac", command: "javac", args: &["-version"], category: "Language", install_key: Some("openjdk") },
-        ToolSpec { name: "Maven", command: "mvn", args: &["-version"], category: "Build", install_key: Some("maven") },
-        ToolSpec { name: "Gradle", command: "gradle", args: &["-v"], category: "Build", install_key: Some("gradle") },
-        ToolSpec { name: "Rust", command: "rustc", args: &["--version"], category: "Language", install_key: Some("rustup") },
-        ToolSpec { name: "Cargo", command: "cargo", args: &["--version"], category: "Build", install_key: Some("rustup") },
-        ToolSpec { name: "Git", command: "git", args: &["--version"], category: "SCM", install_key: Some("git") },
-        ToolSpec { name: "GitHub CLI", command: "gh", args: &["--version"], category: "SCM", install_key: Some("gh") },
-        ToolSpec { name: "Docker", command: "docker", args: &["--version"], category: "Container", install_key: Some("docker-desktop") },
-        ToolSpec { name: "Docker Compose", command: "docker", args: &["compose", "version"], category: "Container", install_key: Some("docker-desktop") },
-        ToolSpec { name: "kubectl", command: "kubectl", args: &["version", "--client"], category: "Container", install_key: Some("kubectl") },
-        ToolSpec { name: "Helm", command: "helm", args: &["version"], category: "Container", install_key: Some("helm") },
-        ToolSpec { name: "Terraform", command: "terraform", args: &["-version"], category: "IaC", install_key: Some("terraform") },
-        ToolSpec { name: ".NET SDK", command: "dotnet", args: &["--version"], category: "Language", install_key: Some("dotnet-sdk") },
-        ToolSpec { name: "PowerShell", command: "pwsh", args: &["--version"], category: "Shell", install_key: Some("powershell") },
-        ToolSpec { name: "VS Code", command: "code", args: &["--version"], category: "IDE", install_key: Some("vscode") },
-        ToolSpec { name: "AWS CLI", command: "aws", args: &["--version"], category: "Cloud", install_key: Some("aws-cli") },
-        ToolSpec { name: "Azure CLI", command: "az", args: &["--version"], category: "Cloud", install_key: Some("azure-cli") },
-        ToolSpec { name: "Google Cloud CLI", command: "gcloud", args: &["--version"], category: "Cloud", install_key: Some("gcloud-cli") },
-        ToolSpec { name: "Flutter", command: "flutter", args: &["--version"], category: "Mobile", install_key: Some("flutter") },
-        ToolSpec { name: "Dart", command: "dart", args: &["--version"], category: "Language", install_key: Some("dart") },
-        ToolSpec { name: "ADB", command: "adb", args: &["version"], category: "Mobile", install_key: Some("android-platform-tools") },
-        ToolSpec { name: "CMake", command: "cmake", args: &["--version"], category: "Build", install_key: Some("cmake") },
-        ToolSpec { name: "SQLite CLI", command: "sqlite3", args: &["--version"], category: "Database", install_key: Some("sqlite") },
-        ToolSpec { name: "PostgreSQL CLI", command: "psql", args: &["--version"], category: "Database", install_key: Some("postgresql") },
-        ToolSpec { name: "MySQL CLI", command: "mysql", args: &["--version"], category: "Database", install_key: Some("mysql") },
-        ToolSpec { name: "MongoDB Shell", command: "mongosh", args: &["--version"], category: "Database", install_key: Some("mongodb-shell") },
-        ToolSpec { name: "Redis CLI", command: "redis-cli", args: &["--version"], category: "Database", install_key: Some("redis") },
-        ToolSpec { name: "Claude Code", command: "claude", args: &["--version"], category: "AI", install_key: Some("claude-code") },
-        ToolSpec { name: "Codex CLI", command: "codex", args: &["--version"], category: "AI", install_key: Some("codex-cli") },
-        ToolSpec { name: "Gemini CLI", command: "gemini", args: &["--version"], category: "AI", install_key: Some("gemini-cli") },
+        ToolSpec::builtin("Node.js", "node", &["-v"], "Runtime", Some("nodejs-lts"))
+            .with_fingerprint(VersionFingerprint::NodeVersionHeader),
+        ToolSpec::builtin("npm", "npm", &["-v"], "Package", Some("nodejs-lts"))
+            .with_fingerprint(VersionFingerprint::NodePackage("npm")),
+        ToolSpec::builtin("pnpm", "pnpm", &["-v"], "Package", Some("pnpm"))
+            .with_fingerprint(VersionFingerprint::NodePackage("pnpm")),
+        ToolSpec::builtin("Yarn", "yarn", &["-v"], "Package", Some("yarn"))
+            .with_fingerprint(VersionFingerprint::NodePackage("yarn")),
+        ToolSpec::builtin("Bun", "bun", &["--version"], "Runtime", Some("bun")),
+        ToolSpec::builtin("Deno", "deno", &["--version"], "Runtime", Some("deno")),
+        ToolSpec::builtin("Python", "python", &["--version"], "Language", Some("python"))
+            .with_fingerprint(VersionFingerprint::Python),
+        ToolSpec::builtin("pip", "pip", &["--version"], "Package", Some("python")),
+        ToolSpec::builtin("pipx", "pipx", &["--version"], "Package", Some("pipx")),
+        ToolSpec::builtin("uv", "uv", &["--version"], "Package", Some("uv")),
+        ToolSpec::builtin("Conda", "conda", &["--version"], "Runtime", Some("miniconda")),
+        ToolSpec::builtin("Go", "go", &["version"], "Language", Some("go"))
+            .with_fingerprint(VersionFingerprint::GoVersionFile),
+        ToolSpec::builtin("Java", "java", &["-version"], "Language", Some("openjdk"))
+            .with_fingerprint(VersionFingerprint::JdkRelease),
+        ToolSpec::builtin("Javac", "javac", &["-version"], "Language", Some("openjdk"))
+            .with_fingerprint(VersionFingerprint::JdkRelease),
+        ToolSpec::builtin("Maven", "mvn", &["-version"], "Build", Some("maven")),
+        ToolSpec::builtin("Gradle", "gradle", &["-v"], "Build", Some("gradle")),
+        ToolSpec::builtin("Rust", "rustc", &["--version"], "Language", Some("rustup")),
+        ToolSpec::builtin("Cargo", "cargo", &["--version"], "Build", Some("rustup")),
+        ToolSpec::builtin("Git", "git", &["--version"], "SCM", Some("git")),
+        ToolSpec::builtin("GitHub CLI", "gh", &["--version"], "SCM", Some("gh")),
+        ToolSpec::builtin("Docker", "docker", &["--version"], "Container", Some("docker-desktop")),
+        ToolSpec::builtin("Docker Compose", "docker", &["compose", "version"], "Container", Some("docker-desktop")),
+        ToolSpec::builtin("kubectl", "kubectl", &["version", "--client"], "Container", Some("kubectl")),
+        ToolSpec::builtin("Helm", "helm", &["version"], "Container", Some("helm")),
+        ToolSpec::builtin("Terraform", "terraform", &["-version"], "IaC", Some("terraform")),
+        ToolSpec::builtin(".NET SDK", "dotnet", &["--version"], "Language", Some("dotnet-sdk"))
+            .with_fingerprint(VersionFingerprint::DotnetSdk),
+        ToolSpec::builtin("PowerShell", "pwsh", &["--version"], "Shell", Some("powershell")),
+        ToolSpec::builtin("VS Code", "code", &["--version"], "IDE", Some("vscode")),
+        ToolSpec::builtin("AWS CLI", "aws", &["--version"], "Cloud", Some("aws-cli")),
+        ToolSpec::builtin("Azure CLI", "az", &["--version"], "Cloud", Some("azure-cli"))
+            .with_fingerprint(VersionFingerprint::AzureCliDistInfo),
+        ToolSpec::builtin("Google Cloud CLI", "gcloud", &["--version"], "Cloud", Some("gcloud-cli"))
+            .with_fingerprint(VersionFingerprint::GcloudVersionFile),
+        ToolSpec::builtin("Flutter", "flutter", &["--version"], "Mobile", Some("flutter")),
+        ToolSpec::builtin("Dart", "dart", &["--version"], "Language", Some("dart")),
+        ToolSpec::builtin("ADB", "adb", &["version"], "Mobile", Some("android-platform-tools")),
+        ToolSpec::builtin("CMake", "cmake", &["--version"], "Build", Some("cmake")),
+        ToolSpec::builtin("SQLite CLI", "sqlite3", &["--version"], "Database", Some("sqlite")),
+        ToolSpec::builtin("PostgreSQL CLI", "psql", &["--version"], "Database", Some("postgresql")),
+        ToolSpec::builtin("MySQL CLI", "mysql", &["--version"], "Database", Some("mysql")),
+        ToolSpec::builtin("MongoDB Shell", "mongosh", &["--version"], "Database", Some("mongodb-shell")),
+        ToolSpec::builtin("Redis CLI", "redis-cli", &["--version"], "Database", Some("redis")),
+        ToolSpec::builtin("Claude Code", "claude", &["--version"], "AI", Some("claude-code"))
+            .with_fingerprint(VersionFingerprint::NodePackage("@anthropic-ai/claude-code")),
+        ToolSpec::builtin("Codex CLI", "codex", &["--version"], "AI", Some("codex-cli"))
+            .with_fingerprint(VersionFingerprint::NodePackage("@openai/codex")),
+        ToolSpec::builtin("Gemini CLI", "gemini", &["--version"], "AI", Some("gemini-cli"))
+            .with_fingerprint(VersionFingerprint::NodePackage("@google/gemini-cli")),
     ]
 }
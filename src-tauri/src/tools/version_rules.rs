@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use regex::Regex;
+
+use crate::contracts::ToolVersion;
+
+use super::specs::ToolSpec;
+
+/// 未配置专用规则时使用：取输出中第一个形如 1.2 / v1.2.3 / 2.43.0.windows.1 / 3.13.0rc1 的版本号
+const GENERIC_VERSION_PATTERN: &str = r"v?(\d+(?:\.\d+)+(?:(?:a|b|rc)\d+)?(?:[-+_.][0-9A-Za-z][0-9A-Za-z.\-]*)?)";
+
+/// 视为预发布的后缀；其余后缀（如 windows.1、desktop.1）归入 build，避免影响版本比较
+const PRERELEASE_TAGS: [&str; 8] = ["alpha", "beta", "rc", "pre", "preview", "dev", "nightly", "canary"];
+
+struct VersionRule {
+    /// 依次尝试，取第一个命中的第一个捕获组
+    patterns: &'static [&'static str],
+    /// Java 8 及更早的 1.x.y_z 版本号按 JEP 223 归一为 x.y.z
+    legacy_java: bool,
+}
+
+const GENERIC_RULE: VersionRule = VersionRule {
+    patterns: &[GENERIC_VERSION_PATTERN],
+    legacy_java: false,
+};
+
+fn version_rule(spec: &ToolSpec) -> VersionRule {
+    match spec.command.as_str() {
+        "java" => VersionRule { patterns: &[r#"version "([^"]+)""#], legacy_java: true },
+        "javac" => VersionRule { patterns: &[r"javac (\S+)"], legacy_java: true },
+        "go" => VersionRule { patterns: &[r"go(\d+(?:\.\d+)+\S*)"], legacy_java: false },
+        "kubectl" => VersionRule {
+            patterns: &[r#"GitVersion:"v([^"]+)""#, r"Client Version: v?(\S+)"],
+            legacy_java: false,
+        },
+        "helm" => VersionRule { patterns: &[r#"Version:"v([^"]+)""#], legacy_java: false },
+        // 第一行是 adb 协议版本，第二行才是 platform-tools 版本
+        "adb" => VersionRule { patterns: &[r"(?m)^Version (\d\S*)", GENERIC_VERSION_PATTERN], legacy_java: false },
+        // 5.7 及更早输出 "Ver 14.14 Distrib 5.7.44"，Ver 后是客户端协议版本
+        "mysql" => VersionRule { patterns: &[r"Distrib (\d+(?:\.\d+)+)", r"Ver (\d+(?:\.\d+)+)"], legacy_java: false },
+        _ => GENERIC_RULE,
+    }
+}
+
+/// 按用户正则或内置规则从完整输出中提取结构化版本
+pub fn parse_tool_version(spec: &ToolSpec, output: &str) -> Option<ToolVersion> {
+    let rule = version_rule(spec);
+    let captured = spec
+        .version_regex
+        .as_deref()
+        .and_then(|pattern| capture_first(pattern, output))
+        .or_else(|| rule.patterns.iter().find_map(|pattern| capture_first(pattern, output)))?;
+
+    normalize_version(&captured, rule.legacy_java)
+}
+
+/// 每次探测都会用到同一批规则，编译结果按模式缓存；无效的用户正则同样只编译一次
+fn cached_regex(pattern: &str) -> Option<Regex> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<Regex>>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).ok())
+        .clone()
+}
+
+fn capture_first(pattern: &str, output: &str) -> Option<String> {
+    let regex = cached_regex(pattern)?;
+    let captures = regex.captures(output)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|value| value.as_str().trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn normalize_version(text: &str, legacy_java: bool) -> Option<ToolVersion> {
+    let raw = text.trim().to_string();
+    let body = raw.strip_prefix(['v', 'V']).unwrap_or(&raw);
+
+    let numeric_end = body
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(body.len());
+    let (numeric, rest) = body.split_at(numeric_end);
+    let numeric = numeric.trim_end_matches('.');
+    if numeric.is_empty() {
+        return None;
+    }
+
+    let mut parts: Vec<u64> = Vec::new();
+    for piece in numeric.split('.') {
+        parts.push(piece.parse().ok()?);
+    }
+
+    let mut rest = rest.to_string();
+    if legacy_java && parts.len() >= 2 && parts[0] == 1 {
+        parts.remove(0);
+        if let Some(update) = rest.strip_prefix('_') {
+            let digits: String = update.chars().take_while(char::is_ascii_digit).collect();
+            if let Ok(update) = digits.parse::<u64>() {
+                parts.truncate(1);
+                parts.extend([0, update]);
+                rest = update_suffix(update, &rest);
+            }
+        }
+    }
+
+    let mut build_parts: Vec<String> = parts.iter().skip(3).map(ToString::to_string).collect();
+    let mut prerelease = None;
+
+    if let Some((tag, remaining)) = attached_prerelease(&rest) {
+        prerelease = Some(tag);
+        rest = remaining;
+    }
+
+    let suffix = rest.trim_start_matches(['-', '+', '_', '.', ' ']);
+    if !suffix.is_empty() {
+        let is_prerelease = rest.starts_with('-')
+            && PRERELEASE_TAGS
+                .iter()
+                .any(|tag| suffix.to_ascii_lowercase().starts_with(tag));
+        if is_prerelease && prerelease.is_none() {
+            prerelease = Some(suffix.to_string());
+        } else {
+            build_parts.push(suffix.to_string());
+        }
+    }
+
+    let major = parts.first().copied();
+    let minor = parts.get(1).copied();
+    let patch = parts.get(2).copied();
+
+    let mut semver = format!("{}.{}.{}", major.unwrap_or(0), minor.unwrap_or(0), patch.unwrap_or(0));
+    if let Some(prerelease) = prerelease.filter(|value| semver::Prerelease::new(value).is_ok()) {
+        semver = format!("{}-{}", semver, prerelease);
+    }
+
+    Some(ToolVersion {
+        raw,
+        semver: Some(semver),
+        major,
+        minor,
+        patch,
+        build: Some(build_parts.join(".")).filter(|build| !build.is_empty()),
+    })
+}
+
+/// PEP 440 风格的 `3.13.0rc1` / `3.12.0a7` / `3.12.0b2` 紧跟数字，归一为 `rc.1` / `alpha.7` / `beta.2`
+fn attached_prerelease(rest: &str) -> Option<(String, String)> {
+    let tag_end = rest.find(|ch: char| !ch.is_ascii_alphabetic())?;
+    let (tag, after_tag) = rest.split_at(tag_end);
+    let tag = match tag.to_ascii_lowercase().as_str() {
+        "a" | "alpha" => "alpha",
+        "b" | "beta" => "beta",
+        "rc" | "c" => "rc",
+        _ => return None,
+    };
+
+    let number_end = after_tag
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(after_tag.len());
+    let (number, remaining) = after_tag.split_at(number_end);
+    let number: u64 = number.parse().ok()?;
+    Some((format!("{}.{}", tag, number), remaining.to_string()))
+}
+
+/// `_392-b08` 去掉已并入 patch 的更新号后剩余 `-b08`
+fn update_suffix(update: u64, rest: &str) -> String {
+    rest.trim_start_matches('_')
+        .strip_prefix(&update.to_string())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::specs::default_tool_specs;
+
+    /// 各工具在 Windows 上的真实输出（Java 为 stderr）
+    const CAPTURED_OUTPUTS: &[(&str, &str, &str, Option<&str>)] = &[
+        ("Node.js", "v20.11.1\n", "20.11.1", None),
+        ("npm", "10.2.4\n", "10.2.4", None),
+        ("pnpm", "9.1.0\n", "9.1.0", None),
+        ("Yarn", "1.22.21\n", "1.22.21", None),
+        ("Bun", "1.1.8\n", "1.1.8", None),
+        ("Deno", "deno 1.43.1 (release, x86_64-pc-windows-msvc)\nv8 12.4.254.12\ntypescript 5.4.3\n", "1.43.1", None),
+        ("Python", "Python 3.12.2\n", "3.12.2", None),
+        ("pip", "pip 24.0 from C:\\Python312\\Lib\\site-packages\\pip (python 3.12)\n", "24.0.0", None),
+        ("pipx", "1.5.0\n", "1.5.0", None),
+        ("uv", "uv 0.4.18 (7b55e9790 2024-10-01)\n", "0.4.18", None),
+        ("Conda", "conda 24.1.2\n", "24.1.2", None),
+        ("Go", "go version go1.22.1 windows/amd64\n", "1.22.1", None),
+        (
+            "Java",
+            "openjdk version \"21.0.2\" 2024-01-16 LTS\nOpenJDK Runtime Environment Microsoft-8902769 (build 21.0.2+13-LTS)\nOpenJDK 64-Bit Server VM Microsoft-8902769 (build 21.0.2+13-LTS, mixed mode, sharing)\n",
+            "21.0.2",
+            None,
+        ),
+        ("Javac", "javac 21.0.2\n", "21.0.2", None),
+        (
+            "Maven",
+            "Apache Maven 3.9.6 (bc0240f3c744dd6b6ec2920b3cd08dcc295161ae)\nMaven home: C:\\tools\\apache-maven-3.9.6\nJava version: 21.0.2, vendor: Microsoft\n",
+            "3.9.6",
+            None,
+        ),
+        (
+            "Gradle",
+            "\n------------------------------------------------------------\nGradle 8.6\n------------------------------------------------------------\n\nBuild time:   2024-02-02 16:47:16 UTC\nKotlin:       1.9.20\n",
+            "8.6.0",
+            None,
+        ),
+        ("Rust", "rustc 1.77.0 (aedd173a2 2024-03-17)\n", "1.77.0", None),
+        ("Cargo", "cargo 1.77.0 (3fe68eabf 2024-02-29)\n", "1.77.0", None),
+        ("Git", "git version 2.43.0.windows.1\n", "2.43.0", Some("windows.1")),
+        ("GitHub CLI", "gh version 2.45.0 (2024-03-04)\nhttps://github.com/cli/cli/releases/tag/v2.45.0\n", "2.45.0", None),
+        ("Docker", "Docker version 25.0.3, build 4debf41\n", "25.0.3", None),
+        ("Docker Compose", "Docker Compose version v2.24.6-desktop.1\n", "2.24.6", Some("desktop.1")),
+        ("kubectl", "Client Version: v1.29.2\nKustomize Version: v5.0.4-0.20230601165947-6ce0bf390ce3\n", "1.29.2", None),
+        (
+            "Helm",
+            "version.BuildInfo{Version:\"v3.14.2\", GitCommit:\"c309b6f0ff63856811846ce18f3bdc93d2b4d54b\", GitTreeState:\"clean\", GoVersion:\"go1.21.7\"}\n",
+            "3.14.2",
+            None,
+        ),
+        ("Terraform", "Terraform v1.7.4\non windows_amd64\n", "1.7.4", None),
+        (".NET SDK", "8.0.201\n", "8.0.201", None),
+        ("PowerShell", "PowerShell 7.4.1\n", "7.4.1", None),
+        ("VS Code", "1.87.0\n019f4d1419fbc8219a181fab7892ebccf7ee29a2\nx64\n", "1.87.0", None),
+        ("AWS CLI", "aws-cli/2.15.25 Python/3.11.8 Windows/10 exe/AMD64 prompt/off\n", "2.15.25", None),
+        (
+            "Azure CLI",
+            "azure-cli                         2.58.0\n\ncore                              2.58.0\ntelemetry                          1.1.0\n\nDependencies:\nmsal                            1.26.0\n",
+            "2.58.0",
+            None,
+        ),
+        ("Google Cloud CLI", "Google Cloud SDK 467.0.0\nbq 2.0.101\ncore 2024.03.01\ngcloud-crc32c 1.0.0\ngsutil 5.27\n", "467.0.0", None),
+        (
+            "Flutter",
+            "Flutter 3.19.3 • channel stable • https://github.com/flutter/flutter.git\nFramework • revision ba39319843 (2 weeks ago) • 2024-03-07 00:08:09 -0600\nEngine • revision 2e4ba9c6fb\nTools • Dart 3.3.1 • DevTools 2.31.1\n",
+            "3.19.3",
+            None,
+        ),
+        ("Dart", "Dart SDK version: 3.3.1 (stable) (Wed Mar 6 13:09:19 2024 +0000) on \"windows_x64\"\n", "3.3.1", None),
+        (
+            "ADB",
+            "Android Debug Bridge version 1.0.41\nVersion 35.0.0-11411520\nInstalled as C:\\Android\\platform-tools\\adb.exe\nRunning on Windows 10.0.22631\n",
+            "35.0.0",
+            Some("11411520"),
+        ),
+        ("CMake", "cmake version 3.28.3\n\nCMake suite maintained and supported by Kitware (kitware.com/cmake).\n", "3.28.3", None),
+        (
+            "SQLite CLI",
+            "3.45.1 2024-01-30 16:01:20 e876e51a0ed5c5b3126f52e532044363a014bc594cfefa87ffb5b82257ccalt1 (64-bit)\n",
+            "3.45.1",
+            None,
+        ),
+        ("PostgreSQL CLI", "psql (PostgreSQL) 16.2\n", "16.2.0", None),
+        ("MySQL CLI", "mysql  Ver 8.0.36 for Win64 on x86_64 (MySQL Community Server - GPL)\n", "8.0.36", None),
+        ("MongoDB Shell", "2.1.5\n", "2.1.5", None),
+        ("Redis CLI", "redis-cli 7.2.4\n", "7.2.4", None),
+        ("Claude Code", "1.0.35 (Claude Code)\n", "1.0.35", None),
+        ("Codex CLI", "codex-cli 0.1.2504301751\n", "0.1.2504301751", None),
+        ("Gemini CLI", "0.1.5\n", "0.1.5", None),
+    ];
+
+    #[test]
+    fn every_default_spec_parses_its_captured_output() {
+        for spec in default_tool_specs() {
+            let (_, output, semver, build) = CAPTURED_OUTPUTS
+                .iter()
+                .find(|(name, ..)| *name == spec.name)
+                .unwrap_or_else(|| panic!("missing captured output for {}", spec.name));
+
+            let version = parse_tool_version(&spec, output)
+                .unwrap_or_else(|| panic!("failed to parse version for {}", spec.name));
+            assert_eq!(version.semver.as_deref(), Some(*semver), "semver of {}", spec.name);
+            assert_eq!(version.build.as_deref(), *build, "build of {}", spec.name);
+            assert!(semver::Version::parse(semver).is_ok(), "{} is not valid semver", semver);
+        }
+    }
+
+    #[test]
+    fn legacy_java_and_mysql_formats_are_normalized() {
+        let java = ToolSpec::builtin("Java", "java", &["-version"], "Language", None);
+        let version = parse_tool_version(&java, "java version \"1.8.0_392\"\nJava(TM) SE Runtime Environment (build 1.8.0_392-b08)\n").unwrap();
+        assert_eq!(version.raw, "1.8.0_392");
+        assert_eq!(version.semver.as_deref(), Some("8.0.392"));
+        assert_eq!((version.major, version.minor, version.patch), (Some(8), Some(0), Some(392)));
+
+        let version = parse_tool_version(&java, "openjdk version \"21\" 2023-09-19\n").unwrap();
+        assert_eq!(version.semver.as_deref(), Some("21.0.0"));
+        assert_eq!(version.minor, None);
+
+        let mysql = ToolSpec::builtin("MySQL CLI", "mysql", &["--version"], "Database", None);
+        let version = parse_tool_version(&mysql, "mysql  Ver 14.14 Distrib 5.7.44, for Win64 (x86_64)\n").unwrap();
+        assert_eq!(version.semver.as_deref(), Some("5.7.44"));
+    }
+
+    #[test]
+    fn prerelease_suffixes_stay_in_semver_and_user_regex_wins() {
+        let version = normalize_version("v9.0.100-rc.1.24452.12", false).unwrap();
+        assert_eq!(version.semver.as_deref(), Some("9.0.100-rc.1.24452.12"));
+        assert_eq!(version.build, None);
+
+        let python = ToolSpec::builtin("Python", "python", &["--version"], "Language", None);
+        let version = parse_tool_version(&python, "Python 3.13.0rc1\n").unwrap();
+        assert_eq!(version.raw, "3.13.0rc1");
+        assert_eq!(version.semver.as_deref(), Some("3.13.0-rc.1"));
+        assert_eq!(version.build, None);
+        assert_eq!(normalize_version("3.12.0a7", false).unwrap().semver.as_deref(), Some("3.12.0-alpha.7"));
+        assert_eq!(normalize_version("3.12.0b2", false).unwrap().semver.as_deref(), Some("3.12.0-beta.2"));
+        assert_eq!(normalize_version("1.1.1w", false).unwrap().build.as_deref(), Some("w"));
+
+        let mut spec = ToolSpec::builtin("protoc", "protoc", &["--version"], "Build", None);
+        spec.version_regex = Some(r"libprotoc (\S+)".to_string());
+        let version = parse_tool_version(&spec, "libprotoc 25.3\n").unwrap();
+        assert_eq!(version.semver.as_deref(), Some("25.3.0"));
+    }
+}
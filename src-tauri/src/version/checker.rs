@@ -31,7 +31,7 @@ fn create_provider(config: VersionSourceConfig) -> Box<dyn VersionSourceProvider
 }
 
 /// Check single source with timeout
-async fn check_single_source(
+pub async fn check_single_source(
     provider: Box<dyn VersionSourceProvider>,
     timeout_ms: u64,
 ) -> SourceCheckResult {
@@ -15,20 +15,46 @@ struct GithubRelease {
     draft: bool,
 }
 
+pub const GITHUB_API_BASE_URL: &str = "https://api.github.com";
+
 pub struct GithubProvider {
     config: GithubReleaseSourceConfig,
+    api_base_url: String,
+    latest_only: bool,
 }
 
 impl GithubProvider {
     pub fn new(config: GithubReleaseSourceConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            api_base_url: GITHUB_API_BASE_URL.to_string(),
+            latest_only: false,
+        }
+    }
+
+    /// Point the provider at a different API host (GitHub Enterprise or a local stand-in)
+    pub fn with_api_base_url(mut self, api_base_url: &str) -> Self {
+        self.api_base_url = api_base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use `/releases/latest`, which follows the release the maintainers marked as latest
+    /// instead of the most recently created one (backport releases come after newer majors)
+    pub fn latest_release_only(mut self) -> Self {
+        self.latest_only = true;
+        self
     }
 
     fn build_api_url(&self) -> String {
-        format!(
-            "https://api.github.com/repos/{}/{}/releases",
-            self.config.owner, self.config.repo
-        )
+        let url = format!(
+            "{}/repos/{}/{}/releases",
+            self.api_base_url, self.config.owner, self.config.repo
+        );
+        if self.latest_only {
+            format!("{}/latest", url)
+        } else {
+            url
+        }
     }
 
     fn filter_releases(&self, releases: Vec<GithubRelease>) -> Option<GithubRelease> {
@@ -87,10 +113,18 @@ impl VersionSourceProvider for GithubProvider {
             )));
         }
 
-        let releases: Vec<GithubRelease> = response
-            .json()
-            .await
-            .map_err(|e| VersionError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+        let releases: Vec<GithubRelease> = if self.latest_only {
+            let release: GithubRelease = response
+                .json()
+                .await
+                .map_err(|e| VersionError::Parse(format!("Failed to parse GitHub response: {}", e)))?;
+            vec![release]
+        } else {
+            response
+                .json()
+                .await
+                .map_err(|e| VersionError::Parse(format!("Failed to parse GitHub response: {}", e)))?
+        };
 
         let latest_release = self
             .filter_releases(releases)
@@ -128,6 +162,20 @@ mod tests {
             provider.build_api_url(),
             "https://api.github.com/repos/nginx/nginx/releases"
         );
+
+        let config = GithubReleaseSourceConfig {
+            owner: "cli".to_string(),
+            repo: "cli".to_string(),
+            include_prerelease: false,
+            token: None,
+        };
+        let provider = GithubProvider::new(config)
+            .with_api_base_url("http://127.0.0.1:9000/")
+            .latest_release_only();
+        assert_eq!(
+            provider.build_api_url(),
+            "http://127.0.0.1:9000/repos/cli/cli/releases/latest"
+        );
     }
 
     #[test]
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use crate::contracts::{VersionCandidate, VersionSourceKind};
+use crate::version::errors::{VersionError, VersionResult};
+use crate::version::source_trait::{fetch_json, VersionSourceProvider};
+use serde::Deserialize;
+
+pub const GO_DEV_BASE_URL: &str = "https://go.dev";
+
+#[derive(Debug, Deserialize)]
+struct GoRelease {
+    version: String,
+    stable: bool,
+}
+
+/// Reads the go.dev download index (`/dl/?mode=json`), which lists supported releases newest first
+pub struct GoDevProvider {
+    base_url: String,
+}
+
+impl GoDevProvider {
+    pub fn new() -> Self {
+        Self {
+            base_url: GO_DEV_BASE_URL.to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+impl Default for GoDevProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VersionSourceProvider for GoDevProvider {
+    fn source_kind(&self) -> VersionSourceKind {
+        VersionSourceKind::GoDev
+    }
+
+    async fn fetch_latest(&self) -> VersionResult<VersionCandidate> {
+        let url = format!("{}/dl/?mode=json", self.base_url);
+        let releases: Vec<GoRelease> = fetch_json(&url, self.timeout_ms(), "go.dev download index").await?;
+        let release = releases
+            .into_iter()
+            .find(|release| release.stable)
+            .ok_or_else(|| VersionError::Parse("No stable Go release found".to_string()))?;
+
+        Ok(VersionCandidate {
+            source: VersionSourceKind::GoDev,
+            raw_reference: Some(format!("{}/doc/devel/release#{}", self.base_url, release.version)),
+            version: release.version,
+            digest: None,
+            release_notes: None,
+            published_at: None,
+        })
+    }
+}
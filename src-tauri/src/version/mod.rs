@@ -6,11 +6,16 @@ pub mod docker_hub;
 pub mod github;
 pub mod git_checker;
 pub mod custom_api;
+pub mod nodejs_dist;
+pub mod go_dev;
+pub mod python_org;
 pub mod checker;
 pub mod updater;
 pub mod rollback;
 pub mod health_check;
+pub mod tool_updates;
 
 pub use state::*;
 pub use checker::check_image_version;
 pub use updater::update_image_and_restart;
+pub use tool_updates::check_tool_updates_internal;
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use crate::contracts::{VersionCandidate, VersionSourceKind};
+use crate::version::errors::{VersionError, VersionResult};
+use crate::version::source_trait::{fetch_json, VersionSourceProvider};
+use serde::Deserialize;
+use serde_json::Value;
+
+pub const NODEJS_DIST_BASE_URL: &str = "https://nodejs.org/dist";
+
+#[derive(Debug, Deserialize)]
+struct NodeDistRelease {
+    version: String,
+    date: Option<String>,
+    /// `false` for Current releases, the codename (e.g. "Iron") for LTS lines
+    #[serde(default)]
+    lts: Value,
+}
+
+impl NodeDistRelease {
+    fn is_lts(&self) -> bool {
+        self.lts.is_string()
+    }
+}
+
+/// Reads `index.json` from the Node.js distribution site, which lists releases newest first
+pub struct NodeDistProvider {
+    base_url: String,
+    lts_only: bool,
+}
+
+impl NodeDistProvider {
+    pub fn new(lts_only: bool) -> Self {
+        Self {
+            base_url: NODEJS_DIST_BASE_URL.to_string(),
+            lts_only,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    fn select_release(&self, releases: Vec<NodeDistRelease>) -> Option<NodeDistRelease> {
+        releases.into_iter().find(|release| !self.lts_only || release.is_lts())
+    }
+}
+
+#[async_trait]
+impl VersionSourceProvider for NodeDistProvider {
+    fn source_kind(&self) -> VersionSourceKind {
+        VersionSourceKind::NodeDist
+    }
+
+    async fn fetch_latest(&self) -> VersionResult<VersionCandidate> {
+        let url = format!("{}/index.json", self.base_url);
+        let releases: Vec<NodeDistRelease> = fetch_json(&url, self.timeout_ms(), "Node.js dist index").await?;
+        let release = self
+            .select_release(releases)
+            .ok_or_else(|| VersionError::Parse("No matching Node.js release found".to_string()))?;
+
+        Ok(VersionCandidate {
+            source: VersionSourceKind::NodeDist,
+            raw_reference: Some(format!("{}/{}/", self.base_url, release.version)),
+            version: release.version,
+            digest: None,
+            release_notes: None,
+            published_at: release.date,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_release_respects_lts_filter() {
+        let index = r#"[
+            {"version":"v22.3.0","date":"2024-06-11","lts":false},
+            {"version":"v20.15.0","date":"2024-06-20","lts":"Iron"}
+        ]"#;
+
+        let releases: Vec<NodeDistRelease> = serde_json::from_str(index).unwrap();
+        let latest = NodeDistProvider::new(false).select_release(releases).unwrap();
+        assert_eq!(latest.version, "v22.3.0");
+
+        let releases: Vec<NodeDistRelease> = serde_json::from_str(index).unwrap();
+        let lts = NodeDistProvider::new(true).select_release(releases).unwrap();
+        assert_eq!(lts.version, "v20.15.0");
+    }
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use crate::contracts::{VersionCandidate, VersionSourceKind};
+use crate::version::errors::{VersionError, VersionResult};
+use crate::version::source_trait::{fetch_json, VersionSourceProvider};
+use serde::Deserialize;
+
+pub const PYTHON_ORG_BASE_URL: &str = "https://www.python.org";
+
+#[derive(Debug, Deserialize)]
+struct PythonRelease {
+    /// e.g. "Python 3.12.4"
+    name: String,
+    /// Major version number
+    version: u64,
+    pre_release: bool,
+    release_date: Option<String>,
+    release_page: Option<String>,
+}
+
+impl PythonRelease {
+    fn numbers(&self) -> Vec<u64> {
+        self.name
+            .trim_start_matches("Python")
+            .trim()
+            .split('.')
+            .map_while(|part| part.parse().ok())
+            .collect()
+    }
+}
+
+/// Reads the python.org downloads API; releases are not ordered, so the highest Python 3 version wins
+pub struct PythonOrgProvider {
+    base_url: String,
+}
+
+impl PythonOrgProvider {
+    pub fn new() -> Self {
+        Self {
+            base_url: PYTHON_ORG_BASE_URL.to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    fn select_release(releases: Vec<PythonRelease>) -> Option<PythonRelease> {
+        releases
+            .into_iter()
+            .filter(|release| release.version == 3 && !release.pre_release && release.numbers().len() >= 2)
+            .max_by_key(PythonRelease::numbers)
+    }
+}
+
+impl Default for PythonOrgProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VersionSourceProvider for PythonOrgProvider {
+    fn source_kind(&self) -> VersionSourceKind {
+        VersionSourceKind::PythonOrg
+    }
+
+    async fn fetch_latest(&self) -> VersionResult<VersionCandidate> {
+        let url = format!(
+            "{}/api/v2/downloads/release/?is_published=true&pre_release=false",
+            self.base_url
+        );
+        let releases: Vec<PythonRelease> = fetch_json(&url, self.timeout_ms(), "python.org downloads API").await?;
+        let release = Self::select_release(releases)
+            .ok_or_else(|| VersionError::Parse("No stable Python 3 release found".to_string()))?;
+
+        Ok(VersionCandidate {
+            source: VersionSourceKind::PythonOrg,
+            version: release.name.trim_start_matches("Python").trim().to_string(),
+            digest: None,
+            release_notes: None,
+            published_at: release.release_date,
+            raw_reference: release.release_page,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_release_picks_highest_python3() {
+        let releases: Vec<PythonRelease> = serde_json::from_str(
+            r#"[
+                {"name":"Python 3.12.4","version":3,"pre_release":false,"release_date":"2024-06-06T00:00:00Z","release_page":null},
+                {"name":"Python 3.9.19","version":3,"pre_release":false,"release_date":"2024-03-19T00:00:00Z","release_page":null},
+                {"name":"Python 3.13.0b2","version":3,"pre_release":true,"release_date":null,"release_page":null},
+                {"name":"Python 2.7.18","version":2,"pre_release":false,"release_date":null,"release_page":null},
+                {"name":"Python 3.12.10","version":3,"pre_release":false,"release_date":null,"release_page":null}
+            ]"#,
+        )
+        .unwrap();
+
+        let latest = PythonOrgProvider::select_release(releases).unwrap();
+        assert_eq!(latest.name, "Python 3.12.10");
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use crate::contracts::{VersionCandidate, VersionSourceKind};
-use crate::version::errors::VersionResult;
+use crate::version::errors::{VersionError, VersionResult};
 
 /// Version source provider trait
 /// All version sources must implement this trait
@@ -17,3 +17,33 @@ pub trait VersionSourceProvider: Send + Sync {
         8000 // Default 8 seconds
     }
 }
+
+/// GET a JSON document for providers that only need a plain anonymous request
+pub async fn fetch_json<T: serde::de::DeserializeOwned>(url: &str, timeout_ms: u64, label: &str) -> VersionResult<T> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .user_agent("DevEnvProbe/1.0")
+        .build()
+        .map_err(|e| VersionError::Http(e.to_string()))?;
+
+    let response = client.get(url).send().await.map_err(|e| {
+        if e.is_timeout() {
+            VersionError::SourceTimeout(format!("{} timeout: {}", label, url))
+        } else {
+            VersionError::SourceUnavailable(format!("{} error: {}", label, e))
+        }
+    })?;
+
+    if !response.status().is_success() {
+        return Err(VersionError::SourceUnavailable(format!(
+            "{} returned status: {}",
+            label,
+            response.status()
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| VersionError::Parse(format!("Failed to parse {} response: {}", label, e)))
+}
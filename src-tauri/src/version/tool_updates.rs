@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use futures::future::join_all;
+use regex::Regex;
+
+use crate::contracts::{GithubReleaseSourceConfig, SourceCheckResult, ToolStatus, ToolUpdateStatus};
+use crate::tools::{detect_tools_cached, load_tool_catalog, ToolCacheState, ToolSpec, UpstreamSource};
+use crate::version::checker::check_single_source;
+use crate::version::github::{GithubProvider, GITHUB_API_BASE_URL};
+use crate::version::go_dev::{GoDevProvider, GO_DEV_BASE_URL};
+use crate::version::nodejs_dist::{NodeDistProvider, NODEJS_DIST_BASE_URL};
+use crate::version::python_org::{PythonOrgProvider, PYTHON_ORG_BASE_URL};
+use crate::version::source_trait::VersionSourceProvider;
+
+pub const TOOL_UPDATE_SOURCE_TIMEOUT_MS: u64 = 8_000;
+
+/// Where the latest stable release of a tool is published
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ToolUpstream {
+    GithubRelease { owner: String, repo: String },
+    /// Node.js even majors become LTS; users on an LTS line are compared against the latest LTS
+    NodeDist { lts_only: bool },
+    GoDev,
+    PythonOrg,
+}
+
+/// Base URLs of the upstream sources, overridable so tests can use a local HTTP stand-in
+#[derive(Debug, Clone)]
+pub struct UpstreamEndpoints {
+    pub github: String,
+    pub nodejs: String,
+    pub go: String,
+    pub python: String,
+}
+
+impl Default for UpstreamEndpoints {
+    fn default() -> Self {
+        Self {
+            github: GITHUB_API_BASE_URL.to_string(),
+            nodejs: NODEJS_DIST_BASE_URL.to_string(),
+            go: GO_DEV_BASE_URL.to_string(),
+            python: PYTHON_ORG_BASE_URL.to_string(),
+        }
+    }
+}
+
+/// Resolve the upstream declared on the tool's spec; the Node.js LTS line depends on the installed major
+pub fn tool_upstream(spec: &ToolSpec, tool: &ToolStatus) -> Option<ToolUpstream> {
+    match spec.upstream.as_ref()? {
+        UpstreamSource::NodeDist => Some(ToolUpstream::NodeDist {
+            lts_only: version_numbers(tool.version.as_deref()?)?.first()? % 2 == 0,
+        }),
+        UpstreamSource::PythonOrg => Some(ToolUpstream::PythonOrg),
+        UpstreamSource::GoDev => Some(ToolUpstream::GoDev),
+        UpstreamSource::GithubRelease { owner, repo } => Some(ToolUpstream::GithubRelease {
+            owner: owner.clone(),
+            repo: repo.clone(),
+        }),
+    }
+}
+
+fn create_provider(upstream: &ToolUpstream, endpoints: &UpstreamEndpoints) -> Box<dyn VersionSourceProvider> {
+    match upstream {
+        ToolUpstream::GithubRelease { owner, repo } => Box::new(
+            GithubProvider::new(GithubReleaseSourceConfig {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                include_prerelease: false,
+                token: None,
+            })
+            .with_api_base_url(&endpoints.github)
+            .latest_release_only(),
+        ),
+        ToolUpstream::NodeDist { lts_only } => {
+            Box::new(NodeDistProvider::new(*lts_only).with_base_url(&endpoints.nodejs))
+        }
+        ToolUpstream::GoDev => Box::new(GoDevProvider::new().with_base_url(&endpoints.go)),
+        ToolUpstream::PythonOrg => Box::new(PythonOrgProvider::new().with_base_url(&endpoints.python)),
+    }
+}
+
+/// First dotted number in a version string: "go1.22.4" -> [1, 22, 4], "bun-v1.1.17" -> [1, 1, 17]
+pub fn version_numbers(text: &str) -> Option<Vec<u64>> {
+    let pattern = Regex::new(r"\d+(?:\.\d+)*").ok()?;
+    let found = pattern.find(text)?;
+    found.as_str().split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Missing trailing components count as zero, so 1.22 and 1.22.0 are equal
+pub fn is_newer(latest: &str, current: &str) -> Option<bool> {
+    let latest = version_numbers(latest)?;
+    let current = version_numbers(current)?;
+    let length = latest.len().max(current.len());
+    let pad = |numbers: &[u64]| (0..length).map(|index| numbers.get(index).copied().unwrap_or(0)).collect::<Vec<_>>();
+    Some(pad(&latest) > pad(&current))
+}
+
+fn update_status(tool: &ToolStatus, upstream: Option<&ToolUpstream>, result: Option<&SourceCheckResult>) -> ToolUpdateStatus {
+    let current_version = tool
+        .parsed_version
+        .as_ref()
+        .and_then(|parsed| parsed.semver.clone())
+        .or_else(|| tool.version.clone());
+    let latest = result.and_then(|result| result.latest.as_ref());
+    let latest_version = latest.and_then(|candidate| version_numbers(&candidate.version)).map(|numbers| {
+        numbers.iter().map(ToString::to_string).collect::<Vec<_>>().join(".")
+    });
+    let has_update = match (&latest_version, &current_version) {
+        (Some(latest), Some(current)) => is_newer(latest, current).unwrap_or(false),
+        _ => false,
+    };
+
+    ToolUpdateStatus {
+        name: tool.name.clone(),
+        command: tool.command.clone(),
+        current_version,
+        latest_version,
+        source: upstream.and(result).map(|result| result.source.clone()),
+        reference: latest.and_then(|candidate| candidate.raw_reference.clone()),
+        has_update,
+        error: result.filter(|result| !result.ok).and_then(|result| result.error_message.clone()),
+    }
+}
+
+/// Compare installed tools against their upstream sources; each distinct source is queried once
+pub async fn check_tool_updates_with(
+    specs: &[ToolSpec],
+    tools: &[ToolStatus],
+    endpoints: &UpstreamEndpoints,
+) -> Vec<ToolUpdateStatus> {
+    let installed: Vec<(&ToolStatus, Option<ToolUpstream>)> = tools
+        .iter()
+        .filter(|tool| tool.installed)
+        .map(|tool| {
+            let spec = specs.iter().find(|spec| spec.name == tool.name);
+            let upstream = spec.and_then(|spec| tool_upstream(spec, tool));
+            (tool, upstream)
+        })
+        .collect();
+
+    let mut upstreams: Vec<ToolUpstream> = Vec::new();
+    for upstream in installed.iter().filter_map(|(_, upstream)| upstream.clone()) {
+        if !upstreams.contains(&upstream) {
+            upstreams.push(upstream);
+        }
+    }
+
+    let checks = upstreams.iter().map(|upstream| {
+        check_single_source(create_provider(upstream, endpoints), TOOL_UPDATE_SOURCE_TIMEOUT_MS)
+    });
+    let results: HashMap<ToolUpstream, SourceCheckResult> = upstreams.iter().cloned().zip(join_all(checks).await).collect();
+
+    installed
+        .iter()
+        .map(|(tool, upstream)| {
+            let result = upstream.as_ref().and_then(|upstream| results.get(upstream));
+            update_status(tool, upstream.as_ref(), result)
+        })
+        .collect()
+}
+
+pub async fn check_tool_updates_internal(cache: ToolCacheState) -> Result<Vec<ToolUpdateStatus>, String> {
+    let (specs, tools) = tokio::task::spawn_blocking(move || {
+        let catalog = load_tool_catalog(None);
+        let tools = detect_tools_cached(&catalog.specs, &cache, false)?;
+        Ok::<_, String>((catalog.specs, tools))
+    })
+    .await
+    .map_err(|error| format!("工具探测任务失败: {}", error))??;
+
+    Ok(check_tool_updates_with(&specs, &tools, &UpstreamEndpoints::default()).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::specs::default_tool_specs;
+    use std::io::{Read, Write};
+
+    fn tool(name: &str, command: &str, version: &str) -> ToolStatus {
+        ToolStatus::for_test(name, command, Some(version))
+    }
+
+    /// Minimal HTTP stand-in: answers each request with the body registered for its path
+    fn serve(routes: Vec<(&'static str, &'static str)>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buffer = [0u8; 4096];
+                let length = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..length]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(version_numbers("go version go1.22.4 linux/amd64"), Some(vec![1, 22, 4]));
+        assert_eq!(version_numbers("bun-v1.1.17"), Some(vec![1, 1, 17]));
+        assert_eq!(is_newer("1.22.10", "1.22.9"), Some(true));
+        assert_eq!(is_newer("1.22", "1.22.0"), Some(false));
+        assert_eq!(is_newer("v2.0.0", "10.0"), Some(false));
+        assert_eq!(is_newer("latest", "1.0"), None);
+    }
+
+    #[test]
+    fn node_follows_lts_line_of_installed_major() {
+        let specs = default_tool_specs();
+        let spec = |name: &str| specs.iter().find(|spec| spec.name == name).unwrap();
+        assert_eq!(
+            tool_upstream(spec("Node.js"), &tool("Node.js", "node", "v20.11.1")),
+            Some(ToolUpstream::NodeDist { lts_only: true })
+        );
+        assert_eq!(
+            tool_upstream(spec("Node.js"), &tool("Node.js", "node", "v21.7.0")),
+            Some(ToolUpstream::NodeDist { lts_only: false })
+        );
+        assert_eq!(tool_upstream(spec("Docker"), &tool("Docker", "docker", "27.0.3")), None);
+        assert_eq!(
+            tool_upstream(spec("Docker Compose"), &tool("Docker Compose", "docker", "v2.24.6")),
+            Some(ToolUpstream::GithubRelease { owner: "docker".to_string(), repo: "compose".to_string() })
+        );
+    }
+
+    #[test]
+    fn updates_are_checked_against_local_stand_in() {
+        let base = serve(vec![
+            (
+                "/dist/index.json",
+                r#"[{"version":"v22.3.0","date":"2024-06-11","lts":false},{"version":"v20.15.0","date":"2024-06-20","lts":"Iron"}]"#,
+            ),
+            ("/dl/?mode=json", r#"[{"version":"go1.23rc1","stable":false},{"version":"go1.22.4","stable":true}]"#),
+            (
+                "/api/v2/downloads/release/?is_published=true&pre_release=false",
+                r#"[{"name":"Python 3.12.4","version":3,"pre_release":false,"release_date":null,"release_page":null}]"#,
+            ),
+            (
+                "/repos/cli/cli/releases/latest",
+                r#"{"tag_name":"v2.52.0","name":"GitHub CLI 2.52.0","body":null,"published_at":null,"prerelease":false,"draft":false}"#,
+            ),
+            (
+                "/repos/bufbuild/buf/releases/latest",
+                r#"{"tag_name":"v1.32.0","name":"v1.32.0","body":null,"published_at":null,"prerelease":false,"draft":false}"#,
+            ),
+        ]);
+        let endpoints = UpstreamEndpoints {
+            github: base.clone(),
+            nodejs: format!("{}/dist", base),
+            go: base.clone(),
+            python: base.clone(),
+        };
+        let mut specs = default_tool_specs();
+        // A catalog-declared upstream is used for tools the builtin list does not know
+        let mut buf = ToolSpec::builtin("buf", "buf", &["--version"], "Build", None);
+        buf.upstream = UpstreamSource::parse("github:bufbuild/buf").unwrap();
+        specs.push(buf);
+        let mut missing = tool("Terraform", "terraform", "Terraform v1.8.5");
+        missing.installed = false;
+        let tools = vec![
+            tool("Node.js", "node", "v20.11.1"),
+            tool("Go", "go", "go version go1.22.4 linux/amd64"),
+            tool("Python", "python", "Python 3.11.9"),
+            tool("GitHub CLI", "gh", "gh version 2.52.0 (2024-06-24)"),
+            tool("Helm", "helm", "v3.15.2"),
+            tool("Git", "git", "git version 2.45.2"),
+            tool("buf", "buf", "1.30.0"),
+            missing,
+        ];
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let updates = runtime.block_on(check_tool_updates_with(&specs, &tools, &endpoints));
+        let summary: Vec<(&str, Option<&str>, bool)> = updates
+            .iter()
+            .map(|update| (update.name.as_str(), update.latest_version.as_deref(), update.has_update))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Node.js", Some("20.15.0"), true),
+                ("Go", Some("1.22.4"), false),
+                ("Python", Some("3.12.4"), true),
+                ("GitHub CLI", Some("2.52.0"), false),
+                ("Helm", None, false),
+                ("Git", None, false),
+                ("buf", Some("1.32.0"), true),
+            ]
+        );
+        // The stand-in has no route for helm, so the 404 is reported on that tool only
+        assert!(updates[4].error.is_some());
+        assert!(updates[5].source.is_none() && updates[5].error.is_none());
+    }
+}
@@ -30,6 +30,7 @@ import {
   showLoading,
   showGlobalNotice,
 } from "./modules/shell-ui";
+import { initBackendEvents } from "./modules/backend-events";
 import {
   applyTheme,
   bindThemeSettingsPanel,
@@ -421,6 +422,10 @@ window.addEventListener("DOMContentLoaded", () => {
 
   bindGithubLink(document.getElementById("github-link") as HTMLAnchorElement | null);
 
+  void initBackendEvents().catch((error) => {
+    console.error("订阅后端事件失败:", error);
+  });
+
   const footerCheckUpdateBtn = document.getElementById("footer-check-update-btn") as HTMLButtonElement | null;
   if (footerCheckUpdateBtn) {
     bindUpdateCheckAction(footerCheckUpdateBtn, "footer");
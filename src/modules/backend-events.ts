@@ -0,0 +1,34 @@
+import { listen } from "@tauri-apps/api/event";
+import { toolsPage } from "../pages";
+import { toolsService } from "../services";
+import type { BaselineDriftReport, ToolStatus } from "../types";
+import { showGlobalNotice } from "./shell-ui";
+
+/** 与后端 baseline::BASELINE_DRIFT_EVENT 保持一致 */
+const BASELINE_DRIFT_EVENT = "baseline-drift";
+const BASELINE_DRIFT_NOTICE_MS = 8000;
+/** 与后端 tools::TOOLS_REPROBED_EVENT 保持一致 */
+const TOOLS_REPROBED_EVENT = "tools-reprobed";
+
+function describeBaselineDrift(report: BaselineDriftReport): string {
+  const drifted = report.entries
+    .filter((entry) => entry.status !== "compliant")
+    .map((entry) => entry.key);
+  const preview = drifted.slice(0, 3).join("、");
+  const more = drifted.length > 3 ? ` 等 ${drifted.length} 项` : "";
+  return `${report.baselineName || report.source}：${preview}${more} 与团队基线不一致`;
+}
+
+/**
+ * 订阅后端主动推送的事件，应用启动时调用一次
+ */
+export async function initBackendEvents(): Promise<void> {
+  await listen<BaselineDriftReport>(BASELINE_DRIFT_EVENT, (event) => {
+    showGlobalNotice("检测到环境漂移", describeBaselineDrift(event.payload), "error", BASELINE_DRIFT_NOTICE_MS);
+  });
+
+  await listen<ToolStatus[]>(TOOLS_REPROBED_EVENT, (event) => {
+    toolsService.applyReprobedTools(event.payload);
+    toolsPage.refreshGrid();
+  });
+}
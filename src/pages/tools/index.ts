@@ -2,6 +2,7 @@
  * ToolsPage 导出
  */
 
+import { appState } from "../../state";
 import { ToolsCoordinator } from "./ToolsCoordinator";
 
 export { ToolsRenderer } from "./ToolsRenderer";
@@ -17,6 +18,15 @@ export class ToolsPage {
   async render(container: HTMLElement, renderEpoch?: number): Promise<void> {
     await this.coordinator.render(container, renderEpoch);
   }
+
+  /**
+   * 工具数据在页面外更新（如后台重新探测）后重绘网格
+   */
+  refreshGrid(): void {
+    if (appState.currentPage === "tools") {
+      this.coordinator.renderGrid();
+    }
+  }
 }
 
 export const toolsPage = new ToolsPage();
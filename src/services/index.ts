@@ -7,5 +7,6 @@ export { ToolsService, toolsService } from "./tools-service";
 export { DockerService, dockerService } from "./docker-service";
 export { DeployService, deployService } from "./deploy-service";
 export { DockerVersionService, dockerVersionService } from "./docker-version-service";
+export { RustupService, rustupService } from "./rustup-service";
 export { installPathPolicy } from "./install-path-policy";
 export type { DockerOverviewMode } from "./docker-service";
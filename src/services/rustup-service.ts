@@ -0,0 +1,55 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  CommandResponse,
+  RustupAction,
+  RustupActionResult,
+  RustupKind,
+  RustupOutputLine,
+  RustupStatus,
+} from "../types";
+
+/** 与后端 rustup::RUSTUP_OUTPUT_EVENT 保持一致 */
+const RUSTUP_OUTPUT_EVENT = "rustup-output";
+
+export class RustupService {
+  async getStatus(toolchain: string | null = null, projectPath: string | null = null): Promise<CommandResponse<RustupStatus>> {
+    return invoke<CommandResponse<RustupStatus>>("get_rustup_status", {
+      toolchain,
+      projectPath,
+    });
+  }
+
+  /**
+   * 执行期间订阅 rustup-output，只转发属于本次操作的输出行
+   */
+  async runAction(
+    kind: RustupKind,
+    action: RustupAction,
+    name: string,
+    toolchain: string | null = null,
+    onLine?: (line: RustupOutputLine) => void,
+  ): Promise<CommandResponse<RustupActionResult>> {
+    const unlisten = onLine
+      ? await listen<RustupOutputLine>(RUSTUP_OUTPUT_EVENT, (event) => {
+          const line = event.payload;
+          if (line.kind === kind && line.action === action && line.name === name.trim()) {
+            onLine(line);
+          }
+        })
+      : null;
+
+    try {
+      return await invoke<CommandResponse<RustupActionResult>>("run_rustup_action", {
+        kind,
+        action,
+        name,
+        toolchain,
+      });
+    } finally {
+      unlisten?.();
+    }
+  }
+}
+
+export const rustupService = new RustupService();
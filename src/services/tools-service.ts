@@ -205,13 +205,29 @@ export class ToolsService {
     toolsState.updateCategories();
   }
 
+  /**
+   * 合并后台重新探测的结果：超时的工具由后端用更长时间重试后推送
+   */
+  applyReprobedTools(reprobed: ToolStatus[]): void {
+    const updates = new Map(reprobed.map((tool) => [toolsState.getToolIdentity(tool), tool]));
+    toolsState.dataCache = toolsState.dataCache.map(
+      (tool) => updates.get(toolsState.getToolIdentity(tool)) ?? tool,
+    );
+    toolsState.updateCategories();
+  }
+
   /**
    * 安装工具
    */
-  async installTool(itemKey: string, installPath: string | null): Promise<CommandResponse<InstallResult>> {
+  async installTool(
+    itemKey: string,
+    installPath: string | null,
+    projectPath: string | null = null,
+  ): Promise<CommandResponse<InstallResult>> {
     const response = await invoke<CommandResponse<InstallResult>>("install_market_item", {
       itemKey,
       installPath: installPath?.trim() || null,
+      projectPath,
     });
 
     return response;
@@ -220,9 +236,10 @@ export class ToolsService {
   /**
    * 卸载工具
    */
-  async uninstallTool(itemKey: string): Promise<CommandResponse<UninstallResult>> {
+  async uninstallTool(itemKey: string, projectPath: string | null = null): Promise<CommandResponse<UninstallResult>> {
     const response = await invoke<CommandResponse<UninstallResult>>("uninstall_market_item", {
       itemKey,
+      projectPath,
     });
 
     return response;
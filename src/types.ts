@@ -33,17 +33,58 @@ export interface SystemSnapshot {
   usedMemoryGb: number;
   memoryUsagePercent: number;
   disks: DiskSnapshot[];
+  sensors?: HardwareSensors | null;
+  environmentKind?: string | null;
+  effectiveLimits?: EffectiveResourceLimits | null;
   sampleMode?: "quick" | "precise";
   sampledAtMs?: number;
   isStale?: boolean;
 }
 
+export interface EffectiveResourceLimits {
+  cgroupVersion: number | null;
+  cpuQuotaCores: number | null;
+  memoryLimitGb: number | null;
+  effectiveCpuCores: number | null;
+  effectiveMemoryGb: number | null;
+}
+
+export interface TemperatureReading {
+  source: "thermal" | "hwmon";
+  label: string;
+  celsius: number;
+  highCelsius: number | null;
+  criticalCelsius: number | null;
+}
+
+export interface FanReading {
+  label: string;
+  rpm: number;
+}
+
+export interface BatteryStatus {
+  name: string;
+  chargePercent: number | null;
+  charging: boolean | null;
+  status: string | null;
+  timeRemainingMinutes: number | null;
+}
+
+export interface HardwareSensors {
+  temperatures: TemperatureReading[];
+  fans: FanReading[];
+  batteries: BatteryStatus[];
+  acOnline: boolean | null;
+  thermalThrottling: boolean;
+}
+
 export interface SystemRealtimeSnapshot {
   uptimeSeconds: number;
   cpuUsagePercent: number;
   totalMemoryGb: number;
   usedMemoryGb: number;
   memoryUsagePercent: number;
+  thermalThrottling?: boolean | null;
   sampleMode?: "quick" | "precise";
   sampledAtMs?: number;
   isStale?: boolean;
@@ -58,6 +99,37 @@ export interface ToolStatus {
   details: string | null;
   installKey: string | null;
   installPath: string | null;
+  parsedVersion?: ToolVersion | null;
+  versionManager?: ManagedToolInfo | null;
+  lifecycle?: ToolLifecycle | null;
+  timedOut?: boolean;
+}
+
+export interface ToolLifecycle {
+  product: string;
+  productName: string;
+  cycle: string;
+  state: "supported" | "security_only" | "eol";
+  releaseDate: string | null;
+  supportEnd: string | null;
+  eolDate: string | null;
+  lts: boolean;
+}
+
+export interface ManagedToolInfo {
+  manager: string;
+  managerName: string;
+  activeVersion: string | null;
+  otherVersions: string[];
+}
+
+export interface ToolVersion {
+  raw: string;
+  semver: string | null;
+  major: number | null;
+  minor: number | null;
+  patch: number | null;
+  build: string | null;
 }
 
 export interface DockerCommandResult {
@@ -283,7 +355,7 @@ export interface ImageSelection {
   projectPath?: string;
 }
 
-export type VersionSourceKind = "dockerHub" | "githubRelease" | "localGit" | "customApi";
+export type VersionSourceKind = "dockerHub" | "githubRelease" | "localGit" | "customApi" | "nodeDist" | "goDev" | "pythonOrg";
 
 export interface DockerHubSourceConfig {
   namespace: string;
@@ -417,3 +489,87 @@ export interface UpdateImageAndRestartResponse {
   stepLogs: UpdateStepLog[];
   rollback: RollbackResult;
 }
+
+export type BaselineDriftStatus = "compliant" | "missing" | "too_old" | "too_new" | "unexpected" | "mismatch" | "unknown";
+
+export interface BaselineDriftEntry {
+  kind: "tool" | "env" | "git";
+  key: string;
+  expected: string;
+  actual: string | null;
+  status: BaselineDriftStatus;
+  toolName: string | null;
+  installKey: string | null;
+  message: string | null;
+}
+
+export interface BaselineDriftReport {
+  source: string;
+  baselineName: string | null;
+  compliant: boolean;
+  driftCount: number;
+  entries: BaselineDriftEntry[];
+  checkedAtMs: number;
+}
+
+export interface RustToolchain {
+  name: string;
+  isDefault: boolean;
+  isActive: boolean;
+}
+
+export interface RustTarget {
+  name: string;
+  installed: boolean;
+}
+
+export interface RustComponent {
+  name: string;
+  target: string | null;
+  installed: boolean;
+}
+
+export interface RustOverride {
+  path: string;
+  toolchain: string;
+}
+
+export interface RustActiveToolchain {
+  toolchain: string;
+  reason: string | null;
+  directory: string | null;
+}
+
+export interface RustupStatus {
+  toolchains: RustToolchain[];
+  toolchain: string | null;
+  targets: RustTarget[];
+  targetsError: string | null;
+  components: RustComponent[];
+  componentsError: string | null;
+  overrides: RustOverride[];
+  overridesError: string | null;
+  active: RustActiveToolchain | null;
+}
+
+export type RustupKind = "toolchain" | "target" | "component";
+export type RustupAction = "install" | "remove";
+
+export interface RustupActionResult {
+  kind: RustupKind;
+  action: RustupAction;
+  name: string;
+  toolchain: string | null;
+  command: string;
+  stdout: string;
+  stderr: string;
+  exitCode: number;
+}
+
+export interface RustupOutputLine {
+  kind: RustupKind;
+  action: RustupAction;
+  name: string;
+  stream: "stdout" | "stderr";
+  line: string;
+}